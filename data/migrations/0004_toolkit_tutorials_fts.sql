@@ -0,0 +1,85 @@
+-- TOOLKIT FTS
+CREATE VIRTUAL TABLE toolkit_fts USING fts5(
+    title,
+    description,
+    overview,
+    key_capabilities,
+    why_it_is_included,
+    resources_on_getting_started,
+    license_and_compliance,
+    versioning_and_community_info,
+    content='toolkit',
+    content_rowid='id',
+    tokenize='unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER toolkit_ai AFTER INSERT ON toolkit BEGIN
+    INSERT INTO toolkit_fts(rowid, title, description, overview, key_capabilities,
+                            why_it_is_included, resources_on_getting_started,
+                            license_and_compliance, versioning_and_community_info)
+    VALUES (new.id, new.title, new.description, COALESCE(new.overview, ''),
+            COALESCE(new.key_capabilities, ''), COALESCE(new.why_it_is_included, ''),
+            COALESCE(new.resources_on_getting_started, ''),
+            COALESCE(new.license_and_compliance, ''),
+            COALESCE(new.versioning_and_community_info, ''));
+END;
+
+CREATE TRIGGER toolkit_ad AFTER DELETE ON toolkit BEGIN
+    INSERT INTO toolkit_fts(toolkit_fts, rowid, title, description, overview, key_capabilities,
+                            why_it_is_included, resources_on_getting_started,
+                            license_and_compliance, versioning_and_community_info)
+    VALUES ('delete', old.id, old.title, old.description, COALESCE(old.overview, ''),
+            COALESCE(old.key_capabilities, ''), COALESCE(old.why_it_is_included, ''),
+            COALESCE(old.resources_on_getting_started, ''),
+            COALESCE(old.license_and_compliance, ''),
+            COALESCE(old.versioning_and_community_info, ''));
+END;
+
+CREATE TRIGGER toolkit_au AFTER UPDATE ON toolkit BEGIN
+    INSERT INTO toolkit_fts(toolkit_fts, rowid, title, description, overview, key_capabilities,
+                            why_it_is_included, resources_on_getting_started,
+                            license_and_compliance, versioning_and_community_info)
+    VALUES ('delete', old.id, old.title, old.description, COALESCE(old.overview, ''),
+            COALESCE(old.key_capabilities, ''), COALESCE(old.why_it_is_included, ''),
+            COALESCE(old.resources_on_getting_started, ''),
+            COALESCE(old.license_and_compliance, ''),
+            COALESCE(old.versioning_and_community_info, ''));
+    INSERT INTO toolkit_fts(rowid, title, description, overview, key_capabilities,
+                            why_it_is_included, resources_on_getting_started,
+                            license_and_compliance, versioning_and_community_info)
+    VALUES (new.id, new.title, new.description, COALESCE(new.overview, ''),
+            COALESCE(new.key_capabilities, ''), COALESCE(new.why_it_is_included, ''),
+            COALESCE(new.resources_on_getting_started, ''),
+            COALESCE(new.license_and_compliance, ''),
+            COALESCE(new.versioning_and_community_info, ''));
+END;
+
+-- TUTORIALS FTS
+CREATE VIRTUAL TABLE tutorials_fts USING fts5(
+    title,
+    description,
+    content='tutorials',
+    content_rowid='id',
+    tokenize='unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER tutorials_ai AFTER INSERT ON tutorials BEGIN
+    INSERT INTO tutorials_fts(rowid, title, description)
+    VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER tutorials_ad AFTER DELETE ON tutorials BEGIN
+    INSERT INTO tutorials_fts(tutorials_fts, rowid, title, description)
+    VALUES ('delete', old.id, old.title, old.description);
+END;
+
+CREATE TRIGGER tutorials_au AFTER UPDATE ON tutorials BEGIN
+    INSERT INTO tutorials_fts(tutorials_fts, rowid, title, description)
+    VALUES ('delete', old.id, old.title, old.description);
+    INSERT INTO tutorials_fts(rowid, title, description)
+    VALUES (new.id, new.title, new.description);
+END;
+
+-- Databases seeded before this migration already have rows; index them.
+INSERT INTO toolkit_fts(toolkit_fts) VALUES ('rebuild');
+INSERT INTO tutorials_fts(tutorials_fts) VALUES ('rebuild');
@@ -0,0 +1,127 @@
+-- SEARCH SUGGESTIONS
+-- One row per suggestible label: sector / organization / tag names and
+-- dataset / model / usecase titles. The prefix index makes `agri*` lookups
+-- cheap; `kind` + `item_id` point back at the source row.
+CREATE VIRTUAL TABLE search_suggest USING fts5(
+    label,
+    kind    UNINDEXED,
+    item_id UNINDEXED,
+    slug    UNINDEXED,
+    prefix='2 3 4',
+    tokenize='unicode61 remove_diacritics 2'
+);
+
+-- SECTORS
+CREATE TRIGGER sectors_suggest_ai AFTER INSERT ON sectors BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.name, 'sector', new.id, new.slug);
+END;
+
+CREATE TRIGGER sectors_suggest_ad AFTER DELETE ON sectors BEGIN
+    DELETE FROM search_suggest WHERE kind = 'sector' AND item_id = old.id;
+END;
+
+CREATE TRIGGER sectors_suggest_au AFTER UPDATE OF name, slug ON sectors BEGIN
+    DELETE FROM search_suggest WHERE kind = 'sector' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.name, 'sector', new.id, new.slug);
+END;
+
+-- ORGANIZATIONS
+CREATE TRIGGER organizations_suggest_ai AFTER INSERT ON organizations
+WHEN new.deleted_at IS NULL BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.name, 'organization', new.id, new.slug);
+END;
+
+CREATE TRIGGER organizations_suggest_ad AFTER DELETE ON organizations BEGIN
+    DELETE FROM search_suggest WHERE kind = 'organization' AND item_id = old.id;
+END;
+
+CREATE TRIGGER organizations_suggest_au AFTER UPDATE OF name, slug, deleted_at ON organizations BEGIN
+    DELETE FROM search_suggest WHERE kind = 'organization' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT new.name, 'organization', new.id, new.slug WHERE new.deleted_at IS NULL;
+END;
+
+-- TAGS
+CREATE TRIGGER tags_suggest_ai AFTER INSERT ON tags BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.name, 'tag', new.id, new.slug);
+END;
+
+CREATE TRIGGER tags_suggest_ad AFTER DELETE ON tags BEGIN
+    DELETE FROM search_suggest WHERE kind = 'tag' AND item_id = old.id;
+END;
+
+CREATE TRIGGER tags_suggest_au AFTER UPDATE OF name, slug ON tags BEGIN
+    DELETE FROM search_suggest WHERE kind = 'tag' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.name, 'tag', new.id, new.slug);
+END;
+
+-- DATASETS
+CREATE TRIGGER datasets_suggest_ai AFTER INSERT ON datasets
+WHEN new.deleted_at IS NULL BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.title, 'dataset', new.id, NULL);
+END;
+
+CREATE TRIGGER datasets_suggest_ad AFTER DELETE ON datasets BEGIN
+    DELETE FROM search_suggest WHERE kind = 'dataset' AND item_id = old.id;
+END;
+
+CREATE TRIGGER datasets_suggest_au AFTER UPDATE OF title, deleted_at ON datasets BEGIN
+    DELETE FROM search_suggest WHERE kind = 'dataset' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT new.title, 'dataset', new.id, NULL WHERE new.deleted_at IS NULL;
+END;
+
+-- MODELS
+CREATE TRIGGER models_suggest_ai AFTER INSERT ON models
+WHEN new.deleted_at IS NULL BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.title, 'model', new.id, NULL);
+END;
+
+CREATE TRIGGER models_suggest_ad AFTER DELETE ON models BEGIN
+    DELETE FROM search_suggest WHERE kind = 'model' AND item_id = old.id;
+END;
+
+CREATE TRIGGER models_suggest_au AFTER UPDATE OF title, deleted_at ON models BEGIN
+    DELETE FROM search_suggest WHERE kind = 'model' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT new.title, 'model', new.id, NULL WHERE new.deleted_at IS NULL;
+END;
+
+-- USECASES
+CREATE TRIGGER usecases_suggest_ai AFTER INSERT ON usecases
+WHEN new.deleted_at IS NULL BEGIN
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    VALUES (new.title, 'usecase', new.id, NULL);
+END;
+
+CREATE TRIGGER usecases_suggest_ad AFTER DELETE ON usecases BEGIN
+    DELETE FROM search_suggest WHERE kind = 'usecase' AND item_id = old.id;
+END;
+
+CREATE TRIGGER usecases_suggest_au AFTER UPDATE OF title, deleted_at ON usecases BEGIN
+    DELETE FROM search_suggest WHERE kind = 'usecase' AND item_id = old.id;
+    INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT new.title, 'usecase', new.id, NULL WHERE new.deleted_at IS NULL;
+END;
+
+-- Backfill rows that existed before this migration (the sector lookups from
+-- 0003 at minimum; everything else on an already-seeded database).
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT name, 'sector', id, slug FROM sectors;
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT name, 'organization', id, slug FROM organizations WHERE deleted_at IS NULL;
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT name, 'tag', id, slug FROM tags;
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT title, 'dataset', id, NULL FROM datasets WHERE deleted_at IS NULL;
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT title, 'model', id, NULL FROM models WHERE deleted_at IS NULL;
+INSERT INTO search_suggest(label, kind, item_id, slug)
+    SELECT title, 'usecase', id, NULL FROM usecases WHERE deleted_at IS NULL;
@@ -0,0 +1,26 @@
+-- SEARCH SYNONYMS (admin-managed, applied in both directions at query time)
+CREATE TABLE search_synonyms (
+    id         INTEGER PRIMARY KEY,
+    term       TEXT NOT NULL,
+    synonym    TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (term, synonym),
+    CHECK (term <> synonym)
+);
+
+CREATE INDEX idx_search_synonyms_synonym ON search_synonyms(synonym);
+
+INSERT INTO search_synonyms (term, synonym) VALUES
+    ('ml',     'machine learning'),
+    ('ai',     'artificial intelligence'),
+    ('nlp',    'natural language processing'),
+    ('llm',    'large language model'),
+    ('asr',    'speech recognition'),
+    ('ocr',    'optical character recognition'),
+    ('kisan',  'farmer'),
+    ('krishi', 'agriculture');
+
+-- FTS VOCABULARIES (term -> document count), used for "did you mean" corrections
+CREATE VIRTUAL TABLE datasets_fts_vocab USING fts5vocab('datasets_fts', 'row');
+CREATE VIRTUAL TABLE models_fts_vocab   USING fts5vocab('models_fts', 'row');
+CREATE VIRTUAL TABLE usecases_fts_vocab USING fts5vocab('usecases_fts', 'row');
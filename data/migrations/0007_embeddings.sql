@@ -0,0 +1,16 @@
+-- EMBEDDINGS (semantic search)
+-- One vector per artifact, produced by whichever embedder is configured.
+-- `model` identifies the embedder so switching models triggers a re-embed;
+-- `source_updated_at` mirrors the artifact's updated_at to detect edits.
+CREATE TABLE embeddings (
+    kind              TEXT NOT NULL CHECK (kind IN ('dataset', 'model', 'usecase', 'article')),
+    item_id           INTEGER NOT NULL,
+    model             TEXT NOT NULL,
+    dim               INTEGER NOT NULL,
+    vector            BLOB NOT NULL,          -- little-endian f32 x dim, L2-normalized
+    source_updated_at TEXT NOT NULL,
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (kind, item_id)
+);
+
+CREATE INDEX idx_embeddings_model ON embeddings(model);
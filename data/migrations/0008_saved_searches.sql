@@ -0,0 +1,37 @@
+-- SAVED SEARCHES (a named ListQuery the owner wants to be alerted about)
+CREATE TABLE saved_searches (
+    id              INTEGER PRIMARY KEY,
+    user_id         INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    kind            TEXT NOT NULL CHECK (kind IN ('dataset', 'model', 'usecase')),
+    search          TEXT,
+    sector          TEXT,
+    organization_id INTEGER REFERENCES organizations(id) ON DELETE CASCADE,
+    -- artifacts created at or after this point are checked on the next run
+    last_checked_at TEXT NOT NULL DEFAULT (datetime('now')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, name)
+);
+
+CREATE INDEX idx_saved_searches_user ON saved_searches(user_id);
+
+-- NOTIFICATIONS
+CREATE TABLE notifications (
+    id              INTEGER PRIMARY KEY,
+    user_id         INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind            TEXT NOT NULL,
+    title           TEXT NOT NULL,
+    body            TEXT,
+    artifact_kind   TEXT CHECK (artifact_kind IN ('dataset', 'model', 'usecase', 'article')),
+    artifact_id     INTEGER,
+    saved_search_id INTEGER REFERENCES saved_searches(id) ON DELETE CASCADE,
+    read_at         TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, created_at);
+
+-- One alert per artifact per saved search, however often the check overlaps.
+CREATE UNIQUE INDEX idx_notifications_saved_search_hit
+    ON notifications(saved_search_id, artifact_kind, artifact_id)
+    WHERE saved_search_id IS NOT NULL;
@@ -0,0 +1,32 @@
+-- SEARCH ANALYTICS
+-- One row per search request that carried query text. `surface` is the
+-- endpoint searched (datasets, models, usecases, toolkit, tutorials, search);
+-- `normalized_query` (lowercased, whitespace-collapsed) is what reports group by.
+CREATE TABLE search_events (
+    id               INTEGER PRIMARY KEY,
+    user_id          INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    surface          TEXT NOT NULL,
+    query            TEXT NOT NULL,
+    normalized_query TEXT NOT NULL,
+    filters          TEXT NOT NULL DEFAULT '{}',   -- JSON object of non-query params
+    result_count     INTEGER NOT NULL,
+    latency_ms       INTEGER NOT NULL,
+    corrected_query  TEXT,                         -- set when "did you mean" kicked in
+    created_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_search_events_created ON search_events(created_at);
+CREATE INDEX idx_search_events_query   ON search_events(normalized_query);
+
+-- Results opened from a logged search.
+CREATE TABLE search_clicks (
+    id              INTEGER PRIMARY KEY,
+    search_event_id INTEGER NOT NULL REFERENCES search_events(id) ON DELETE CASCADE,
+    artifact_kind   TEXT NOT NULL
+                    CHECK (artifact_kind IN ('dataset', 'model', 'usecase', 'article', 'toolkit', 'tutorial')),
+    artifact_id     INTEGER NOT NULL,
+    position        INTEGER,                       -- 1-based rank in the result list
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_search_clicks_event ON search_clicks(search_event_id);
@@ -0,0 +1,17 @@
+-- DATASET FILES
+-- Uploaded files attached to a dataset. The bytes live in the configured
+-- storage backend under `storage_key`; this row is the catalog entry.
+CREATE TABLE dataset_files (
+    id                  INTEGER PRIMARY KEY,
+    dataset_id          INTEGER NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    name                TEXT NOT NULL,
+    size_bytes          INTEGER NOT NULL,
+    media_type          TEXT NOT NULL,
+    sha256              TEXT NOT NULL,
+    storage_key         TEXT NOT NULL UNIQUE,
+    uploaded_by_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (dataset_id, name)
+);
+
+CREATE INDEX idx_dataset_files_dataset ON dataset_files(dataset_id);
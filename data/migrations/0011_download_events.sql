@@ -0,0 +1,15 @@
+-- DOWNLOAD EVENTS
+-- One row per completed-from-the-start download (a Range request resuming
+-- mid-file doesn't count again). `downloads_count` on datasets/models is
+-- incremented in the same transaction as the insert.
+CREATE TABLE download_events (
+    id              INTEGER PRIMARY KEY,
+    user_id         INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    artifact_kind   TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model')),
+    artifact_id     INTEGER NOT NULL,
+    dataset_file_id INTEGER REFERENCES dataset_files(id) ON DELETE SET NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_download_events_user     ON download_events(user_id, artifact_kind);
+CREATE INDEX idx_download_events_artifact ON download_events(artifact_kind, artifact_id);
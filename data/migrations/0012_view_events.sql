@@ -0,0 +1,20 @@
+-- VIEW EVENTS
+-- One row per counted detail-page view. Repeat views by the same viewer
+-- within the dedupe window (VIEW_DEDUPE_SECS) are not recorded, so each row
+-- here corresponds to one increment of the artifact's `views_count`.
+-- The viewer is the user when known, otherwise the client's session id.
+CREATE TABLE view_events (
+    id            INTEGER PRIMARY KEY,
+    user_id       INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    session_id    TEXT,
+    artifact_kind TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model', 'usecase')),
+    artifact_id   INTEGER NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_view_events_user     ON view_events(user_id, created_at);
+CREATE INDEX idx_view_events_session  ON view_events(session_id, created_at);
+CREATE INDEX idx_view_events_artifact ON view_events(artifact_kind, artifact_id, created_at);
+
+-- Use cases had no counter; datasets and models already do.
+ALTER TABLE usecases ADD COLUMN views_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,15 @@
+-- LIKES (one per user per artifact; counters on the artifact rows are
+-- maintained by the like/unlike handlers in the same transaction)
+CREATE TABLE likes (
+    user_id       INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    artifact_kind TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model', 'usecase', 'article')),
+    artifact_id   INTEGER NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, artifact_kind, artifact_id)
+);
+
+CREATE INDEX idx_likes_artifact ON likes(artifact_kind, artifact_id);
+
+-- Datasets and models already carry likes_count.
+ALTER TABLE usecases ADD COLUMN likes_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE articles ADD COLUMN likes_count INTEGER NOT NULL DEFAULT 0;
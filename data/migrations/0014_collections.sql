@@ -0,0 +1,29 @@
+-- COLLECTIONS (user-curated, ordered lists of catalog entries; public ones are
+-- shared by slug and can be forked into a private copy)
+CREATE TABLE collections (
+    id             INTEGER PRIMARY KEY,
+    user_id        INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name           TEXT NOT NULL,
+    slug           TEXT NOT NULL UNIQUE,
+    description    TEXT,
+    visibility     TEXT NOT NULL DEFAULT 'private' CHECK (visibility IN ('private', 'public')),
+    forked_from_id INTEGER REFERENCES collections(id) ON DELETE SET NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_collections_user ON collections(user_id);
+
+CREATE TABLE collection_items (
+    id            INTEGER PRIMARY KEY,
+    collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    artifact_kind TEXT NOT NULL
+                  CHECK (artifact_kind IN ('dataset', 'model', 'usecase', 'article', 'toolkit')),
+    artifact_id   INTEGER NOT NULL,
+    position      INTEGER NOT NULL,
+    note          TEXT,
+    added_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (collection_id, artifact_kind, artifact_id)
+);
+
+CREATE INDEX idx_collection_items_order ON collection_items(collection_id, position);
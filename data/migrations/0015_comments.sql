@@ -0,0 +1,24 @@
+-- COMMENTS (threaded discussion on catalog entries; bodies are markdown source)
+-- Top-level comments start a thread (root_id NULL); replies point at their
+-- parent and at the thread root, which is where moderators lock a thread.
+CREATE TABLE comments (
+    id                INTEGER PRIMARY KEY,
+    artifact_kind     TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model', 'usecase', 'article')),
+    artifact_id       INTEGER NOT NULL,
+    user_id           INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    parent_id         INTEGER REFERENCES comments(id) ON DELETE CASCADE,
+    root_id           INTEGER REFERENCES comments(id) ON DELETE CASCADE,
+    body              TEXT NOT NULL,
+    edited_at         TEXT,
+    hidden_at         TEXT,
+    hidden_by_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    locked_at         TEXT,
+    locked_by_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now')),
+    deleted_at        TEXT
+);
+
+CREATE INDEX idx_comments_artifact ON comments(artifact_kind, artifact_id, created_at)
+    WHERE parent_id IS NULL;
+CREATE INDEX idx_comments_root ON comments(root_id, created_at);
+CREATE INDEX idx_comments_parent ON comments(parent_id);
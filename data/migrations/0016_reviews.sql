@@ -0,0 +1,20 @@
+-- REVIEWS (1-5 star rating with optional text; one per user per dataset/model)
+CREATE TABLE reviews (
+    id            INTEGER PRIMARY KEY,
+    user_id       INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    artifact_kind TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model')),
+    artifact_id   INTEGER NOT NULL,
+    rating        INTEGER NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    body          TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, artifact_kind, artifact_id)
+);
+
+CREATE INDEX idx_reviews_artifact ON reviews(artifact_kind, artifact_id, updated_at);
+
+-- Aggregates recomputed from `reviews` whenever one changes.
+ALTER TABLE datasets ADD COLUMN rating_average REAL;
+ALTER TABLE datasets ADD COLUMN rating_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE models ADD COLUMN rating_average REAL;
+ALTER TABLE models ADD COLUMN rating_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+-- FOLLOWS (organizations, sectors, tags and other users feeding /api/feed)
+CREATE TABLE follows (
+    user_id     INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_kind TEXT NOT NULL CHECK (target_kind IN ('organization', 'sector', 'tag', 'user')),
+    target_id   INTEGER NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, target_kind, target_id)
+);
+
+CREATE INDEX idx_follows_target ON follows(target_kind, target_id);
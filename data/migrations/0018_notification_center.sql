@@ -0,0 +1,9 @@
+-- NOTIFICATION CENTER (the notifications table itself arrived with saved searches)
+
+CREATE INDEX idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+-- One alert per user per artifact published by something they follow, however
+-- many of their follows it matches.
+CREATE UNIQUE INDEX idx_notifications_follow_hit
+    ON notifications(user_id, artifact_kind, artifact_id)
+    WHERE kind = 'followed_activity';
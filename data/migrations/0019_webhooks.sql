@@ -0,0 +1,153 @@
+-- WEBHOOKS (admin-registered subscriptions to catalog events)
+--
+-- Triggers append dataset/model changes to the `catalog_events` outbox, so
+-- every write path is covered. The dispatcher job fans each event out to the
+-- matching subscriptions as `webhook_deliveries` and retries failed ones.
+--   created   row inserted
+--   updated   a catalog field changed (counters and ratings don't count)
+--   published visibility changed to 'Open' (also reported as updated)
+--   deleted   soft-deleted (deleted_at set) or removed outright
+
+CREATE TABLE catalog_events (
+    id            INTEGER PRIMARY KEY,
+    event         TEXT NOT NULL,  -- e.g. 'dataset.created'
+    artifact_kind TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model')),
+    artifact_id   INTEGER NOT NULL,
+    dispatched_at TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_catalog_events_pending ON catalog_events(id) WHERE dispatched_at IS NULL;
+
+CREATE TABLE webhooks (
+    id                 INTEGER PRIMARY KEY,
+    url                TEXT NOT NULL,
+    description        TEXT,
+    events             TEXT NOT NULL,  -- JSON array of event names
+    secret             TEXT NOT NULL,  -- HMAC-SHA256 key for X-Aikosh-Signature
+    active             INTEGER NOT NULL DEFAULT 1,
+    created_by_user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at         TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE webhook_deliveries (
+    id               INTEGER PRIMARY KEY,
+    webhook_id       INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    catalog_event_id INTEGER REFERENCES catalog_events(id) ON DELETE SET NULL,
+    event            TEXT NOT NULL,
+    payload          TEXT NOT NULL,  -- JSON body, fixed when the delivery is created
+    status           TEXT NOT NULL DEFAULT 'pending'
+                     CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    last_attempt_at  TEXT,
+    response_status  INTEGER,
+    response_body    TEXT,
+    error            TEXT,
+    redelivery_of_id INTEGER REFERENCES webhook_deliveries(id) ON DELETE SET NULL,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, id);
+
+-- DATASET EVENTS
+CREATE TRIGGER datasets_event_created AFTER INSERT ON datasets BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('dataset.created', 'dataset', new.id);
+END;
+
+CREATE TRIGGER datasets_event_updated AFTER UPDATE OF
+    title, description, about_dataset, image_url, organization_id, sector_id, license,
+    geographical_coverage, author, data_quality_score, dataset_type, frequency,
+    time_granularity, year_range, data_collected_at, visibility, hosted, data_type,
+    data_collection_method, tags_text
+ON datasets
+WHEN new.deleted_at IS NULL AND (
+      old.title IS NOT new.title OR old.description IS NOT new.description OR
+      old.about_dataset IS NOT new.about_dataset OR old.image_url IS NOT new.image_url OR
+      old.organization_id IS NOT new.organization_id OR old.sector_id IS NOT new.sector_id OR
+      old.license IS NOT new.license OR
+      old.geographical_coverage IS NOT new.geographical_coverage OR
+      old.author IS NOT new.author OR old.data_quality_score IS NOT new.data_quality_score OR
+      old.dataset_type IS NOT new.dataset_type OR old.frequency IS NOT new.frequency OR
+      old.time_granularity IS NOT new.time_granularity OR
+      old.year_range IS NOT new.year_range OR
+      old.data_collected_at IS NOT new.data_collected_at OR
+      old.visibility IS NOT new.visibility OR old.hosted IS NOT new.hosted OR
+      old.data_type IS NOT new.data_type OR
+      old.data_collection_method IS NOT new.data_collection_method OR
+      old.tags_text IS NOT new.tags_text
+)
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('dataset.updated', 'dataset', new.id);
+END;
+
+CREATE TRIGGER datasets_event_published AFTER UPDATE OF visibility ON datasets
+WHEN new.deleted_at IS NULL AND old.visibility <> 'Open' AND new.visibility = 'Open'
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('dataset.published', 'dataset', new.id);
+END;
+
+CREATE TRIGGER datasets_event_soft_deleted AFTER UPDATE OF deleted_at ON datasets
+WHEN old.deleted_at IS NULL AND new.deleted_at IS NOT NULL
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('dataset.deleted', 'dataset', new.id);
+END;
+
+CREATE TRIGGER datasets_event_deleted AFTER DELETE ON datasets
+WHEN old.deleted_at IS NULL
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('dataset.deleted', 'dataset', old.id);
+END;
+
+-- MODEL EVENTS
+CREATE TRIGGER models_event_created AFTER INSERT ON models BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('model.created', 'model', new.id);
+END;
+
+CREATE TRIGGER models_event_updated AFTER UPDATE OF
+    title, description, about_model, image_url, organization_id, sector_id, license, hosted_by,
+    model_type, model_format, visibility, size, model_updated_at, tags_text
+ON models
+WHEN new.deleted_at IS NULL AND (
+      old.title IS NOT new.title OR old.description IS NOT new.description OR
+      old.about_model IS NOT new.about_model OR old.image_url IS NOT new.image_url OR
+      old.organization_id IS NOT new.organization_id OR old.sector_id IS NOT new.sector_id OR
+      old.license IS NOT new.license OR old.hosted_by IS NOT new.hosted_by OR
+      old.model_type IS NOT new.model_type OR old.model_format IS NOT new.model_format OR
+      old.visibility IS NOT new.visibility OR old.size IS NOT new.size OR
+      old.model_updated_at IS NOT new.model_updated_at OR old.tags_text IS NOT new.tags_text
+)
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('model.updated', 'model', new.id);
+END;
+
+CREATE TRIGGER models_event_published AFTER UPDATE OF visibility ON models
+WHEN new.deleted_at IS NULL AND old.visibility <> 'Open' AND new.visibility = 'Open'
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('model.published', 'model', new.id);
+END;
+
+CREATE TRIGGER models_event_soft_deleted AFTER UPDATE OF deleted_at ON models
+WHEN old.deleted_at IS NULL AND new.deleted_at IS NOT NULL
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('model.deleted', 'model', new.id);
+END;
+
+CREATE TRIGGER models_event_deleted AFTER DELETE ON models
+WHEN old.deleted_at IS NULL
+BEGIN
+    INSERT INTO catalog_events (event, artifact_kind, artifact_id)
+    VALUES ('model.deleted', 'model', old.id);
+END;
@@ -0,0 +1,28 @@
+-- EMAIL DIGESTS (opt-in daily/weekly summaries of followed sectors and tags)
+--
+-- A digest covers (last_sent_at, now]. The first one goes out a full period
+-- after opting in and covers the period before it. Windows with nothing new
+-- advance last_sent_at without sending; sent digests are logged below.
+
+CREATE TABLE digest_preferences (
+    user_id      INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    frequency    TEXT NOT NULL DEFAULT 'off' CHECK (frequency IN ('off', 'daily', 'weekly')),
+    opted_in_at  TEXT,
+    last_sent_at TEXT,
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_digest_preferences_on ON digest_preferences(frequency) WHERE frequency <> 'off';
+
+CREATE TABLE digest_log (
+    id           INTEGER PRIMARY KEY,
+    user_id      INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    frequency    TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end   TEXT NOT NULL,
+    item_count   INTEGER NOT NULL,
+    subject      TEXT NOT NULL,
+    sent_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_digest_log_user ON digest_log(user_id, id);
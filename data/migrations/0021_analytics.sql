@@ -0,0 +1,19 @@
+-- CHAT EVENTS
+-- One row per chatbot question, written as soon as the chatbot has answered
+-- (or failed). Only sizes are kept, not the conversation itself.
+CREATE TABLE chat_events (
+    id              INTEGER PRIMARY KEY,
+    user_id         INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    status          TEXT NOT NULL CHECK (status IN ('answered', 'failed')),
+    question_chars  INTEGER NOT NULL,
+    answer_chars    INTEGER NOT NULL DEFAULT 0,
+    latency_ms      INTEGER NOT NULL,  -- until the chatbot replied, not until streaming ended
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_chat_events_created ON chat_events(created_at);
+
+-- Admin analytics filter every event table by date.
+CREATE INDEX idx_download_events_created ON download_events(created_at);
+CREATE INDEX idx_view_events_created     ON view_events(created_at);
+CREATE INDEX idx_users_created           ON users(created_at);
@@ -0,0 +1,18 @@
+-- TRENDING SCORES
+-- Recomputed wholesale by the trending refresher from recent view, like and
+-- download events, each weighted and decayed exponentially by age. Entries
+-- with no recent activity have no row (score 0). `views`, `likes` and
+-- `downloads` are the raw event counts the score was computed from.
+CREATE TABLE trending_scores (
+    artifact_kind TEXT NOT NULL CHECK (artifact_kind IN ('dataset', 'model', 'usecase')),
+    artifact_id   INTEGER NOT NULL,
+    score         REAL NOT NULL,
+    views         INTEGER NOT NULL,
+    likes         INTEGER NOT NULL,
+    downloads     INTEGER NOT NULL,
+    computed_at   TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (artifact_kind, artifact_id)
+);
+
+CREATE INDEX idx_trending_scores_rank ON trending_scores(artifact_kind, score DESC);
+CREATE INDEX idx_likes_created ON likes(created_at);
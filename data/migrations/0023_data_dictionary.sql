@@ -0,0 +1,22 @@
+-- DATA DICTIONARY
+-- One row per column of a dataset, shown in `position` order. Names are
+-- unique per dataset ignoring case, which is also how the `column:` search
+-- qualifier matches them. `example_values` is a JSON array of strings.
+CREATE TABLE dataset_columns (
+    id             INTEGER PRIMARY KEY,
+    dataset_id     INTEGER NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    position       INTEGER NOT NULL,
+    name           TEXT NOT NULL,
+    data_type      TEXT NOT NULL
+                   CHECK (data_type IN ('string', 'integer', 'decimal', 'boolean',
+                                        'date', 'datetime', 'other')),
+    unit           TEXT,
+    description    TEXT NOT NULL DEFAULT '',
+    nullable       INTEGER NOT NULL DEFAULT 1,
+    example_values TEXT NOT NULL DEFAULT '[]',
+    created_at     TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE UNIQUE INDEX idx_dataset_columns_name ON dataset_columns(dataset_id, name COLLATE NOCASE);
+CREATE INDEX idx_dataset_columns_lookup ON dataset_columns(name COLLATE NOCASE, dataset_id);
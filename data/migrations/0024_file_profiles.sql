@@ -0,0 +1,15 @@
+-- DATASET FILE PROFILES
+-- Written once per uploaded file by the profiling job, which picks up every
+-- file without a row here (deleting the row queues the file again). Formats
+-- it can't read are recorded as 'unsupported'; unreadable files as 'failed'
+-- with the error. `columns` and `sample_rows` are JSON arrays.
+CREATE TABLE dataset_file_profiles (
+    file_id     INTEGER PRIMARY KEY REFERENCES dataset_files(id) ON DELETE CASCADE,
+    status      TEXT NOT NULL CHECK (status IN ('succeeded', 'failed', 'unsupported')),
+    format      TEXT CHECK (format IN ('csv', 'tsv', 'jsonl', 'parquet')),
+    row_count   INTEGER,
+    columns     TEXT NOT NULL DEFAULT '[]',
+    sample_rows TEXT NOT NULL DEFAULT '[]',
+    error       TEXT,
+    profiled_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
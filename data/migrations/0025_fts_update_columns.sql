@@ -0,0 +1,36 @@
+-- FTS UPDATE TRIGGERS
+-- The baseline `_au` triggers fire on any update, so every view, download,
+-- like or rating bump deleted and re-inserted the row's FTS entry. Recreate
+-- them to fire only when an indexed column changes, as 0005 does for the
+-- suggest triggers.
+DROP TRIGGER datasets_au;
+CREATE TRIGGER datasets_au AFTER UPDATE OF title, description, about_dataset, tags_text ON datasets BEGIN
+    INSERT INTO datasets_fts(datasets_fts, rowid, title, description, about_dataset, tags_text)
+    VALUES ('delete', old.id, old.title, old.description, COALESCE(old.about_dataset, ''), old.tags_text);
+    INSERT INTO datasets_fts(rowid, title, description, about_dataset, tags_text)
+    VALUES (new.id, new.title, new.description, COALESCE(new.about_dataset, ''), new.tags_text);
+END;
+
+DROP TRIGGER models_au;
+CREATE TRIGGER models_au AFTER UPDATE OF title, description, about_model, tags_text ON models BEGIN
+    INSERT INTO models_fts(models_fts, rowid, title, description, about_model, tags_text)
+    VALUES ('delete', old.id, old.title, old.description, COALESCE(old.about_model, ''), old.tags_text);
+    INSERT INTO models_fts(rowid, title, description, about_model, tags_text)
+    VALUES (new.id, new.title, new.description, COALESCE(new.about_model, ''), new.tags_text);
+END;
+
+DROP TRIGGER usecases_au;
+CREATE TRIGGER usecases_au AFTER UPDATE OF title, description, about_use_case, tags_text ON usecases BEGIN
+    INSERT INTO usecases_fts(usecases_fts, rowid, title, description, about_use_case, tags_text)
+    VALUES ('delete', old.id, old.title, old.description, COALESCE(old.about_use_case, ''), old.tags_text);
+    INSERT INTO usecases_fts(rowid, title, description, about_use_case, tags_text)
+    VALUES (new.id, new.title, new.description, COALESCE(new.about_use_case, ''), new.tags_text);
+END;
+
+DROP TRIGGER articles_au;
+CREATE TRIGGER articles_au AFTER UPDATE OF title, description, content ON articles BEGIN
+    INSERT INTO articles_fts(articles_fts, rowid, title, description, content)
+    VALUES ('delete', old.id, old.title, old.description, old.content);
+    INSERT INTO articles_fts(rowid, title, description, content)
+    VALUES (new.id, new.title, new.description, new.content);
+END;
@@ -0,0 +1,7 @@
+-- FILE PROFILE RETRIES
+-- A file that couldn't be fetched from storage gets no profile row, so the
+-- profiling job would pick it up first on every run. Count those attempts
+-- and back off between them; after too many the file is recorded as failed.
+-- Requeueing a file resets both.
+ALTER TABLE dataset_files ADD COLUMN profile_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE dataset_files ADD COLUMN profile_retry_at TEXT;
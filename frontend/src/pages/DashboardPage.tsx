@@ -23,9 +23,9 @@ export default function DashboardPage() {
                     getUseCases(),
                 ]);
                 setDashboard(dashRes.data);
-                setDatasets(datasetsRes.data);
-                setModels(modelsRes.data);
-                setUseCases(useCasesRes.data);
+                setDatasets(datasetsRes.data.items);
+                setModels(modelsRes.data.items);
+                setUseCases(useCasesRes.data.items);
             } catch (error) {
                 console.error('Error fetching dashboard data:', error);
             } finally {
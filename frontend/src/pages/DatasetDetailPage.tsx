@@ -1,6 +1,6 @@
 import { useEffect, useState } from 'react';
 import { useParams, Link } from 'react-router-dom';
-import { getDatasetById } from '../services/api';
+import { datasetFileDownloadUrl, getDatasetById } from '../services/api';
 import type { Dataset } from '../types';
 
 export default function DatasetDetailPage() {
@@ -140,9 +140,24 @@ export default function DatasetDetailPage() {
           </div>
 
           <div className="flex gap-4">
-            <button className="bg-orange-500 hover:bg-orange-600 text-white px-6 py-3 rounded-lg font-medium transition-colors">
-              Download Dataset
-            </button>
+            {dataset.files && dataset.files.length > 0 ? (
+              dataset.files.map((file) => (
+                <a
+                  key={file.id}
+                  href={datasetFileDownloadUrl(dataset.id, file.id)}
+                  className="bg-orange-500 hover:bg-orange-600 text-white px-6 py-3 rounded-lg font-medium transition-colors"
+                >
+                  Download {file.name}
+                </a>
+              ))
+            ) : (
+              <button
+                disabled
+                className="bg-orange-300 text-white px-6 py-3 rounded-lg font-medium cursor-not-allowed"
+              >
+                No files available
+              </button>
+            )}
             <button className="bg-gray-200 hover:bg-gray-300 text-gray-700 px-6 py-3 rounded-lg font-medium transition-colors">
               Bookmark
             </button>
@@ -13,7 +13,7 @@ export default function DatasetsPage() {
     const timer = setTimeout(async () => {
       try {
         const response = await getDatasets(searchTerm || undefined);
-        setDatasets(response.data);
+        setDatasets(response.data.items);
       } catch (error) {
         console.error('Error fetching datasets:', error);
       } finally {
@@ -13,7 +13,7 @@ export default function ModelsPage() {
     const timer = setTimeout(async () => {
       try {
         const response = await getModels(searchTerm || undefined);
-        setModels(response.data);
+        setModels(response.data.items);
       } catch (error) {
         console.error('Error fetching models:', error);
       } finally {
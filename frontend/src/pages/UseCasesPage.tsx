@@ -13,7 +13,7 @@ export default function UseCasesPage() {
     const timer = setTimeout(async () => {
       try {
         const response = await getUseCases(searchTerm || undefined);
-        setUseCases(response.data);
+        setUseCases(response.data.items);
       } catch (error) {
         console.error('Error fetching use cases:', error);
       } finally {
@@ -1,43 +1,134 @@
 import axios from "axios";
 import type {
+  AnalyticsRange,
+  AnalyticsSummary,
+  DailyActiveUsers,
+  DailyChatUsage,
+  DailyRegistrations,
+  DownloadBreakdown,
+  TopArtifact,
   Dashboard,
+  DashboardWindow,
   Dataset,
+  DatasetColumn,
+  DatasetColumnInput,
+  DatasetFile,
+  DigestFrequency,
+  DigestPreferences,
+  DigestPreview,
+  FeedPage,
+  FileProfile,
+  Follow,
+  FollowKind,
+  FollowState,
   Model,
+  Notification,
+  UnreadCount,
   UseCase,
+  CreatedWebhook,
+  Webhook,
+  WebhookDelivery,
+  WebhookDeliveryStatus,
+  WebhookEvent,
   Tutorial,
   Article,
   Toolkit,
   User,
+  RelatedItem,
+  Review,
+  Collection,
+  CollectionDetail,
+  CollectionItemKind,
+  Comment,
+  CommentPage,
+  LikeableKind,
+  LikedItem,
+  ListSort,
+  LikeState,
+  SavedSearch,
+  SemanticHit,
+  Suggestion,
+  SearchResults,
+  TrendingItem,
 } from "../types";
 
 const API_BASE = "http://127.0.0.1:3000/api";
 
+const SESSION_ID_KEY = "aikosh_session_id";
+
+// Stable per-browser id the backend uses to dedupe detail-page views.
+const sessionId = (() => {
+  try {
+    let id = localStorage.getItem(SESSION_ID_KEY);
+    if (!id) {
+      id = crypto.randomUUID();
+      localStorage.setItem(SESSION_ID_KEY, id);
+    }
+    return id;
+  } catch {
+    // Storage disabled (e.g. private mode): dedupe for this page load only.
+    return crypto.randomUUID();
+  }
+})();
+
 const api = axios.create({
   baseURL: API_BASE,
   headers: {
     "Content-Type": "application/json",
+    "X-Session-Id": sessionId,
   },
 });
 
-export const getDashboard = () => api.get<Dashboard>("/dashboard");
+export const getDashboard = (window?: DashboardWindow) =>
+  api.get<Dashboard>("/dashboard", { params: { window } });
 
-export const getDatasets = (search?: string) =>
-  api.get<Dataset[]>("/datasets", {
-    params: search ? { search } : {},
+export const getDatasets = (search?: string, sort?: ListSort) =>
+  api.get<SearchResults<Dataset>>("/datasets", {
+    params: { ...(search ? { search } : {}), ...(sort ? { sort } : {}) },
   });
 
 export const getDatasetById = (id: number) =>
   api.get<Dataset>(`/datasets/${id}`);
+export const getDatasetFiles = (id: number) =>
+  api.get<DatasetFile[]>(`/datasets/${id}/files`);
+export const uploadDatasetFiles = (id: number, files: File[]) => {
+  const form = new FormData();
+  files.forEach((f) => form.append("file", f));
+  return api.post<DatasetFile[]>(`/datasets/${id}/files`, form, {
+    headers: { "Content-Type": "multipart/form-data" },
+  });
+};
+export const datasetFileDownloadUrl = (id: number, fileId: number) =>
+  `${API_BASE}/datasets/${id}/files/${fileId}/download`;
+export const deleteDatasetFile = (id: number, fileId: number) =>
+  api.delete(`/datasets/${id}/files/${fileId}`);
+export const getDatasetFileProfile = (id: number, fileId: number) =>
+  api.get<FileProfile>(`/datasets/${id}/files/${fileId}/profile`);
+export const reprofileDatasetFile = (id: number, fileId: number) =>
+  api.post(`/datasets/${id}/files/${fileId}/profile`);
+export const getDatasetColumns = (id: number) =>
+  api.get<DatasetColumn[]>(`/datasets/${id}/columns`);
+export const createDatasetColumn = (id: number, data: DatasetColumnInput) =>
+  api.post<DatasetColumn>(`/datasets/${id}/columns`, data);
+export const updateDatasetColumn = (
+  id: number,
+  columnId: number,
+  data: Partial<DatasetColumnInput>,
+) => api.put<DatasetColumn>(`/datasets/${id}/columns/${columnId}`, data);
+export const deleteDatasetColumn = (id: number, columnId: number) =>
+  api.delete(`/datasets/${id}/columns/${columnId}`);
 
-export const getModels = (search?: string) =>
-  api.get<Model[]>("/models", {
-    params: search ? { search } : {},
+export const getModels = (search?: string, sort?: ListSort) =>
+  api.get<SearchResults<Model>>("/models", {
+    params: { ...(search ? { search } : {}), ...(sort ? { sort } : {}) },
   });
 
+export const recordModelDownload = (id: number) =>
+  api.post<{ downloads_count: number }>(`/models/${id}/download`);
 export const getModelById = (id: number) => api.get<Model>(`/models/${id}`);
 
 export const getUseCases = (search?: string) =>
-  api.get<UseCase[]>("/usecases", {
+  api.get<SearchResults<UseCase>>("/usecases", {
     params: search ? { search } : {},
   });
 
@@ -52,14 +143,199 @@ export const getToolkit = (search?: string) =>
 export const getToolkitById = (id: number) =>
   api.get<Toolkit>(`/toolkit/${id}`);
 
-export const getTutorials = () => api.get<Tutorial[]>("/tutorials");
+export const getTutorials = (search?: string) =>
+  api.get<Tutorial[]>("/tutorials", {
+    params: search ? { search } : {},
+  });
 
 export const getArticles = () => api.get<Article[]>("/articles");
 export const getArticleById = (id: number) =>
   api.get<Article>(`/articles/${id}`);
 
+export const getSearchSuggestions = (q: string, limit?: number) =>
+  api.get<Suggestion[]>("/search/suggest", {
+    params: limit ? { q, limit } : { q },
+  });
+export const getTrending = (params?: {
+  type?: TrendingItem["kind"];
+  sector?: string;
+  limit?: number;
+}) => api.get<TrendingItem[]>("/trending", { params });
+export const getRelated = (
+  kind: "datasets" | "models" | "usecases",
+  id: number,
+  types?: string,
+) => api.get<RelatedItem[]>(`/${kind}/${id}/related`, { params: { types } });
+export const recordSearchClick = (data: {
+  search_id: number;
+  kind: string;
+  id: number;
+  position?: number;
+}) => api.post("/search/clicks", data);
+export const searchCatalog = (q: string, types?: string, limit?: number) =>
+  api.get<SemanticHit[]>("/search", { params: { q, types, limit } });
+
+export const likeArtifact = (kind: LikeableKind, id: number) =>
+  api.put<LikeState>(`/${kind}s/${id}/like`);
+export const unlikeArtifact = (kind: LikeableKind, id: number) =>
+  api.delete<LikeState>(`/${kind}s/${id}/like`);
+
+export const getReviews = (kind: "dataset" | "model", id: number) =>
+  api.get<Review[]>(`/${kind}s/${id}/reviews`);
+export const saveReview = (
+  kind: "dataset" | "model",
+  id: number,
+  data: { rating: number; body?: string },
+) => api.put<Review>(`/${kind}s/${id}/review`, data);
+export const deleteReview = (kind: "dataset" | "model", id: number) =>
+  api.delete(`/${kind}s/${id}/review`);
+
+export const getComments = (
+  kind: LikeableKind,
+  id: number,
+  page?: number,
+  perPage?: number,
+) =>
+  api.get<CommentPage>(`/${kind}s/${id}/comments`, {
+    params: { page, per_page: perPage },
+  });
+export const createComment = (
+  kind: LikeableKind,
+  id: number,
+  body: string,
+  parentId?: number,
+) =>
+  api.post<Comment>(`/${kind}s/${id}/comments`, { body, parent_id: parentId });
+export const updateComment = (id: number, body: string) =>
+  api.patch<Comment>(`/comments/${id}`, { body });
+export const deleteComment = (id: number) => api.delete(`/comments/${id}`);
+export const moderateComment = (
+  id: number,
+  data: { hidden?: boolean; locked?: boolean },
+) => api.patch<Comment>(`/admin/comments/${id}`, data);
+
+export const getAnalyticsSummary = (range?: AnalyticsRange) =>
+  api.get<AnalyticsSummary>("/admin/analytics/summary", { params: range });
+export const getActiveUsers = (range?: AnalyticsRange) =>
+  api.get<DailyActiveUsers[]>("/admin/analytics/active-users", { params: range });
+export const getRegistrations = (range?: AnalyticsRange) =>
+  api.get<DailyRegistrations[]>("/admin/analytics/registrations", { params: range });
+export const getTopArtifacts = (
+  params?: AnalyticsRange & {
+    metric?: "views" | "downloads";
+    kind?: "dataset" | "model" | "usecase";
+    limit?: number;
+  },
+) => api.get<TopArtifact[]>("/admin/analytics/top-artifacts", { params });
+export const getDownloadsBySector = (params?: AnalyticsRange & { limit?: number }) =>
+  api.get<DownloadBreakdown[]>("/admin/analytics/downloads-by-sector", { params });
+export const getDownloadsByOrganization = (params?: AnalyticsRange & { limit?: number }) =>
+  api.get<DownloadBreakdown[]>("/admin/analytics/downloads-by-organization", { params });
+export const getChatUsage = (range?: AnalyticsRange) =>
+  api.get<DailyChatUsage[]>("/admin/analytics/chat", { params: range });
+// Any analytics report as a CSV download, e.g. analyticsCsvUrl("active-users", range).
+export const analyticsCsvUrl = (report: string, params?: Record<string, string | number>) =>
+  `${API_BASE}/admin/analytics/${report}?${new URLSearchParams({
+    ...Object.fromEntries(Object.entries(params ?? {}).map(([k, v]) => [k, String(v)])),
+    format: "csv",
+  })}`;
+
+export const getWebhooks = () => api.get<Webhook[]>("/admin/webhooks");
+export const createWebhook = (data: {
+  url: string;
+  events: WebhookEvent[];
+  description?: string;
+  active?: boolean;
+}) => api.post<CreatedWebhook>("/admin/webhooks", data);
+export const updateWebhook = (
+  id: number,
+  data: { url?: string; events?: WebhookEvent[]; description?: string; active?: boolean },
+) => api.patch<Webhook>(`/admin/webhooks/${id}`, data);
+export const deleteWebhook = (id: number) => api.delete(`/admin/webhooks/${id}`);
+export const getWebhookDeliveries = (
+  id: number,
+  params?: { status?: WebhookDeliveryStatus; before?: number; limit?: number },
+) => api.get<WebhookDelivery[]>(`/admin/webhooks/${id}/deliveries`, { params });
+export const pingWebhook = (id: number) =>
+  api.post<WebhookDelivery>(`/admin/webhooks/${id}/ping`);
+export const redeliverWebhookDelivery = (id: number, deliveryId: number) =>
+  api.post<WebhookDelivery>(`/admin/webhooks/${id}/deliveries/${deliveryId}/redeliver`);
+
+export const getDigestPreferences = () =>
+  api.get<DigestPreferences>("/users/profile/digest");
+export const updateDigestPreferences = (frequency: DigestFrequency) =>
+  api.put<DigestPreferences>("/users/profile/digest", { frequency });
+export const previewDigest = (frequency?: DigestFrequency) =>
+  api.get<DigestPreview>("/users/profile/digest/preview", { params: { frequency } });
+
+export const getNotifications = (params?: {
+  unread?: boolean;
+  before?: number;
+  limit?: number;
+}) => api.get<Notification[]>("/notifications", { params });
+export const getUnreadNotificationCount = () =>
+  api.get<UnreadCount>("/notifications/unread-count");
+export const markNotificationRead = (id: number) =>
+  api.post<UnreadCount>(`/notifications/${id}/read`);
+export const markAllNotificationsRead = () =>
+  api.post<UnreadCount>("/notifications/read-all");
+// For EventSource: "notification" events carry a Notification, "unread" an UnreadCount.
+export const notificationStreamUrl = () => `${API_BASE}/notifications/stream`;
+
+export const followTarget = (kind: FollowKind, id: number) =>
+  api.put<FollowState>(`/follows/${kind}/${id}`);
+export const unfollowTarget = (kind: FollowKind, id: number) =>
+  api.delete<FollowState>(`/follows/${kind}/${id}`);
+export const getFeed = (cursor?: string, limit?: number) =>
+  api.get<FeedPage>("/feed", { params: { cursor, limit } });
+
 export const getUserProfile = () => api.get<User>("/users/profile");
+export const getSavedSearches = () =>
+  api.get<SavedSearch[]>("/users/profile/saved-searches");
+export const createSavedSearch = (
+  data: Pick<SavedSearch, "name" | "kind"> &
+    Partial<Pick<SavedSearch, "search" | "sector" | "organization_id">>,
+) => api.post<SavedSearch>("/users/profile/saved-searches", data);
+export const deleteSavedSearch = (id: number) =>
+  api.delete(`/users/profile/saved-searches/${id}`);
+export const getMyLikes = () => api.get<LikedItem[]>("/users/profile/likes");
+export const getMyFollows = () => api.get<Follow[]>("/users/profile/follows");
+export const getMyCollections = () =>
+  api.get<Collection[]>("/users/profile/collections");
+export const createCollection = (
+  data: Pick<Collection, "name"> &
+    Partial<Pick<Collection, "description" | "visibility">>,
+) => api.post<Collection>("/users/profile/collections", data);
 export const updateUserProfile = (data: Partial<User>) =>
   api.patch<User>("/users/profile", data);
 
+export const getCollection = (slug: string) =>
+  api.get<CollectionDetail>(`/collections/${slug}`);
+export const updateCollection = (
+  slug: string,
+  data: Partial<Pick<Collection, "name" | "description" | "visibility">>,
+) => api.patch<Collection>(`/collections/${slug}`, data);
+export const deleteCollection = (slug: string) =>
+  api.delete(`/collections/${slug}`);
+export const addCollectionItem = (
+  slug: string,
+  data: { kind: CollectionItemKind; id: number; note?: string },
+) => api.post<CollectionDetail>(`/collections/${slug}/items`, data);
+export const updateCollectionItem = (
+  slug: string,
+  itemId: number,
+  note: string,
+) =>
+  api.patch<CollectionDetail>(`/collections/${slug}/items/${itemId}`, {
+    note,
+  });
+export const deleteCollectionItem = (slug: string, itemId: number) =>
+  api.delete<CollectionDetail>(`/collections/${slug}/items/${itemId}`);
+export const reorderCollection = (slug: string, itemIds: number[]) =>
+  api.put<CollectionDetail>(`/collections/${slug}/order`, {
+    item_ids: itemIds,
+  });
+export const forkCollection = (slug: string, name?: string) =>
+  api.post<CollectionDetail>(`/collections/${slug}/fork`, name ? { name } : {});
+
 export default api;
\ No newline at end of file
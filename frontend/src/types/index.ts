@@ -1,21 +1,93 @@
+export type DashboardWindow = "7d" | "30d" | "90d" | "all";
+
 export interface Dashboard {
   greeting: string;
   role: string;
   login_streak: number;
-  artifacts_viewed: ArtifactsViewed;
-  artifacts_downloaded: ArtifactsDownloaded;
-  last_login: string;
+  last_login: string | null;
+  window: DashboardWindow;
+  artifacts_viewed: ArtifactCounts;
+  artifacts_downloaded: ArtifactCounts;
+  artifacts_liked: ArtifactCounts;
+  contributions: Contributions;
+  timeline: ActivityEvent[];
+  recently_viewed: RecentView[];
+  recommended: Recommendation[];
+  heatmap: HeatmapDay[];
 }
 
-export interface ArtifactsViewed {
+export interface ArtifactCounts {
   datasets: number;
   models: number;
   use_cases: number;
+  articles: number;
 }
 
-export interface ArtifactsDownloaded {
-  datasets: number;
-  models: number;
+export interface Contributions {
+  total: number;
+  open: number;
+  restricted: number;
+  private: number;
+  items: Contribution[];
+}
+
+export interface Contribution {
+  kind: "dataset" | "model";
+  id: number;
+  title: string;
+  visibility: "Open" | "Restricted" | "Private";
+  rating_average: number | null;
+  rating_count: number;
+  comment_count: number;
+  views_count: number;
+  downloads_count: number;
+  likes_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ActivityEvent {
+  action: "viewed" | "downloaded" | "liked" | "reviewed" | "commented" | "contributed";
+  kind: "dataset" | "model" | "usecase" | "article";
+  id: number;
+  title: string;
+  occurred_at: string;
+}
+
+export interface RecentView {
+  kind: "dataset" | "model" | "usecase";
+  id: number;
+  title: string;
+  viewed_at: string;
+}
+
+export interface Recommendation {
+  kind: "dataset" | "model" | "usecase";
+  id: number;
+  title: string;
+  description: string;
+  image_url: string | null;
+  sector: string | null;
+  reason: "sector_interest" | "popular";
+}
+
+export interface HeatmapDay {
+  date: string;
+  count: number;
+}
+
+export type ListSort = "rating" | "trending";
+
+export interface SearchResults<T> {
+  items: T[];
+  did_you_mean: string | null;
+}
+
+export interface SearchMatches {
+  title?: string;
+  description?: string;
+  about?: string;
+  tags?: string;
 }
 
 export interface Dataset {
@@ -27,8 +99,11 @@ export interface Dataset {
   likes_count: number;
   downloads_count: number;
   views_count: number;
+  rating_average: number | null;
+  rating_count: number;
   source_org: string | null;
   tags: string[];
+  matches?: SearchMatches;
   license: string;
   geographical_coverage: string;
   sector: string;
@@ -45,6 +120,79 @@ export interface Dataset {
   hosted: string;
   data_type: string | null;
   data_collection_method: string | null;
+  files?: DatasetFile[];
+  columns?: DatasetColumn[];
+  liked_by_me?: boolean;
+  rating?: RatingSummary;
+  my_review?: Review | null;
+}
+
+export interface DatasetFile {
+  id: number;
+  dataset_id: number;
+  name: string;
+  size_bytes: number;
+  media_type: string;
+  sha256: string;
+  profile_status: ProfileStatus;
+  created_at: string;
+}
+
+export type ProfileStatus = "pending" | "succeeded" | "failed" | "unsupported";
+
+export interface ColumnProfile {
+  name: string;
+  data_type: ColumnType;
+  null_count: number;
+  null_ratio: number;
+  distinct_count: number;
+  distinct_capped: boolean;
+  min: string | null;
+  max: string | null;
+}
+
+export interface FileProfile {
+  file_id: number;
+  status: ProfileStatus;
+  format: "csv" | "tsv" | "jsonl" | "parquet" | null;
+  row_count: number | null;
+  columns: ColumnProfile[];
+  sample_rows: (string | null)[][];
+  error: string | null;
+  profiled_at: string | null;
+}
+
+export type ColumnType =
+  | "string"
+  | "integer"
+  | "decimal"
+  | "boolean"
+  | "date"
+  | "datetime"
+  | "other";
+
+export interface DatasetColumn {
+  id: number;
+  dataset_id: number;
+  position: number;
+  name: string;
+  data_type: ColumnType;
+  unit: string | null;
+  description: string;
+  nullable: boolean;
+  example_values: string[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface DatasetColumnInput {
+  name: string;
+  data_type: ColumnType;
+  unit?: string;
+  description?: string;
+  nullable?: boolean;
+  example_values?: string[];
+  position?: number;
 }
 
 export interface Model {
@@ -54,8 +202,11 @@ export interface Model {
   about_model: string;
   image_url: string | null;
   tags: string[];
+  matches?: SearchMatches;
   likes_count: number;
   downloads_count: number;
+  rating_average: number | null;
+  rating_count: number;
   source_org: string;
   license: string;
   hosted_by: string;
@@ -67,6 +218,25 @@ export interface Model {
   updated_at: string;
   created_by: string;
   size: string;
+  liked_by_me?: boolean;
+  rating?: RatingSummary;
+  my_review?: Review | null;
+}
+
+export interface RatingSummary {
+  average: number | null;
+  count: number;
+  distribution: [number, number, number, number, number];
+}
+
+export interface Review {
+  id: number;
+  author_id: number;
+  author: string;
+  rating: number;
+  body: string | null;
+  created_at: string;
+  updated_at: string;
 }
 
 export interface UseCase {
@@ -74,10 +244,14 @@ export interface UseCase {
   title: string;
   description: string;
   image_url: string | null;
+  likes_count: number;
+  views_count: number;
   source_org: string;
   tags: string[];
+  matches?: SearchMatches;
   sector: string;
   about_use_case: string;
+  liked_by_me?: boolean;
 }
 
 export interface Tutorial {
@@ -95,9 +269,28 @@ export interface Article {
   description: string;
   content: string | null;
   image_url: string;
+  likes_count: number;
   author: string;
   read_time: string;
   published_date: string;
+  liked_by_me?: boolean;
+}
+
+export type LikeableKind = "dataset" | "model" | "usecase" | "article";
+
+export interface LikeState {
+  liked: boolean;
+  likes_count: number;
+}
+
+export interface LikedItem {
+  kind: LikeableKind;
+  id: number;
+  title: string;
+  description: string;
+  image_url: string | null;
+  likes_count: number;
+  liked_at: string;
 }
 
 export interface Toolkit {
@@ -123,3 +316,273 @@ export interface User {
   profile_picture_url: string | null;
   role: string;
 }
+
+export interface TrendingItem {
+  kind: "dataset" | "model" | "usecase";
+  id: number;
+  title: string;
+  description: string;
+  image_url: string | null;
+  sector: string | null;
+  sector_slug: string | null;
+  score: number;
+  views: number;
+  likes: number;
+  downloads: number;
+}
+
+export interface Suggestion {
+  kind: "sector" | "tag" | "organization" | "dataset" | "model" | "usecase";
+  id: number;
+  label: string;
+  slug: string | null;
+}
+
+export interface SemanticHit {
+  kind: "dataset" | "model" | "usecase" | "article";
+  id: number;
+  title: string;
+  description: string | null;
+  score: number;
+  keyword_score: number;
+  similarity: number;
+}
+
+export interface RelatedItem {
+  kind: "dataset" | "model" | "usecase" | "article";
+  id: number;
+  title: string;
+  description: string;
+  image_url: string | null;
+  score: number;
+  shared_tags: number;
+  same_sector: boolean;
+  same_organization: boolean;
+  similarity: number;
+}
+
+export interface SavedSearch {
+  id: number;
+  name: string;
+  kind: "dataset" | "model" | "usecase";
+  search: string | null;
+  sector: string | null;
+  organization_id: number | null;
+  last_checked_at: string;
+  created_at: string;
+}
+
+export interface Comment {
+  id: number;
+  parent_id: number | null;
+  root_id: number | null;
+  author_id: number;
+  author: string;
+  body: string | null;
+  is_deleted: boolean;
+  is_hidden: boolean;
+  is_locked: boolean;
+  edited_at: string | null;
+  created_at: string;
+}
+
+export interface CommentPage {
+  comments: Comment[];
+  total_threads: number;
+  page: number;
+  per_page: number;
+}
+
+export interface Notification {
+  id: number;
+  kind: string;
+  title: string;
+  body: string | null;
+  artifact_kind: "dataset" | "model" | "usecase" | "article" | null;
+  artifact_id: number | null;
+  read_at: string | null;
+  created_at: string;
+}
+
+export interface UnreadCount {
+  unread: number;
+}
+
+export interface AnalyticsRange {
+  from?: string; // YYYY-MM-DD
+  to?: string;
+}
+
+export interface AnalyticsSummary {
+  from: string;
+  to: string;
+  active_users: number;
+  registrations: number;
+  views: number;
+  downloads: number;
+  searches: number;
+  chat_messages: number;
+}
+
+export interface DailyActiveUsers {
+  date: string;
+  active_users: number;
+}
+
+export interface DailyRegistrations {
+  date: string;
+  registrations: number;
+  total_users: number;
+}
+
+export interface TopArtifact {
+  kind: "dataset" | "model" | "usecase";
+  id: number;
+  title: string;
+  count: number;
+  unique_users: number;
+}
+
+export interface DownloadBreakdown {
+  id: number | null;
+  name: string;
+  downloads: number;
+  unique_users: number;
+  datasets: number;
+  models: number;
+}
+
+export interface DailyChatUsage {
+  date: string;
+  messages: number;
+  users: number;
+  failed: number;
+  avg_latency_ms: number | null;
+}
+
+export type DigestFrequency = "off" | "daily" | "weekly";
+
+export interface DigestPreferences {
+  frequency: DigestFrequency;
+  email: string;
+  last_sent_at: string | null;
+  next_digest_at: string | null;
+}
+
+export interface DigestPreview {
+  period_start: string;
+  period_end: string;
+  item_count: number;
+  subject: string;
+  text: string;
+  html: string;
+}
+
+export type WebhookEvent =
+  | "dataset.created"
+  | "dataset.updated"
+  | "dataset.published"
+  | "dataset.deleted"
+  | "model.created"
+  | "model.updated"
+  | "model.published"
+  | "model.deleted";
+
+export interface Webhook {
+  id: number;
+  url: string;
+  description: string | null;
+  events: WebhookEvent[];
+  active: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+// Returned only by createWebhook; the secret is not shown again.
+export interface CreatedWebhook extends Webhook {
+  secret: string;
+}
+
+export type WebhookDeliveryStatus = "pending" | "succeeded" | "failed";
+
+export interface WebhookDelivery {
+  id: number;
+  webhook_id: number;
+  event: WebhookEvent | "ping";
+  payload: { event: string; occurred_at: string; data: Record<string, unknown> };
+  status: WebhookDeliveryStatus;
+  attempts: number;
+  next_attempt_at: string;
+  last_attempt_at: string | null;
+  response_status: number | null;
+  response_body: string | null;
+  error: string | null;
+  redelivery_of_id: number | null;
+  created_at: string;
+}
+
+export type FollowKind = "organization" | "sector" | "tag" | "user";
+
+export interface FollowState {
+  following: boolean;
+  followers_count: number;
+}
+
+export interface Follow {
+  kind: FollowKind;
+  id: number;
+  name: string;
+  slug: string;
+  followed_at: string;
+}
+
+export interface FeedItem {
+  kind: "dataset" | "model" | "usecase" | "article";
+  id: number;
+  title: string;
+  description: string;
+  image_url: string | null;
+  event: "published" | "updated";
+  occurred_at: string;
+}
+
+export interface FeedPage {
+  items: FeedItem[];
+  next_cursor: string | null;
+}
+
+export type CollectionItemKind =
+  | "dataset"
+  | "model"
+  | "usecase"
+  | "article"
+  | "toolkit";
+
+export interface Collection {
+  id: number;
+  name: string;
+  slug: string;
+  description: string | null;
+  visibility: "private" | "public";
+  owner: string;
+  forked_from_id: number | null;
+  item_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CollectionItem {
+  id: number;
+  kind: CollectionItemKind;
+  artifact_id: number;
+  position: number;
+  note: string | null;
+  title: string;
+  description: string;
+  image_url: string | null;
+  added_at: string;
+}
+
+export interface CollectionDetail extends Collection {
+  items: CollectionItem[];
+}
@@ -0,0 +1,504 @@
+//! Platform usage analytics for admins.
+//!
+//! Reports are computed on request from the event tables (views, downloads,
+//! searches, likes, comments, reviews, chat) over an inclusive range of UTC
+//! days. A user counts as active on a day if they left any event that day.
+//! Every report can be returned as JSON or as a CSV download.
+
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::time::Instant;
+
+use crate::errors::AppError;
+use crate::models::{
+    AnalyticsFormat, AnalyticsMetric, AnalyticsQuery, AnalyticsSummary, DailyActiveUsers,
+    DailyChatUsage, DailyRegistrations, DownloadBreakdown, TopArtifact,
+};
+
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_RANGE_DAYS: i64 = 366;
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 500;
+
+/// Inclusive range of UTC days, as `YYYY-MM-DD`.
+pub struct Range {
+    pub from: String,
+    pub to: String,
+}
+
+impl Range {
+    pub fn from_query(params: &AnalyticsQuery) -> Result<Self, AppError> {
+        let parse = |field: &str, value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                AppError::ValidationError(format!("{field} must be a date (YYYY-MM-DD)"))
+            })
+        };
+        let to = match &params.to {
+            Some(to) => parse("to", to)?,
+            None => Utc::now().date_naive(),
+        };
+        let from = match &params.from {
+            Some(from) => parse("from", from)?,
+            None => to - Duration::days(DEFAULT_RANGE_DAYS - 1),
+        };
+        if from > to {
+            return Err(AppError::ValidationError(
+                "from must not be after to".to_string(),
+            ));
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(AppError::ValidationError(format!(
+                "Range must be at most {MAX_RANGE_DAYS} days"
+            )));
+        }
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+}
+
+pub fn limit(params: &AnalyticsQuery) -> i64 {
+    params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+}
+
+// =============================================================================
+// CSV EXPORT
+// =============================================================================
+
+pub trait CsvRow {
+    const HEADER: &'static [&'static str];
+    fn record(&self) -> Vec<String>;
+}
+
+fn csv_field(value: &str) -> String {
+    // Spreadsheets run cells starting with these as formulas.
+    let value = if value.starts_with(['=', '+', '-', '@']) && value.parse::<f64>().is_err() {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn opt<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+pub fn to_csv<T: CsvRow>(rows: &[T]) -> String {
+    let mut out = T::HEADER.join(",");
+    out.push_str("\r\n");
+    for row in rows {
+        let fields: Vec<String> = row.record().iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// JSON array, or a CSV attachment named after the report and range.
+pub fn respond<T: Serialize + CsvRow>(
+    rows: Vec<T>,
+    format: Option<AnalyticsFormat>,
+    report: &str,
+    range: &Range,
+) -> Response {
+    match format.unwrap_or_default() {
+        AnalyticsFormat::Json => Json(rows).into_response(),
+        AnalyticsFormat::Csv => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"{report}-{}-{}.csv\"",
+                        range.from, range.to
+                    ),
+                ),
+            ],
+            to_csv(&rows),
+        )
+            .into_response(),
+    }
+}
+
+impl CsvRow for AnalyticsSummary {
+    const HEADER: &'static [&'static str] = &[
+        "from",
+        "to",
+        "active_users",
+        "registrations",
+        "views",
+        "downloads",
+        "searches",
+        "chat_messages",
+    ];
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.from.clone(),
+            self.to.clone(),
+            self.active_users.to_string(),
+            self.registrations.to_string(),
+            self.views.to_string(),
+            self.downloads.to_string(),
+            self.searches.to_string(),
+            self.chat_messages.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for DailyActiveUsers {
+    const HEADER: &'static [&'static str] = &["date", "active_users"];
+    fn record(&self) -> Vec<String> {
+        vec![self.date.clone(), self.active_users.to_string()]
+    }
+}
+
+impl CsvRow for DailyRegistrations {
+    const HEADER: &'static [&'static str] = &["date", "registrations", "total_users"];
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.date.clone(),
+            self.registrations.to_string(),
+            self.total_users.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for TopArtifact {
+    const HEADER: &'static [&'static str] = &["kind", "id", "title", "count", "unique_users"];
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.kind.clone(),
+            self.id.to_string(),
+            self.title.clone(),
+            self.count.to_string(),
+            self.unique_users.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for DownloadBreakdown {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "name",
+        "downloads",
+        "unique_users",
+        "datasets",
+        "models",
+    ];
+    fn record(&self) -> Vec<String> {
+        vec![
+            opt(&self.id),
+            self.name.clone(),
+            self.downloads.to_string(),
+            self.unique_users.to_string(),
+            self.datasets.to_string(),
+            self.models.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for DailyChatUsage {
+    const HEADER: &'static [&'static str] =
+        &["date", "messages", "users", "failed", "avg_latency_ms"];
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.date.clone(),
+            self.messages.to_string(),
+            self.users.to_string(),
+            self.failed.to_string(),
+            opt(&self.avg_latency_ms.map(|ms| format!("{ms:.1}"))),
+        ]
+    }
+}
+
+// =============================================================================
+// REPORTS
+// =============================================================================
+
+/// Binds ?1 first day, ?2 last day.
+const DAYS_CTE: &str = r#"
+    days(day) AS (
+        SELECT ?1
+        UNION ALL
+        SELECT date(day, '+1 day') FROM days WHERE day < ?2
+    )
+"#;
+
+/// Every signed-in user event in the range. Binds ?1 first day, ?2 last day.
+const ACTIVE_CTE: &str = r#"
+    active AS (
+        SELECT user_id, created_at FROM view_events
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, created_at FROM download_events
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, created_at FROM search_events
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, created_at FROM likes
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, created_at FROM comments
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, updated_at FROM reviews
+        WHERE updated_at >= ?1 AND updated_at < date(?2, '+1 day')
+        UNION ALL
+        SELECT user_id, created_at FROM chat_events
+        WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')
+    )
+"#;
+
+pub async fn summary(db: &SqlitePool, range: &Range) -> Result<AnalyticsSummary, AppError> {
+    let row = sqlx::query_as::<_, AnalyticsSummary>(&format!(
+        r#"
+        WITH {ACTIVE_CTE}
+        SELECT ?1 AS "from", ?2 AS "to",
+               (SELECT COUNT(DISTINCT user_id) FROM active) AS active_users,
+               (SELECT COUNT(*) FROM users
+                WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')) AS registrations,
+               (SELECT COUNT(*) FROM view_events
+                WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')) AS views,
+               (SELECT COUNT(*) FROM download_events
+                WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')) AS downloads,
+               (SELECT COUNT(*) FROM search_events
+                WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')) AS searches,
+               (SELECT COUNT(*) FROM chat_events
+                WHERE created_at >= ?1 AND created_at < date(?2, '+1 day')) AS chat_messages
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .fetch_one(db)
+    .await?;
+    Ok(row)
+}
+
+/// Distinct signed-in users with any event, per day (zero-filled).
+pub async fn active_users(
+    db: &SqlitePool,
+    range: &Range,
+) -> Result<Vec<DailyActiveUsers>, AppError> {
+    let rows = sqlx::query_as::<_, DailyActiveUsers>(&format!(
+        r#"
+        WITH RECURSIVE {DAYS_CTE}, {ACTIVE_CTE}
+        SELECT days.day AS date, COUNT(DISTINCT a.user_id) AS active_users
+        FROM days
+        LEFT JOIN active a ON date(a.created_at) = days.day
+        GROUP BY days.day
+        ORDER BY days.day
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// New accounts per day, with the running total at the end of each day.
+pub async fn registrations(
+    db: &SqlitePool,
+    range: &Range,
+) -> Result<Vec<DailyRegistrations>, AppError> {
+    let rows = sqlx::query_as::<_, DailyRegistrations>(&format!(
+        r#"
+        WITH RECURSIVE {DAYS_CTE}
+        SELECT days.day AS date,
+               (SELECT COUNT(*) FROM users u
+                WHERE u.created_at >= days.day
+                  AND u.created_at < date(days.day, '+1 day')) AS registrations,
+               (SELECT COUNT(*) FROM users u
+                WHERE u.created_at < date(days.day, '+1 day')) AS total_users
+        FROM days
+        ORDER BY days.day
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// Most viewed or downloaded entries in the range, optionally of one kind.
+pub async fn top_artifacts(
+    db: &SqlitePool,
+    range: &Range,
+    metric: AnalyticsMetric,
+    kind: Option<&str>,
+    limit: i64,
+) -> Result<Vec<TopArtifact>, AppError> {
+    let allowed: &[&str] = match metric {
+        AnalyticsMetric::Views => &["dataset", "model", "usecase"],
+        AnalyticsMetric::Downloads => &["dataset", "model"],
+    };
+    if let Some(kind) = kind.filter(|k| !allowed.contains(k)) {
+        return Err(AppError::ValidationError(format!(
+            "kind '{kind}' must be one of {}",
+            allowed.join(", ")
+        )));
+    }
+    // Views may be anonymous; their session id stands in for the user.
+    let (table, viewer) = match metric {
+        AnalyticsMetric::Views => (
+            "view_events",
+            "COALESCE('u' || e.user_id, 's' || e.session_id)",
+        ),
+        AnalyticsMetric::Downloads => ("download_events", "e.user_id"),
+    };
+    let rows = sqlx::query_as::<_, TopArtifact>(&format!(
+        r#"
+        SELECT e.artifact_kind AS kind, e.artifact_id AS id,
+               COALESCE(d.title, m.title, u.title) AS title,
+               COUNT(*) AS count,
+               COUNT(DISTINCT {viewer}) AS unique_users
+        FROM {table} e
+        LEFT JOIN datasets d ON e.artifact_kind = 'dataset' AND d.id = e.artifact_id
+        LEFT JOIN models m   ON e.artifact_kind = 'model'   AND m.id = e.artifact_id
+        LEFT JOIN usecases u ON e.artifact_kind = 'usecase' AND u.id = e.artifact_id
+        WHERE e.created_at >= ?1 AND e.created_at < date(?2, '+1 day')
+          AND (?3 IS NULL OR e.artifact_kind = ?3)
+          AND COALESCE(d.id, m.id, u.id) IS NOT NULL
+        GROUP BY e.artifact_kind, e.artifact_id
+        ORDER BY count DESC, unique_users DESC, kind, id
+        LIMIT ?4
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .bind(kind)
+    .bind(limit)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+#[derive(Clone, Copy)]
+pub enum Breakdown {
+    Sector,
+    Organization,
+}
+
+/// Downloads grouped by the downloaded entry's sector or organization.
+pub async fn downloads_by(
+    db: &SqlitePool,
+    range: &Range,
+    by: Breakdown,
+    limit: i64,
+) -> Result<Vec<DownloadBreakdown>, AppError> {
+    let (column, table) = match by {
+        Breakdown::Sector => ("sector_id", "sectors"),
+        Breakdown::Organization => ("organization_id", "organizations"),
+    };
+    let rows = sqlx::query_as::<_, DownloadBreakdown>(&format!(
+        r#"
+        WITH dl AS (
+            SELECT e.user_id, e.artifact_kind, COALESCE(d.{column}, m.{column}) AS group_id
+            FROM download_events e
+            LEFT JOIN datasets d ON e.artifact_kind = 'dataset' AND d.id = e.artifact_id
+            LEFT JOIN models m   ON e.artifact_kind = 'model'   AND m.id = e.artifact_id
+            WHERE e.created_at >= ?1 AND e.created_at < date(?2, '+1 day')
+        )
+        SELECT dl.group_id AS id, COALESCE(g.name, 'Unassigned') AS name,
+               COUNT(*) AS downloads,
+               COUNT(DISTINCT dl.user_id) AS unique_users,
+               SUM(dl.artifact_kind = 'dataset') AS datasets,
+               SUM(dl.artifact_kind = 'model') AS models
+        FROM dl
+        LEFT JOIN {table} g ON g.id = dl.group_id
+        GROUP BY dl.group_id
+        ORDER BY downloads DESC, name
+        LIMIT ?3
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .bind(limit)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// Chatbot questions per day (zero-filled).
+pub async fn chat_usage(db: &SqlitePool, range: &Range) -> Result<Vec<DailyChatUsage>, AppError> {
+    let rows = sqlx::query_as::<_, DailyChatUsage>(&format!(
+        r#"
+        WITH RECURSIVE {DAYS_CTE}
+        SELECT days.day AS date,
+               COUNT(c.id) AS messages,
+               COUNT(DISTINCT c.user_id) AS users,
+               COALESCE(SUM(c.status = 'failed'), 0) AS failed,
+               AVG(c.latency_ms) AS avg_latency_ms
+        FROM days
+        LEFT JOIN chat_events c
+               ON c.created_at >= days.day AND c.created_at < date(days.day, '+1 day')
+        GROUP BY days.day
+        ORDER BY days.day
+        "#
+    ))
+    .bind(&range.from)
+    .bind(&range.to)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+// =============================================================================
+// CHAT LOGGING
+// =============================================================================
+
+/// A chatbot question in flight; latency runs from `start` until `record`.
+/// Record as soon as the chatbot has replied, before streaming the answer, so
+/// a client that disconnects mid-stream is still counted.
+pub struct ChatLog {
+    user_id: Option<i64>,
+    question_chars: i64,
+    started: Instant,
+}
+
+impl ChatLog {
+    pub fn start(user_id: Option<i64>, question: &str) -> Self {
+        Self {
+            user_id,
+            question_chars: question.chars().count() as i64,
+            started: Instant::now(),
+        }
+    }
+
+    /// Store the event. Logging never fails the chat — errors are only traced.
+    pub async fn record(self, db: &SqlitePool, answer: Option<&str>) {
+        let latency_ms = self.started.elapsed().as_millis() as i64;
+        let res = sqlx::query(
+            r#"
+            INSERT INTO chat_events (user_id, status, question_chars, answer_chars, latency_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(if answer.is_some() {
+            "answered"
+        } else {
+            "failed"
+        })
+        .bind(self.question_chars)
+        .bind(answer.map_or(0, |a| a.chars().count() as i64))
+        .bind(latency_ms)
+        .execute(db)
+        .await;
+        if let Err(e) = res {
+            tracing::warn!("failed to log chat event: {e}");
+        }
+    }
+}
@@ -873,4 +873,4 @@ struct RawToolkit {
     license_and_compliance: Option<String>,
     screenshots_and_ui_previews: Option<String>,
     versioning_and_community_info: Option<String>,
-}
\ No newline at end of file
+}
@@ -8,11 +8,71 @@ pub struct Config {
     pub frontend_origin: String,
     pub chatbot_url: String,
     pub chatbot_timeout: Duration,
+    pub embedder: EmbedderKind,
+    pub embeddings_url: Option<String>,
+    pub embeddings_model: String,
+    pub embeddings_api_key: Option<String>,
+    pub embeddings_dim: usize,
+    pub embeddings_reindex_interval: Duration,
+    pub search_hybrid_alpha: f64,
+    pub saved_search_check_interval: Duration,
+    pub follow_check_interval: Duration,
+    pub storage: StorageKind,
+    pub storage_local_dir: String,
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: String,
+    pub s3_access_key_id: Option<String>,
+    pub s3_secret_access_key: Option<String>,
+    pub upload_max_bytes: u64,
+    pub view_dedupe_window: Duration,
+    pub webhook_dispatch_interval: Duration,
+    pub webhook_max_attempts: i64,
+    pub webhook_retry_base: Duration,
+    pub webhook_retention_days: i64,
+    pub mailer: MailerKind,
+    pub mail_from: String,
+    pub mail_file_dir: String,
+    pub mail_http_url: Option<String>,
+    pub mail_http_api_key: Option<String>,
+    pub digest_check_interval: Duration,
+    pub trending_refresh_interval: Duration,
+    pub trending_half_life_hours: f64,
+    pub profile_check_interval: Duration,
+}
+
+/// Which `Embedder` implementation backs semantic search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmbedderKind {
+    /// Lexical feature-hashing embedder computed in-process; a fallback for
+    /// tests and offline development, not a semantic model.
+    Hashing,
+    /// OpenAI-compatible `/embeddings` endpoint at `EMBEDDINGS_URL`; the
+    /// default whenever that URL is set.
+    Http,
+}
+
+/// Where uploaded dataset files are stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageKind {
+    /// Files under `STORAGE_LOCAL_DIR` on this machine.
+    Local,
+    /// An S3-compatible bucket (AWS, MinIO, ...) addressed path-style.
+    S3,
+}
+
+/// How outgoing email (digests) is delivered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailerKind {
+    /// One `.eml` file per message under `MAIL_FILE_DIR`; nothing leaves the machine.
+    File,
+    /// JSON POST of each message to a mail relay at `MAIL_HTTP_URL`.
+    Http,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self, ConfigError> {
-        Ok(Self {
+        let config = Self {
             server_host: env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
 
             server_port: env::var("SERVER_PORT")
@@ -32,7 +92,202 @@ impl Config {
                     .parse()
                     .map_err(|_| ConfigError::Invalid("CHATBOT_TIMEOUT_SECS must be a number"))?,
             ),
-        })
+
+            // Without an embeddings service the server still boots, on the
+            // lexical fallback (see `embeddings::from_config`).
+            embedder: match env::var("EMBEDDER").as_deref() {
+                Ok("hashing") => EmbedderKind::Hashing,
+                Ok("http") => EmbedderKind::Http,
+                Err(_) if env::var("EMBEDDINGS_URL").is_ok() => EmbedderKind::Http,
+                Err(_) => EmbedderKind::Hashing,
+                _ => return Err(ConfigError::Invalid("EMBEDDER must be 'hashing' or 'http'")),
+            },
+
+            embeddings_url: env::var("EMBEDDINGS_URL").ok(),
+
+            embeddings_model: env::var("EMBEDDINGS_MODEL")
+                .unwrap_or_else(|_| "text-embedding-3-small".to_string()),
+
+            embeddings_api_key: env::var("EMBEDDINGS_API_KEY").ok(),
+
+            embeddings_dim: env::var("EMBEDDINGS_DIM")
+                .unwrap_or_else(|_| "384".to_string())
+                .parse()
+                .ok()
+                .filter(|d: &usize| *d > 0)
+                .ok_or(ConfigError::Invalid(
+                    "EMBEDDINGS_DIM must be a positive number",
+                ))?,
+
+            embeddings_reindex_interval: Duration::from_secs(
+                env::var("EMBEDDINGS_REINDEX_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .map_err(|_| {
+                        ConfigError::Invalid("EMBEDDINGS_REINDEX_SECS must be a number")
+                    })?,
+            ),
+
+            search_hybrid_alpha: env::var("SEARCH_HYBRID_ALPHA")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .ok()
+                .filter(|a: &f64| (0.0..=1.0).contains(a))
+                .ok_or(ConfigError::Invalid(
+                    "SEARCH_HYBRID_ALPHA must be between 0 and 1",
+                ))?,
+
+            saved_search_check_interval: Duration::from_secs(
+                env::var("SAVED_SEARCH_CHECK_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .map_err(|_| {
+                        ConfigError::Invalid("SAVED_SEARCH_CHECK_SECS must be a number")
+                    })?,
+            ),
+
+            follow_check_interval: Duration::from_secs(
+                env::var("FOLLOW_CHECK_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("FOLLOW_CHECK_SECS must be a number"))?,
+            ),
+
+            storage: match env::var("STORAGE_BACKEND").as_deref().unwrap_or("local") {
+                "local" => StorageKind::Local,
+                "s3" => StorageKind::S3,
+                _ => {
+                    return Err(ConfigError::Invalid(
+                        "STORAGE_BACKEND must be 'local' or 's3'",
+                    ))
+                }
+            },
+
+            storage_local_dir: env::var("STORAGE_LOCAL_DIR")
+                .unwrap_or_else(|_| "data/uploads".to_string()),
+
+            s3_endpoint: env::var("S3_ENDPOINT").ok(),
+
+            s3_bucket: env::var("S3_BUCKET").ok(),
+
+            s3_region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+
+            s3_access_key_id: env::var("S3_ACCESS_KEY_ID").ok(),
+
+            s3_secret_access_key: env::var("S3_SECRET_ACCESS_KEY").ok(),
+
+            upload_max_bytes: env::var("UPLOAD_MAX_BYTES")
+                .unwrap_or_else(|_| "1073741824".to_string())
+                .parse()
+                .map_err(|_| ConfigError::Invalid("UPLOAD_MAX_BYTES must be a number"))?,
+
+            view_dedupe_window: Duration::from_secs(
+                env::var("VIEW_DEDUPE_SECS")
+                    .unwrap_or_else(|_| "1800".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("VIEW_DEDUPE_SECS must be a number"))?,
+            ),
+
+            webhook_dispatch_interval: Duration::from_secs(
+                env::var("WEBHOOK_DISPATCH_SECS")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("WEBHOOK_DISPATCH_SECS must be a number"))?,
+            ),
+
+            webhook_max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()
+                .ok()
+                .filter(|n: &i64| *n > 0)
+                .ok_or(ConfigError::Invalid(
+                    "WEBHOOK_MAX_ATTEMPTS must be a positive number",
+                ))?,
+
+            webhook_retry_base: Duration::from_secs(
+                env::var("WEBHOOK_RETRY_BASE_SECS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .map_err(|_| {
+                        ConfigError::Invalid("WEBHOOK_RETRY_BASE_SECS must be a number")
+                    })?,
+            ),
+
+            webhook_retention_days: env::var("WEBHOOK_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .ok()
+                .filter(|n: &i64| *n > 0)
+                .ok_or(ConfigError::Invalid(
+                    "WEBHOOK_RETENTION_DAYS must be a positive number",
+                ))?,
+
+            mailer: match env::var("MAIL_TRANSPORT").as_deref().unwrap_or("file") {
+                "file" => MailerKind::File,
+                "http" => MailerKind::Http,
+                _ => {
+                    return Err(ConfigError::Invalid(
+                        "MAIL_TRANSPORT must be 'file' or 'http'",
+                    ))
+                }
+            },
+
+            mail_from: env::var("MAIL_FROM")
+                .unwrap_or_else(|_| "AIKosh <no-reply@aikosh.local>".to_string()),
+
+            mail_file_dir: env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "data/outbox".to_string()),
+
+            mail_http_url: env::var("MAIL_HTTP_URL").ok(),
+
+            mail_http_api_key: env::var("MAIL_HTTP_API_KEY").ok(),
+
+            digest_check_interval: Duration::from_secs(
+                env::var("DIGEST_CHECK_SECS")
+                    .unwrap_or_else(|_| "900".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("DIGEST_CHECK_SECS must be a number"))?,
+            ),
+
+            trending_refresh_interval: Duration::from_secs(
+                env::var("TRENDING_REFRESH_SECS")
+                    .unwrap_or_else(|_| "600".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("TRENDING_REFRESH_SECS must be a number"))?,
+            ),
+
+            trending_half_life_hours: env::var("TRENDING_HALF_LIFE_HOURS")
+                .unwrap_or_else(|_| "72".to_string())
+                .parse()
+                .ok()
+                .filter(|h: &f64| *h > 0.0)
+                .ok_or(ConfigError::Invalid(
+                    "TRENDING_HALF_LIFE_HOURS must be a positive number",
+                ))?,
+
+            profile_check_interval: Duration::from_secs(
+                env::var("PROFILE_CHECK_SECS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::Invalid("PROFILE_CHECK_SECS must be a number"))?,
+            ),
+        };
+
+        if config.mailer == MailerKind::Http && config.mail_http_url.is_none() {
+            return Err(ConfigError::Missing("MAIL_HTTP_URL"));
+        }
+        if config.storage == StorageKind::S3 {
+            for (key, value) in [
+                ("S3_ENDPOINT", &config.s3_endpoint),
+                ("S3_BUCKET", &config.s3_bucket),
+                ("S3_ACCESS_KEY_ID", &config.s3_access_key_id),
+                ("S3_SECRET_ACCESS_KEY", &config.s3_secret_access_key),
+            ] {
+                if value.is_none() {
+                    return Err(ConfigError::Missing(key));
+                }
+            }
+        }
+        Ok(config)
     }
 }
 
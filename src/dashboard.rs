@@ -0,0 +1,269 @@
+//! Per-user dashboard metrics.
+//!
+//! Everything is derived from one `activity` stream: the user's views,
+//! downloads, likes, reviews, comments and contributions, each as
+//! (action, kind, id, at). Window counts, the timeline and the heatmap read it
+//! directly. Recommendations weight sectors by that activity plus followed
+//! sectors, then round-robin the most popular unseen entries across them.
+
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::{
+    ActivityEvent, ArtifactCounts, Contribution, Contributions, DashboardWindow, HeatmapDay,
+    RecentView, Recommendation,
+};
+
+/// Binds ?1 user id.
+const ACTIVITY_CTE: &str = r#"
+    activity AS (
+        SELECT 'viewed' AS action, artifact_kind AS kind, artifact_id AS id, created_at AS at
+        FROM view_events WHERE user_id = ?1
+        UNION ALL
+        SELECT 'downloaded', artifact_kind, artifact_id, created_at
+        FROM download_events WHERE user_id = ?1
+        UNION ALL
+        SELECT 'liked', artifact_kind, artifact_id, created_at
+        FROM likes WHERE user_id = ?1
+        UNION ALL
+        SELECT 'reviewed', artifact_kind, artifact_id, updated_at
+        FROM reviews WHERE user_id = ?1
+        UNION ALL
+        SELECT 'commented', artifact_kind, artifact_id, created_at
+        FROM comments WHERE user_id = ?1 AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'contributed', 'dataset', id, created_at
+        FROM datasets WHERE uploaded_by_user_id = ?1 AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'contributed', 'model', id, created_at
+        FROM models WHERE created_by_user_id = ?1 AND deleted_at IS NULL
+    )
+"#;
+
+const TIMELINE_LIMIT: i64 = 20;
+const RECENTLY_VIEWED_LIMIT: i64 = 10;
+const CONTRIBUTIONS_LIMIT: usize = 20;
+const RECOMMENDATIONS_LIMIT: i64 = 8;
+const HEATMAP_DAYS: i64 = 30;
+
+/// Distinct entries viewed, downloaded and liked within `window`.
+pub async fn activity_counts(
+    db: &SqlitePool,
+    user_id: i64,
+    window: DashboardWindow,
+) -> Result<(ArtifactCounts, ArtifactCounts, ArtifactCounts), AppError> {
+    let rows: Vec<(String, String, i64)> = sqlx::query_as(&format!(
+        r#"
+        WITH {ACTIVITY_CTE}
+        SELECT action, kind, COUNT(DISTINCT id)
+        FROM activity
+        WHERE action IN ('viewed', 'downloaded', 'liked')
+          AND (?2 IS NULL OR at >= datetime('now', ?2))
+        GROUP BY action, kind
+        "#
+    ))
+    .bind(user_id)
+    .bind(window.modifier())
+    .fetch_all(db)
+    .await?;
+
+    let (mut viewed, mut downloaded, mut liked) = Default::default();
+    for (action, kind, n) in rows {
+        let counts: &mut ArtifactCounts = match action.as_str() {
+            "viewed" => &mut viewed,
+            "downloaded" => &mut downloaded,
+            _ => &mut liked,
+        };
+        match kind.as_str() {
+            "dataset" => counts.datasets = n,
+            "model" => counts.models = n,
+            "usecase" => counts.use_cases = n,
+            _ => counts.articles = n,
+        }
+    }
+    Ok((viewed, downloaded, liked))
+}
+
+pub async fn contributions(db: &SqlitePool, user_id: i64) -> Result<Contributions, AppError> {
+    let mut items = sqlx::query_as::<_, Contribution>(
+        r#"
+        SELECT 'dataset' AS kind, d.id, d.title, d.visibility, d.rating_average, d.rating_count,
+               (SELECT COUNT(*) FROM comments c
+                WHERE c.artifact_kind = 'dataset' AND c.artifact_id = d.id
+                  AND c.deleted_at IS NULL AND c.hidden_at IS NULL) AS comment_count,
+               d.views_count, d.downloads_count, d.likes_count, d.created_at, d.updated_at
+        FROM datasets d
+        WHERE d.uploaded_by_user_id = ?1 AND d.deleted_at IS NULL
+        UNION ALL
+        SELECT 'model', m.id, m.title, m.visibility, m.rating_average, m.rating_count,
+               (SELECT COUNT(*) FROM comments c
+                WHERE c.artifact_kind = 'model' AND c.artifact_id = m.id
+                  AND c.deleted_at IS NULL AND c.hidden_at IS NULL),
+               m.views_count, m.downloads_count, m.likes_count, m.created_at, m.updated_at
+        FROM models m
+        WHERE m.created_by_user_id = ?1 AND m.deleted_at IS NULL
+        ORDER BY updated_at DESC, kind, id DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(db)
+    .await?;
+
+    let count =
+        |visibility: &str| items.iter().filter(|c| c.visibility == visibility).count() as i64;
+    let (open, restricted, private) = (count("Open"), count("Restricted"), count("Private"));
+    let total = items.len() as i64;
+    items.truncate(CONTRIBUTIONS_LIMIT);
+    Ok(Contributions {
+        total,
+        open,
+        restricted,
+        private,
+        items,
+    })
+}
+
+/// Most recent activity on entries that still exist, newest first.
+pub async fn timeline(db: &SqlitePool, user_id: i64) -> Result<Vec<ActivityEvent>, AppError> {
+    let rows = sqlx::query_as::<_, ActivityEvent>(&format!(
+        r#"
+        WITH {ACTIVITY_CTE}
+        SELECT a.action, a.kind, a.id,
+               COALESCE(d.title, m.title, u.title, r.title) AS title, a.at AS occurred_at
+        FROM activity a
+        LEFT JOIN datasets d ON a.kind = 'dataset' AND d.id = a.id AND d.deleted_at IS NULL
+        LEFT JOIN models m   ON a.kind = 'model'   AND m.id = a.id AND m.deleted_at IS NULL
+        LEFT JOIN usecases u ON a.kind = 'usecase' AND u.id = a.id AND u.deleted_at IS NULL
+        LEFT JOIN articles r ON a.kind = 'article' AND r.id = a.id AND r.deleted_at IS NULL
+        WHERE COALESCE(d.id, m.id, u.id, r.id) IS NOT NULL
+        ORDER BY a.at DESC, a.action, a.kind, a.id DESC
+        LIMIT ?2
+        "#
+    ))
+    .bind(user_id)
+    .bind(TIMELINE_LIMIT)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// Entries the user opened, each once at its latest view, newest first.
+pub async fn recently_viewed(db: &SqlitePool, user_id: i64) -> Result<Vec<RecentView>, AppError> {
+    let rows = sqlx::query_as::<_, RecentView>(
+        r#"
+        WITH latest AS (
+            SELECT artifact_kind, artifact_id, MAX(created_at) AS viewed_at
+            FROM view_events
+            WHERE user_id = ?1
+            GROUP BY artifact_kind, artifact_id
+        )
+        SELECT l.artifact_kind AS kind, l.artifact_id AS id,
+               COALESCE(d.title, m.title, u.title) AS title, l.viewed_at
+        FROM latest l
+        LEFT JOIN datasets d ON l.artifact_kind = 'dataset' AND d.id = l.artifact_id AND d.deleted_at IS NULL
+        LEFT JOIN models m   ON l.artifact_kind = 'model'   AND m.id = l.artifact_id AND m.deleted_at IS NULL
+        LEFT JOIN usecases u ON l.artifact_kind = 'usecase' AND u.id = l.artifact_id AND u.deleted_at IS NULL
+        WHERE COALESCE(d.id, m.id, u.id) IS NOT NULL
+        ORDER BY l.viewed_at DESC, l.artifact_kind, l.artifact_id DESC
+        LIMIT ?2
+        "#,
+    )
+    .bind(user_id)
+    .bind(RECENTLY_VIEWED_LIMIT)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// Open entries the user hasn't touched, from the sectors they engage with most.
+/// Sector score: view 1, like 2, download 3, review or comment 3, contribution 4
+/// over the last 90 days, plus 5 for following the sector. Without any signal
+/// this falls back to the most popular entries of each sector.
+pub async fn recommendations(
+    db: &SqlitePool,
+    user_id: i64,
+) -> Result<Vec<Recommendation>, AppError> {
+    let rows = sqlx::query_as::<_, Recommendation>(&format!(
+        r#"
+        WITH {ACTIVITY_CTE},
+        entries AS (
+            SELECT 'dataset' AS kind, id, title, description, image_url, sector_id,
+                   visibility = 'Open' AS listed, deleted_at,
+                   views_count + 2 * likes_count + 3 * downloads_count AS popularity
+            FROM datasets
+            UNION ALL
+            SELECT 'model', id, title, description, image_url, sector_id,
+                   visibility = 'Open', deleted_at,
+                   views_count + 2 * likes_count + 3 * downloads_count
+            FROM models
+            UNION ALL
+            SELECT 'usecase', id, title, description, image_url, sector_id,
+                   1, deleted_at, views_count + 2 * likes_count
+            FROM usecases
+        ),
+        interest AS (
+            SELECT sector_id, SUM(weight) AS score
+            FROM (
+                SELECT e.sector_id,
+                       CASE a.action
+                           WHEN 'viewed' THEN 1
+                           WHEN 'liked' THEN 2
+                           WHEN 'contributed' THEN 4
+                           ELSE 3
+                       END AS weight
+                FROM activity a
+                JOIN entries e ON e.kind = a.kind AND e.id = a.id
+                WHERE a.at >= datetime('now', '-90 days')
+                UNION ALL
+                SELECT target_id, 5 FROM follows WHERE user_id = ?1 AND target_kind = 'sector'
+            )
+            WHERE sector_id IS NOT NULL
+            GROUP BY sector_id
+        ),
+        candidates AS (
+            SELECT e.*, i.score,
+                   ROW_NUMBER() OVER (PARTITION BY e.sector_id
+                                      ORDER BY e.popularity DESC, e.kind, e.id DESC) AS rank
+            FROM entries e
+            LEFT JOIN interest i ON i.sector_id = e.sector_id
+            WHERE e.deleted_at IS NULL AND e.listed
+              AND NOT EXISTS (SELECT 1 FROM activity a WHERE a.kind = e.kind AND a.id = e.id)
+        )
+        SELECT c.kind, c.id, c.title, c.description, c.image_url, s.name AS sector,
+               CASE WHEN c.score IS NULL THEN 'popular' ELSE 'sector_interest' END AS reason
+        FROM candidates c
+        LEFT JOIN sectors s ON s.id = c.sector_id
+        ORDER BY c.score IS NULL, c.rank, c.score DESC, c.popularity DESC, c.kind, c.id DESC
+        LIMIT ?2
+        "#
+    ))
+    .bind(user_id)
+    .bind(RECOMMENDATIONS_LIMIT)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+/// Activity per UTC day for the last 30 days, oldest first, zero-filled.
+pub async fn heatmap(db: &SqlitePool, user_id: i64) -> Result<Vec<HeatmapDay>, AppError> {
+    let rows = sqlx::query_as::<_, HeatmapDay>(&format!(
+        r#"
+        WITH RECURSIVE days(day) AS (
+            SELECT date('now', printf('-%d days', ?2 - 1))
+            UNION ALL
+            SELECT date(day, '+1 day') FROM days WHERE day < date('now')
+        ),
+        {ACTIVITY_CTE}
+        SELECT days.day AS date, COUNT(a.at) AS count
+        FROM days
+        LEFT JOIN activity a ON date(a.at) = days.day
+        GROUP BY days.day
+        ORDER BY days.day
+        "#
+    ))
+    .bind(user_id)
+    .bind(HEATMAP_DAYS)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
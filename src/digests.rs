@@ -0,0 +1,356 @@
+//! Email digests of new catalog content.
+//!
+//! Users opt into a daily or weekly digest of datasets, models, use cases and
+//! articles created in the sectors and tags they follow. Articles have no
+//! sector or tags, so they match when their category names a followed sector
+//! or tag. `spawn_sender` mails every digest that is due through the
+//! configured `Mailer`; windows with nothing new are skipped silently.
+
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+
+use crate::errors::AppError;
+use crate::mail::{Email, Mailer};
+use crate::models::{ArtifactKind, DigestFrequency};
+use crate::state::AppState;
+
+/// Entries listed per section; the rest are summed up in one line.
+const SECTION_LIMIT: usize = 10;
+
+/// Characters of description shown under each entry.
+const SUMMARY_CHARS: usize = 160;
+
+/// Binds: ?1 user id, ?2 window start (exclusive), ?3 window end (inclusive).
+const DIGEST_SQL: &str = r#"
+    WITH f AS (SELECT target_kind, target_id FROM follows
+               WHERE user_id = ?1 AND target_kind IN ('sector', 'tag')),
+    followed_names AS (
+        SELECT lower(s.name) AS name FROM sectors s
+        JOIN f ON f.target_kind = 'sector' AND f.target_id = s.id
+        UNION
+        SELECT lower(t.name) FROM tags t
+        JOIN f ON f.target_kind = 'tag' AND f.target_id = t.id
+    )
+    SELECT 'dataset' AS kind, d.id, d.title, d.description, d.created_at
+    FROM datasets d
+    WHERE d.deleted_at IS NULL AND d.created_at > ?2 AND d.created_at <= ?3
+      AND (d.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+           OR EXISTS (SELECT 1 FROM dataset_tags x
+                      WHERE x.dataset_id = d.id
+                        AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+    UNION ALL
+    SELECT 'model', m.id, m.title, m.description, m.created_at
+    FROM models m
+    WHERE m.deleted_at IS NULL AND m.created_at > ?2 AND m.created_at <= ?3
+      AND (m.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+           OR EXISTS (SELECT 1 FROM model_tags x
+                      WHERE x.model_id = m.id
+                        AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+    UNION ALL
+    SELECT 'usecase', u.id, u.title, u.description, u.created_at
+    FROM usecases u
+    WHERE u.deleted_at IS NULL AND u.created_at > ?2 AND u.created_at <= ?3
+      AND (u.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+           OR EXISTS (SELECT 1 FROM usecase_tags x
+                      WHERE x.usecase_id = u.id
+                        AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+    UNION ALL
+    SELECT 'article', a.id, a.title, a.description, a.created_at
+    FROM articles a
+    WHERE a.deleted_at IS NULL AND a.created_at > ?2 AND a.created_at <= ?3
+      AND lower(a.category) IN (SELECT name FROM followed_names)
+    ORDER BY created_at DESC, id DESC
+"#;
+
+/// SQLite datetime modifier for the window a digest covers.
+pub fn period(frequency: DigestFrequency) -> &'static str {
+    match frequency {
+        DigestFrequency::Weekly => "-7 days",
+        DigestFrequency::Daily | DigestFrequency::Off => "-1 day",
+    }
+}
+
+#[derive(FromRow)]
+pub struct DigestItem {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+}
+
+pub struct Digest {
+    pub frequency: DigestFrequency,
+    pub period_start: String,
+    pub period_end: String,
+    pub items: Vec<DigestItem>,
+}
+
+/// New entries for `user_id` created in (`period_start`, `period_end`].
+pub async fn collect(
+    db: &SqlitePool,
+    user_id: i64,
+    frequency: DigestFrequency,
+    period_start: String,
+    period_end: String,
+) -> Result<Digest, AppError> {
+    let items = sqlx::query_as::<_, DigestItem>(DIGEST_SQL)
+        .bind(user_id)
+        .bind(&period_start)
+        .bind(&period_end)
+        .fetch_all(db)
+        .await?;
+    Ok(Digest {
+        frequency,
+        period_start,
+        period_end,
+        items,
+    })
+}
+
+// =============================================================================
+// TEMPLATES
+// =============================================================================
+
+fn section_title(kind: ArtifactKind) -> &'static str {
+    match kind {
+        ArtifactKind::Dataset => "New datasets",
+        ArtifactKind::Model => "New models",
+        ArtifactKind::UseCase => "New use cases",
+        ArtifactKind::Article => "New articles",
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn summary(description: &str) -> String {
+    let flat = description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= SUMMARY_CHARS {
+        return flat;
+    }
+    let cut: String = flat.chars().take(SUMMARY_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Non-empty sections in catalog order, each with its total before truncation.
+fn sections(digest: &Digest) -> Vec<(ArtifactKind, Vec<&DigestItem>, usize)> {
+    ArtifactKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let items: Vec<_> = digest
+                .items
+                .iter()
+                .filter(|i| i.kind == kind.as_str())
+                .collect();
+            let total = items.len();
+            (total > 0).then(|| (kind, items.into_iter().take(SECTION_LIMIT).collect(), total))
+        })
+        .collect()
+}
+
+/// Plain-text and HTML versions of the digest, linking into the frontend at `base_url`.
+pub fn render(digest: &Digest, to: &str, name: &str, base_url: &str) -> Email {
+    let base_url = base_url.trim_end_matches('/');
+    let cadence = match digest.frequency {
+        DigestFrequency::Weekly => "weekly",
+        DigestFrequency::Daily | DigestFrequency::Off => "daily",
+    };
+    let count = digest.items.len();
+    let subject = format!(
+        "Your {cadence} AIKosh digest: {count} new item{}",
+        if count == 1 { "" } else { "s" }
+    );
+    let intro = format!(
+        "Here is what was added to AIKosh in the sectors and tags you follow between {} and {} (UTC).",
+        digest.period_start, digest.period_end
+    );
+    let link = |kind: ArtifactKind, id: i64| format!("{base_url}/{}/{id}", kind.table());
+    let settings = format!("{base_url}/profile");
+
+    let mut text = format!("Hi {name},\n\n{intro}\n");
+    let mut body = format!(
+        "<p>Hi {},</p>\n<p>{}</p>\n",
+        escape_html(name),
+        escape_html(&intro)
+    );
+    for (kind, items, total) in sections(digest) {
+        let heading = format!("{} ({total})", section_title(kind));
+        text.push_str(&format!(
+            "\n{heading}\n{}\n",
+            "-".repeat(heading.chars().count())
+        ));
+        body.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape_html(&heading)));
+        for item in &items {
+            let url = link(kind, item.id);
+            let summary = summary(&item.description);
+            text.push_str(&format!("* {}\n  {summary}\n  {url}\n", item.title));
+            body.push_str(&format!(
+                "  <li><a href=\"{}\">{}</a><br><span style=\"color:#555\">{}</span></li>\n",
+                escape_html(&url),
+                escape_html(&item.title),
+                escape_html(&summary)
+            ));
+        }
+        if total > items.len() {
+            let more = format!("…and {} more", total - items.len());
+            text.push_str(&format!("{more}: {base_url}/{}\n", kind.table()));
+            body.push_str(&format!(
+                "  <li><a href=\"{base_url}/{}\">{}</a></li>\n",
+                kind.table(),
+                escape_html(&more)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    text.push_str(&format!(
+        "\nYou get this email because you opted into {cadence} digests. \
+         Change or turn them off in your profile: {settings}\n"
+    ));
+    let html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n\
+         <body style=\"font-family:sans-serif;max-width:640px\">\n{body}\
+         <p style=\"color:#777;font-size:12px\">You get this email because you opted into \
+         {cadence} digests. <a href=\"{}\">Change or turn them off</a> in your profile.</p>\n\
+         </body>\n</html>\n",
+        escape_html(&subject),
+        escape_html(&settings)
+    );
+
+    Email {
+        to: to.to_string(),
+        subject,
+        text,
+        html,
+    }
+}
+
+// =============================================================================
+// SCHEDULER
+// =============================================================================
+
+#[derive(FromRow)]
+struct DueDigest {
+    user_id: i64,
+    frequency: String,
+    email: String,
+    full_name: String,
+    period_start: String,
+    period_end: String,
+}
+
+/// Send every digest whose period has elapsed; returns how many were mailed.
+/// A failed send leaves the window open, so the next run retries it.
+pub async fn send_due(
+    db: &SqlitePool,
+    mailer: &dyn Mailer,
+    base_url: &str,
+) -> Result<u64, AppError> {
+    let due = sqlx::query_as::<_, DueDigest>(
+        r#"
+        SELECT p.user_id, p.frequency, u.email, u.full_name,
+               COALESCE(p.last_sent_at, datetime('now', w.period)) AS period_start,
+               datetime('now') AS period_end
+        FROM digest_preferences p
+        JOIN users u ON u.id = p.user_id AND u.deleted_at IS NULL
+        JOIN (SELECT 'daily' AS frequency, '-1 day' AS period
+              UNION ALL SELECT 'weekly', '-7 days') w ON w.frequency = p.frequency
+        WHERE COALESCE(p.last_sent_at, p.opted_in_at) <= datetime('now', w.period)
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut sent = 0;
+    for d in due {
+        // One user's failure must not hold up everyone else's digest.
+        match send_one(db, mailer, base_url, &d).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!("digest for user {} failed: {e}", d.user_id),
+        }
+    }
+    Ok(sent)
+}
+
+/// Build, mail and log one due digest, then close its window. Returns whether
+/// an email went out; an empty digest closes the window without mailing.
+async fn send_one(
+    db: &SqlitePool,
+    mailer: &dyn Mailer,
+    base_url: &str,
+    d: &DueDigest,
+) -> Result<bool, AppError> {
+    let frequency = match d.frequency.as_str() {
+        "weekly" => DigestFrequency::Weekly,
+        _ => DigestFrequency::Daily,
+    };
+    let digest = collect(
+        db,
+        d.user_id,
+        frequency,
+        d.period_start.clone(),
+        d.period_end.clone(),
+    )
+    .await?;
+
+    let mailed = !digest.items.is_empty();
+    if mailed {
+        let email = render(&digest, &d.email, &d.full_name, base_url);
+        if let Err(e) = mailer.send(&email).await {
+            tracing::warn!("digest for user {} not sent: {e}", d.user_id);
+            return Ok(false);
+        }
+        // The email is out: a missing log row must not reopen the window and
+        // mail the same items again.
+        if let Err(e) = sqlx::query(
+            r#"
+            INSERT INTO digest_log
+                (user_id, frequency, period_start, period_end, item_count, subject)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(d.user_id)
+        .bind(frequency.as_str())
+        .bind(&digest.period_start)
+        .bind(&digest.period_end)
+        .bind(digest.items.len() as i64)
+        .bind(&email.subject)
+        .execute(db)
+        .await
+        {
+            tracing::error!("digest for user {} sent but not logged: {e}", d.user_id);
+        }
+    }
+
+    sqlx::query("UPDATE digest_preferences SET last_sent_at = ?1 WHERE user_id = ?2")
+        .bind(&digest.period_end)
+        .bind(d.user_id)
+        .execute(db)
+        .await?;
+    Ok(mailed)
+}
+
+/// Send due digests every `digest_check_interval`.
+pub fn spawn_sender(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.digest_check_interval);
+        loop {
+            ticker.tick().await;
+            match send_due(
+                &state.db,
+                state.mailer.as_ref(),
+                &state.config.frontend_origin,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("sent {n} email digests"),
+                Err(e) => tracing::error!("digest run failed: {e}"),
+            }
+        }
+    });
+}
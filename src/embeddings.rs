@@ -0,0 +1,514 @@
+//! Embeddings for semantic search.
+//!
+//! An `Embedder` turns text into L2-normalized vectors. `HttpEmbedder` calls
+//! any OpenAI-compatible `/embeddings` endpoint (a local sentence-embedding
+//! server or a hosted API) at `EMBEDDINGS_URL`, and is what makes the search
+//! semantic; it is used whenever that URL is set. Without it the server falls
+//! back to `HashingEmbedder` (also selectable with `EMBEDDER=hashing` for
+//! tests) and logs a warning: feature hashing over words and character
+//! trigrams needs no model, but only matches shared spelling ("agricultural"
+//! with "agriculture"), not meaning.
+//!
+//! Vectors live in the `embeddings` table next to the catalog rows and are
+//! refreshed by `spawn_indexer`. `hybrid_search` blends them with bm25 from
+//! the FTS tables; `similar_to` feeds the related-artifacts ranking.
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::{Config, EmbedderKind};
+use crate::errors::AppError;
+use crate::models::{ArtifactKind, SemanticHit};
+use crate::search::build_fts_any_query;
+use crate::state::AppState;
+
+/// Rows embedded per indexer batch (and per HTTP request).
+const INDEX_BATCH_SIZE: i64 = 32;
+
+/// Characters of source text fed to the embedder per artifact.
+const MAX_EMBED_CHARS: usize = 2000;
+
+/// Keyword candidates pulled from each FTS table before blending.
+const KEYWORD_CANDIDATES: i64 = 50;
+
+pub trait Embedder: Send + Sync {
+    /// Identifies the model; stored with each vector so a change re-embeds.
+    fn model_id(&self) -> String;
+
+    fn embed<'a>(&'a self, texts: &'a [String]) -> BoxFuture<'a, Result<Vec<Vec<f32>>, AppError>>;
+}
+
+pub fn from_config(config: &Config) -> Arc<dyn Embedder> {
+    match (config.embedder, &config.embeddings_url) {
+        (EmbedderKind::Http, Some(url)) => Arc::new(HttpEmbedder {
+            client: reqwest::Client::new(),
+            url: url.clone(),
+            model: config.embeddings_model.clone(),
+            api_key: config.embeddings_api_key.clone(),
+        }),
+        (kind, _) => {
+            if kind == EmbedderKind::Http {
+                tracing::warn!("EMBEDDER=http but EMBEDDINGS_URL is not set");
+            }
+            tracing::warn!(
+                "no embeddings service configured: semantic search falls back to lexical matching"
+            );
+            Arc::new(HashingEmbedder {
+                dim: config.embeddings_dim,
+            })
+        }
+    }
+}
+
+// =============================================================================
+// VECTOR HELPERS
+// =============================================================================
+
+fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// Cosine similarity of two L2-normalized vectors.
+fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| (x * y) as f64).sum()
+}
+
+fn encode(v: &[f32]) -> Vec<u8> {
+    v.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+// =============================================================================
+// HASHING EMBEDDER (lexical fallback for tests and offline development)
+// =============================================================================
+
+pub struct HashingEmbedder {
+    dim: usize,
+}
+
+/// FNV-1a; stable across builds, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl HashingEmbedder {
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut v = vec![0f32; self.dim];
+        let mut add = |feature: &str, weight: f32| {
+            let h = fnv1a(feature.as_bytes());
+            let idx = (h % self.dim as u64) as usize;
+            let sign = if h >> 63 == 0 { 1.0 } else { -1.0 };
+            v[idx] += sign * weight;
+        };
+
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() > 1)
+        {
+            let word = word.to_lowercase();
+            add(&format!("w:{word}"), 1.0);
+
+            let padded: Vec<char> = format!("<{word}>").chars().collect();
+            for tri in padded.windows(3) {
+                add(&format!("t:{}", tri.iter().collect::<String>()), 0.5);
+            }
+        }
+
+        normalize(&mut v);
+        v
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn model_id(&self) -> String {
+        format!("hashing-v1-{}", self.dim)
+    }
+
+    fn embed<'a>(&'a self, texts: &'a [String]) -> BoxFuture<'a, Result<Vec<Vec<f32>>, AppError>> {
+        Box::pin(async move { Ok(texts.iter().map(|t| self.embed_one(t)).collect()) })
+    }
+}
+
+// =============================================================================
+// HTTP EMBEDDER (OpenAI-compatible)
+// =============================================================================
+
+pub struct HttpEmbedder {
+    client: reqwest::Client,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl Embedder for HttpEmbedder {
+    fn model_id(&self) -> String {
+        self.model.clone()
+    }
+
+    fn embed<'a>(&'a self, texts: &'a [String]) -> BoxFuture<'a, Result<Vec<Vec<f32>>, AppError>> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&EmbeddingRequest {
+                model: &self.model,
+                input: texts,
+            });
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+
+            let response = request
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| AppError::Embedding(e.to_string()))?;
+            let mut body: EmbeddingResponse = response
+                .json()
+                .await
+                .map_err(|e| AppError::Embedding(e.to_string()))?;
+
+            if body.data.len() != texts.len() {
+                return Err(AppError::Embedding(format!(
+                    "expected {} embeddings, got {}",
+                    texts.len(),
+                    body.data.len()
+                )));
+            }
+            body.data.sort_by_key(|d| d.index);
+            Ok(body
+                .data
+                .into_iter()
+                .map(|d| {
+                    let mut v = d.embedding;
+                    normalize(&mut v);
+                    v
+                })
+                .collect())
+        })
+    }
+}
+
+// =============================================================================
+// INDEXER
+// =============================================================================
+
+/// SQL returning (id, text, updated_at) for rows whose embedding is missing,
+/// stale, or from another model. ?1 = model id, ?2 = batch size.
+fn stale_rows_sql(kind: ArtifactKind) -> String {
+    let text = match kind {
+        ArtifactKind::Dataset => {
+            "t.title || '. ' || t.description || ' ' || COALESCE(t.about_dataset, '')"
+        }
+        ArtifactKind::Model => {
+            "t.title || '. ' || t.description || ' ' || COALESCE(t.about_model, '')"
+        }
+        ArtifactKind::UseCase => {
+            "t.title || '. ' || t.description || ' ' || COALESCE(t.about_use_case, '')"
+        }
+        ArtifactKind::Article => "t.title || '. ' || t.description || ' ' || t.content",
+    };
+    format!(
+        r#"
+        SELECT t.id, {text}, t.updated_at
+        FROM {table} t
+        LEFT JOIN embeddings e ON e.kind = '{kind}' AND e.item_id = t.id
+        WHERE t.deleted_at IS NULL
+          AND (e.item_id IS NULL OR e.model <> ?1 OR e.source_updated_at <> t.updated_at)
+        LIMIT ?2
+        "#,
+        table = kind.table(),
+        kind = kind.as_str(),
+    )
+}
+
+/// Embed every artifact whose vector is missing or out of date.
+/// Returns the number of rows (re-)embedded.
+pub async fn reindex(db: &SqlitePool, embedder: &dyn Embedder) -> Result<usize, AppError> {
+    let model = embedder.model_id();
+    let mut total = 0;
+
+    for kind in ArtifactKind::ALL {
+        let sql = stale_rows_sql(kind);
+        loop {
+            let rows: Vec<(i64, String, String)> = sqlx::query_as(&sql)
+                .bind(&model)
+                .bind(INDEX_BATCH_SIZE)
+                .fetch_all(db)
+                .await?;
+            if rows.is_empty() {
+                break;
+            }
+
+            let texts: Vec<String> = rows
+                .iter()
+                .map(|(_, text, _)| text.chars().take(MAX_EMBED_CHARS).collect())
+                .collect();
+            let vectors = embedder.embed(&texts).await?;
+
+            let mut tx = db.begin().await?;
+            for ((id, _, updated_at), vector) in rows.iter().zip(&vectors) {
+                sqlx::query(
+                    r#"
+                    INSERT INTO embeddings (kind, item_id, model, dim, vector, source_updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (kind, item_id) DO UPDATE SET
+                        model             = excluded.model,
+                        dim               = excluded.dim,
+                        vector            = excluded.vector,
+                        source_updated_at = excluded.source_updated_at,
+                        updated_at        = datetime('now')
+                    "#,
+                )
+                .bind(kind.as_str())
+                .bind(id)
+                .bind(&model)
+                .bind(vector.len() as i64)
+                .bind(encode(vector))
+                .bind(updated_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            total += rows.len();
+        }
+    }
+
+    Ok(total)
+}
+
+/// Run `reindex` now and then every `embeddings_reindex_interval`.
+pub fn spawn_indexer(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.embeddings_reindex_interval);
+        loop {
+            ticker.tick().await;
+            match reindex(&state.db, state.embedder.as_ref()).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("embedded {n} artifacts"),
+                Err(e) => tracing::error!("embedding reindex failed: {e}"),
+            }
+        }
+    });
+}
+
+// =============================================================================
+// HYBRID SEARCH
+// =============================================================================
+
+/// bm25 keyword hits for `kind`, as (id, score) with higher = better.
+async fn keyword_hits(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    fts: &str,
+) -> Result<Vec<(i64, f64)>, AppError> {
+    let sql = format!(
+        r#"
+        SELECT t.id, -bm25({fts_table})
+        FROM {fts_table}
+        JOIN {table} t ON t.id = {fts_table}.rowid
+        WHERE {fts_table} MATCH ?1
+          AND t.deleted_at IS NULL
+        ORDER BY rank
+        LIMIT ?2
+        "#,
+        fts_table = kind.fts_table(),
+        table = kind.table(),
+    );
+    let rows = sqlx::query_as(&sql)
+        .bind(fts)
+        .bind(KEYWORD_CANDIDATES)
+        .fetch_all(db)
+        .await?;
+    Ok(rows)
+}
+
+/// Title/description for the given ids of `kind`, skipping deleted rows.
+async fn hydrate(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    ids: &[i64],
+) -> Result<HashMap<i64, (String, String)>, AppError> {
+    let sql = format!(
+        r#"
+        SELECT id, title, description FROM {table}
+        WHERE deleted_at IS NULL AND id IN (SELECT value FROM json_each(?1))
+        "#,
+        table = kind.table(),
+    );
+    let rows: Vec<(i64, String, String)> = sqlx::query_as(&sql)
+        .bind(serde_json::json!(ids).to_string())
+        .fetch_all(db)
+        .await?;
+    Ok(rows.into_iter().map(|(id, t, d)| (id, (t, d))).collect())
+}
+
+/// Rank artifacts of `kinds` against `query` by
+/// `alpha * cosine + (1 - alpha) * normalized_bm25`.
+/// bm25 is normalized so the best keyword hit across all kinds scores 1.0.
+pub async fn hybrid_search(
+    db: &SqlitePool,
+    embedder: &dyn Embedder,
+    query: &str,
+    kinds: &[ArtifactKind],
+    limit: usize,
+    alpha: f64,
+) -> Result<Vec<SemanticHit>, AppError> {
+    // (kind, id) -> (keyword_score, similarity)
+    let mut scores: HashMap<(ArtifactKind, i64), (f64, f64)> = HashMap::new();
+
+    let fts = build_fts_any_query(query);
+    let mut keyword = Vec::new();
+    for &kind in kinds {
+        for (id, s) in keyword_hits(db, kind, &fts).await? {
+            keyword.push((kind, id, s));
+        }
+    }
+    let best = keyword.iter().map(|(_, _, s)| *s).fold(0.0, f64::max);
+    if best > 0.0 {
+        for (kind, id, s) in keyword {
+            scores.entry((kind, id)).or_default().0 = s / best;
+        }
+    }
+
+    // An unreachable embedder degrades to keyword-only ranking.
+    let query_vec = match embedder.embed(&[query.to_string()]).await {
+        Ok(mut v) => v.pop(),
+        Err(e) => {
+            tracing::warn!("query embedding failed, using keyword ranking only: {e}");
+            None
+        }
+    };
+    let vectors: Vec<(String, i64, Vec<u8>)> = match &query_vec {
+        Some(_) => {
+            sqlx::query_as("SELECT kind, item_id, vector FROM embeddings WHERE model = ?1")
+                .bind(embedder.model_id())
+                .fetch_all(db)
+                .await?
+        }
+        None => Vec::new(),
+    };
+    let query_vec = query_vec.unwrap_or_default();
+    for (kind, id, bytes) in vectors {
+        let Some(kind) = kinds.iter().copied().find(|k| k.as_str() == kind) else {
+            continue;
+        };
+        let sim = cosine(&query_vec, &decode(&bytes)).max(0.0);
+        if sim > 0.0 {
+            scores.entry((kind, id)).or_default().1 = sim;
+        }
+    }
+
+    let mut ranked: Vec<_> = scores
+        .into_iter()
+        .map(|(key, (kw, sim))| (key, kw, sim, alpha * sim + (1.0 - alpha) * kw))
+        .filter(|(_, _, _, score)| *score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.3.total_cmp(&a.3));
+
+    // Over-fetch a little so rows deleted since indexing don't shorten the page.
+    ranked.truncate(limit * 2);
+    let mut details = HashMap::new();
+    for &kind in kinds {
+        let ids: Vec<i64> = ranked
+            .iter()
+            .filter(|((k, _), ..)| *k == kind)
+            .map(|((_, id), ..)| *id)
+            .collect();
+        if !ids.is_empty() {
+            details.insert(kind, hydrate(db, kind, &ids).await?);
+        }
+    }
+
+    Ok(ranked
+        .into_iter()
+        .filter_map(|((kind, id), kw, sim, score)| {
+            let (title, description) = details.get(&kind)?.get(&id)?.clone();
+            Some(SemanticHit {
+                kind,
+                id,
+                title,
+                description,
+                score,
+                keyword_score: kw,
+                similarity: sim,
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
+// =============================================================================
+// NEAREST NEIGHBOURS
+// =============================================================================
+
+/// Cosine similarity of every other embedded artifact to (`kind`, `id`).
+/// Empty when the source hasn't been embedded yet.
+pub async fn similar_to(
+    db: &SqlitePool,
+    embedder: &dyn Embedder,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<HashMap<(ArtifactKind, i64), f64>, AppError> {
+    let model = embedder.model_id();
+    let source: Option<(Vec<u8>,)> = sqlx::query_as(
+        "SELECT vector FROM embeddings WHERE kind = ?1 AND item_id = ?2 AND model = ?3",
+    )
+    .bind(kind.as_str())
+    .bind(id)
+    .bind(&model)
+    .fetch_optional(db)
+    .await?;
+    let Some((source,)) = source else {
+        return Ok(HashMap::new());
+    };
+    let source = decode(&source);
+
+    let rows: Vec<(String, i64, Vec<u8>)> =
+        sqlx::query_as("SELECT kind, item_id, vector FROM embeddings WHERE model = ?1")
+            .bind(&model)
+            .fetch_all(db)
+            .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(k, item_id, bytes)| {
+            let k = k.parse::<ArtifactKind>().ok()?;
+            if (k, item_id) == (kind, id) {
+                return None;
+            }
+            let sim = cosine(&source, &decode(&bytes)).max(0.0);
+            (sim > 0.0).then_some(((k, item_id), sim))
+        })
+        .collect())
+}
@@ -11,6 +11,9 @@ pub enum AppError {
     #[error("not found")]
     NotFound,
 
+    #[error("forbidden")]
+    Forbidden,
+
     #[error("validation error: {0}")]
     ValidationError(String),
 
@@ -22,17 +25,32 @@ pub enum AppError {
 
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("embedding error: {0}")]
+    Embedding(String),
+
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("mail error: {0}")]
+    Mail(String),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
             AppError::NotFound => (StatusCode::NOT_FOUND, "Not Found".to_string()),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden".to_string()),
             AppError::Database(sqlx::Error::RowNotFound) => {
                 (StatusCode::NOT_FOUND, "Not Found".to_string())
             }
             AppError::ValidationError(m) => (StatusCode::BAD_REQUEST, m.clone()),
-            AppError::Database(_) | AppError::Json(_) | AppError::Io(_) => {
+            AppError::Database(_)
+            | AppError::Json(_)
+            | AppError::Io(_)
+            | AppError::Embedding(_)
+            | AppError::Storage(_)
+            | AppError::Mail(_) => {
                 tracing::error!("internal error: {self}");
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
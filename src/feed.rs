@@ -0,0 +1,113 @@
+//! Personal activity feed.
+//!
+//! Datasets, models, use cases and articles reach a user's feed through what
+//! they follow: the entry's organization, sector or tags, or the user who
+//! uploaded it. Articles carry no such links, only a free-text author, so they
+//! match a followed user by full name. Each entry appears once, at its latest
+//! activity, newest first; pages are cut with an opaque keyset cursor.
+
+use crate::errors::AppError;
+use crate::models::{FeedItem, FeedPage};
+use sqlx::SqlitePool;
+
+/// Binds: ?1 user id, ?2/?3/?4 cursor (occurred_at, kind, id) or NULLs, ?5 limit.
+const FEED_SQL: &str = r#"
+    WITH f AS (SELECT target_kind, target_id FROM follows WHERE user_id = ?1),
+    entries AS (
+        SELECT 'dataset' AS kind, d.id, d.title, d.description, d.image_url,
+               d.created_at, d.updated_at
+        FROM datasets d
+        WHERE d.deleted_at IS NULL
+          AND (d.organization_id IN (SELECT target_id FROM f WHERE target_kind = 'organization')
+               OR d.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+               OR d.uploaded_by_user_id IN (SELECT target_id FROM f WHERE target_kind = 'user')
+               OR EXISTS (SELECT 1 FROM dataset_tags x
+                          WHERE x.dataset_id = d.id
+                            AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+        UNION ALL
+        SELECT 'model', m.id, m.title, m.description, m.image_url, m.created_at, m.updated_at
+        FROM models m
+        WHERE m.deleted_at IS NULL
+          AND (m.organization_id IN (SELECT target_id FROM f WHERE target_kind = 'organization')
+               OR m.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+               OR m.created_by_user_id IN (SELECT target_id FROM f WHERE target_kind = 'user')
+               OR EXISTS (SELECT 1 FROM model_tags x
+                          WHERE x.model_id = m.id
+                            AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+        UNION ALL
+        SELECT 'usecase', u.id, u.title, u.description, u.image_url, u.created_at, u.updated_at
+        FROM usecases u
+        WHERE u.deleted_at IS NULL
+          AND (u.organization_id IN (SELECT target_id FROM f WHERE target_kind = 'organization')
+               OR u.sector_id IN (SELECT target_id FROM f WHERE target_kind = 'sector')
+               OR EXISTS (SELECT 1 FROM usecase_tags x
+                          WHERE x.usecase_id = u.id
+                            AND x.tag_id IN (SELECT target_id FROM f WHERE target_kind = 'tag')))
+        UNION ALL
+        SELECT 'article', a.id, a.title, a.description, a.image_url, a.created_at, a.updated_at
+        FROM articles a
+        WHERE a.deleted_at IS NULL
+          AND a.author IN (SELECT p.full_name FROM users p
+                           JOIN f ON f.target_kind = 'user' AND f.target_id = p.id
+                           WHERE p.deleted_at IS NULL)
+    ),
+    feed AS (
+        SELECT kind, id, title, description, image_url,
+               CASE WHEN updated_at > created_at THEN 'updated' ELSE 'published' END AS event,
+               MAX(created_at, updated_at) AS occurred_at
+        FROM entries
+    )
+    SELECT kind, id, title, description, image_url, event, occurred_at
+    FROM feed
+    WHERE ?2 IS NULL OR (occurred_at, kind, id) < (?2, ?3, ?4)
+    ORDER BY occurred_at DESC, kind DESC, id DESC
+    LIMIT ?5
+"#;
+
+fn encode_cursor(item: &FeedItem) -> String {
+    hex::encode(format!("{}|{}|{}", item.occurred_at, item.kind, item.id))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(String, String, i64)> {
+    let raw = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+    let mut parts = raw.splitn(3, '|');
+    let occurred_at = parts.next()?.to_string();
+    let kind = parts.next()?.to_string();
+    let id = parts.next()?.parse().ok()?;
+    Some((occurred_at, kind, id))
+}
+
+/// One page of `user_id`'s feed, starting after `cursor`.
+pub async fn feed(
+    db: &SqlitePool,
+    user_id: i64,
+    cursor: Option<&str>,
+    limit: i64,
+) -> Result<FeedPage, AppError> {
+    let after = cursor
+        .map(|c| {
+            decode_cursor(c).ok_or_else(|| AppError::ValidationError("Invalid cursor".to_string()))
+        })
+        .transpose()?;
+    let (at, kind, id) = match after {
+        Some((at, kind, id)) => (Some(at), Some(kind), Some(id)),
+        None => (None, None, None),
+    };
+
+    // One extra row tells us whether there is a next page.
+    let mut items = sqlx::query_as::<_, FeedItem>(FEED_SQL)
+        .bind(user_id)
+        .bind(at)
+        .bind(kind)
+        .bind(id)
+        .bind(limit + 1)
+        .fetch_all(db)
+        .await?;
+    let next_cursor = if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        items.last().map(encode_cursor)
+    } else {
+        None
+    };
+    Ok(FeedPage { items, next_cursor })
+}
@@ -1,21 +1,53 @@
 //! HTTP handlers — Step 2B Batch 2: real SQL implementations.
 
-use axum::extract::{Path, Query, State};
+use axum::body::Body;
+use axum::extract::multipart::MultipartError;
+use axum::extract::{Multipart, Path, Query, State};
+use axum::http::{header, HeaderMap, Method, StatusCode};
 use axum::response::sse::{Event, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use futures::stream::Stream;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
 use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::time::sleep;
 
+use crate::analytics::{self, ChatLog};
+use crate::dashboard;
+use crate::digests;
+use crate::embeddings;
 use crate::errors::AppError;
+use crate::feed;
 use crate::models::{
-    Article, ArtifactCounts, ChatMessage, Dashboard, Dataset, DownloadCounts, ListQuery, Model,
-    Organization, PythonChatRequest, PythonChatResponse, Sector, Toolkit, Tutorial,
-    UpdateUserProfile, UseCase, User,
+    AddCollectionItem, AnalyticsFormat, AnalyticsMetric, AnalyticsQuery, Article, ArtifactKind,
+    ChatMessage, ClickThroughStats, Collection, CollectionItem, ColumnType, Comment, CommentPage,
+    CommentQuery, CreateCollection, CreateComment, CreateDatasetColumn, CreateSavedSearch,
+    CreateSearchClick, CreateSearchSynonym, CreateWebhook, Dashboard, DashboardQuery, Dataset,
+    DatasetColumn, DatasetFile, DeliveryQuery, DigestFrequency, DigestPreferences, DigestPreview,
+    DigestPreviewQuery, FeedPage, FeedQuery, FileProfile, Follow, FollowState, ForkCollection,
+    LikeState, LikedItem, ListQuery, ListSort, Model, ModerateComment, Notification,
+    NotificationQuery, Organization, PythonChatRequest, PythonChatResponse, RatingSummary,
+    RelatedItem, RelatedQuery, ReorderCollection, Review, SavedSearch, SearchReportQuery,
+    SearchResults, SearchSynonym, Sector, SemanticHit, SemanticSearchQuery, SuggestQuery,
+    Suggestion, Toolkit, TopSearchQuery, TrendingItem, TrendingQuery, Tutorial, UnreadCount,
+    UpdateCollection, UpdateCollectionItem, UpdateComment, UpdateDatasetColumn,
+    UpdateDigestPreferences, UpdateUserProfile, UpdateWebhook, UpsertReview, UseCase, User,
+    Webhook, WebhookDelivery, ZeroResultQuery,
 };
+use crate::notifications;
+use crate::related;
+use crate::search::{
+    self, build_fts_prefix_query, QueryFilters, SearchIndex, SearchQuery, Synonyms,
+};
+use crate::search_log::{self, SearchLog};
 use crate::state::AppState;
+use crate::storage::TempFile;
+use crate::trending;
+use crate::webhooks;
 
 // User identity is hardcoded until JWT auth lands in Step 4.
 // All user-scoped reads/writes target this row.
@@ -33,27 +65,75 @@ fn parse_tags_csv(csv: Option<&str>) -> Vec<String> {
     }
 }
 
-/// Convert a free-form user search string into an FTS5 MATCH expression.
-/// Splits on whitespace, wraps each token in double quotes, joins with AND.
-/// "AI agriculture" -> r#""AI" AND "agriculture""#
-/// This is safe against FTS5 syntax injection (e.g., user typing `"OR"` or `-`).
-fn build_fts_query(input: &str) -> String {
-    input
-        .split_whitespace()
-        .filter(|t| !t.is_empty())
-        .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
-        .collect::<Vec<_>>()
-        .join(" AND ")
+/// Reject the request unless the current user has the Admin role.
+async fn is_admin(db: &SqlitePool) -> Result<bool, AppError> {
+    let role: Option<(String,)> =
+        sqlx::query_as("SELECT role FROM users WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(CURRENT_USER_ID)
+            .fetch_optional(db)
+            .await?;
+    Ok(matches!(role, Some((role,)) if role == "Admin"))
+}
+
+async fn require_admin(db: &SqlitePool) -> Result<(), AppError> {
+    if is_admin(db).await? {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Markers wrapped around matched terms in `matches` unless the caller overrides them.
+const DEFAULT_HIGHLIGHT_START: &str = "<mark>";
+const DEFAULT_HIGHLIGHT_END: &str = "</mark>";
+const MAX_HIGHLIGHT_MARKER_LEN: usize = 32;
+
+/// Resolve the (start, end) highlight markers requested via `highlight_start`/`highlight_end`.
+fn highlight_markers(params: &ListQuery) -> Result<(String, String), AppError> {
+    let start = params
+        .highlight_start
+        .clone()
+        .unwrap_or_else(|| DEFAULT_HIGHLIGHT_START.to_string());
+    let end = params
+        .highlight_end
+        .clone()
+        .unwrap_or_else(|| DEFAULT_HIGHLIGHT_END.to_string());
+    // Matches are detected by comparing the highlighted column with the raw
+    // one, which only differs when the markers are non-empty.
+    if start.is_empty() || end.is_empty() {
+        return Err(AppError::ValidationError(
+            "Highlight markers must not be empty".to_string(),
+        ));
+    }
+    if start.len() > MAX_HIGHLIGHT_MARKER_LEN || end.len() > MAX_HIGHLIGHT_MARKER_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Highlight markers must be at most {MAX_HIGHLIGHT_MARKER_LEN} bytes"
+        )));
+    }
+    Ok((start, end))
+}
+
+/// `column:` matches data dictionary entries, which only datasets have.
+fn reject_column_filter(filters: &QueryFilters) -> Result<(), AppError> {
+    if filters.columns.is_some() {
+        return Err(AppError::ValidationError(
+            "The column: qualifier only applies to datasets".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 /// Replace the internal `tags_csv` field in a serialized JSON object
-/// with a real `tags: [...]` array.
+/// with a real `tags: [...]` array, and attach `matches` for FTS hits.
 fn dataset_to_json(d: Dataset) -> serde_json::Value {
     let tags = parse_tags_csv(d.tags_csv.as_deref());
     let mut v = serde_json::to_value(&d).unwrap_or(serde_json::json!({}));
     if let Some(obj) = v.as_object_mut() {
         obj.remove("tags_csv");
         obj.insert("tags".to_string(), serde_json::json!(tags));
+        if !d.matches.is_empty() {
+            obj.insert("matches".to_string(), serde_json::json!(d.matches));
+        }
     }
     v
 }
@@ -64,6 +144,9 @@ fn model_to_json(m: Model) -> serde_json::Value {
     if let Some(obj) = v.as_object_mut() {
         obj.remove("tags_csv");
         obj.insert("tags".to_string(), serde_json::json!(tags));
+        if !m.matches.is_empty() {
+            obj.insert("matches".to_string(), serde_json::json!(m.matches));
+        }
     }
     v
 }
@@ -74,10 +157,103 @@ fn usecase_to_json(u: UseCase) -> serde_json::Value {
     if let Some(obj) = v.as_object_mut() {
         obj.remove("tags_csv");
         obj.insert("tags".to_string(), serde_json::json!(tags));
+        if !u.matches.is_empty() {
+            obj.insert("matches".to_string(), serde_json::json!(u.matches));
+        }
     }
     v
 }
 
+/// Comma-separated `types=` list; empty or absent means every kind.
+fn parse_kinds(types: Option<&str>) -> Result<Vec<ArtifactKind>, AppError> {
+    match types.filter(|s| !s.trim().is_empty()) {
+        Some(types) => types
+            .split(',')
+            .map(|t| t.trim().parse())
+            .collect::<Result<Vec<ArtifactKind>, _>>()
+            .map_err(AppError::ValidationError),
+        None => Ok(ArtifactKind::ALL.to_vec()),
+    }
+}
+
+/// Log a list request that carried search text; returns the `X-Search-Id`
+/// header (empty when there was no search or logging failed).
+async fn log_list_search(
+    db: &SqlitePool,
+    log: SearchLog,
+    params: &ListQuery,
+    result_count: usize,
+    did_you_mean: Option<&str>,
+) -> HeaderMap {
+    let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return HeaderMap::new();
+    };
+    let filters = serde_json::json!({
+        "sector": params.sector,
+        "organization_id": params.organization_id,
+    });
+    let search_id = log
+        .record(db, search, filters, result_count, did_you_mean)
+        .await;
+    search_log::header(search_id)
+}
+
+/// Client-generated id for anonymous viewers, used only for view dedupe.
+const SESSION_ID_HEADER: &str = "x-session-id";
+const MAX_SESSION_ID_LEN: usize = 128;
+
+/// Count a detail-page view unless the same viewer saw this artifact within
+/// `view_dedupe_window`. The viewer is the client's session whenever it sends
+/// one: identity is hardcoded for now, so every visitor is `CURRENT_USER_ID`
+/// and deduping on the user would count one view per window platform-wide.
+/// The user only identifies session-less clients, and is recorded on the row
+/// for the dashboard. Failures are logged, never surfaced to the reader.
+async fn record_view(state: &AppState, kind: ArtifactKind, id: i64, headers: &HeaderMap) {
+    let session = headers
+        .get(SESSION_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|s| !s.is_empty() && s.len() <= MAX_SESSION_ID_LEN);
+    let window = format!("-{} seconds", state.config.view_dedupe_window.as_secs());
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = state.db.begin().await?;
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO view_events (user_id, session_id, artifact_kind, artifact_id)
+            SELECT ?1, ?2, ?3, ?4
+            WHERE NOT EXISTS (
+                SELECT 1 FROM view_events
+                WHERE artifact_kind = ?3 AND artifact_id = ?4
+                  AND created_at >= datetime('now', ?5)
+                  AND CASE WHEN ?2 IS NOT NULL THEN session_id = ?2
+                           ELSE session_id IS NULL AND user_id = ?1 END
+            )
+            "#,
+        )
+        .bind(CURRENT_USER_ID)
+        .bind(session)
+        .bind(kind.as_str())
+        .bind(id)
+        .bind(&window)
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() > 0 {
+            sqlx::query(&format!(
+                "UPDATE {} SET views_count = views_count + 1 WHERE id = ?1",
+                kind.table()
+            ))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!("failed to record view of {} {id}: {e}", kind.as_str());
+    }
+}
+
 // =============================================================================
 // HEALTH
 // =============================================================================
@@ -106,91 +282,181 @@ pub async fn health_check(
 // DATASETS
 // =============================================================================
 
+/// FTS branch of `get_datasets`; run by `search_with_fallback` once per query variant.
+async fn search_datasets(
+    db: &SqlitePool,
+    fts: String,
+    params: &ListQuery,
+    filters: &QueryFilters,
+    hl_start: &str,
+    hl_end: &str,
+) -> Result<Vec<Dataset>, AppError> {
+    let rows = sqlx::query_as::<_, Dataset>(
+        r#"
+        SELECT
+            d.id, d.title, d.description, d.about_dataset, d.image_url,
+            d.likes_count, d.downloads_count, d.views_count, d.rating_average, d.rating_count,
+            o.name AS source_org,
+            o.name AS source_organisation,
+            s.name AS sector,
+            s.slug AS sector_slug,
+            u.full_name AS uploaded_by,
+            d.license, d.geographical_coverage, d.author, d.data_quality_score,
+            d.dataset_type, d.frequency, d.time_granularity, d.year_range,
+            d.data_collected_at, d.visibility, d.hosted, d.data_type, d.data_collection_method,
+            (SELECT GROUP_CONCAT(t.name)
+               FROM dataset_tags dt JOIN tags t ON t.id = dt.tag_id
+               WHERE dt.dataset_id = d.id) AS tags_csv,
+            CASE WHEN highlight(datasets_fts, 0, ?4, ?5) <> d.title
+                 THEN highlight(datasets_fts, 0, ?4, ?5) END AS match_title,
+            CASE WHEN highlight(datasets_fts, 1, ?4, ?5) <> d.description
+                 THEN snippet(datasets_fts, 1, ?4, ?5, '…', 24) END AS match_description,
+            CASE WHEN highlight(datasets_fts, 2, ?4, ?5) <> COALESCE(d.about_dataset, '')
+                 THEN snippet(datasets_fts, 2, ?4, ?5, '…', 24) END AS match_about,
+            CASE WHEN highlight(datasets_fts, 3, ?4, ?5) <> COALESCE(d.tags_text, '')
+                 THEN highlight(datasets_fts, 3, ?4, ?5) END AS match_tags
+        FROM datasets d
+        JOIN datasets_fts f ON f.rowid = d.id
+        LEFT JOIN organizations o ON o.id = d.organization_id
+        LEFT JOIN sectors s       ON s.id = d.sector_id
+        LEFT JOIN users u         ON u.id = d.uploaded_by_user_id
+        WHERE datasets_fts MATCH ?1
+          AND d.deleted_at IS NULL
+          AND (?2 IS NULL OR s.slug = ?2)
+          AND (?3 IS NULL OR d.organization_id = ?3)
+          AND (?6 IS NULL OR EXISTS (SELECT 1 FROM json_each(?6) j
+                               WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+          AND (?7 IS NULL OR EXISTS (SELECT 1 FROM json_each(?7) j
+                               WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+          AND NOT EXISTS (SELECT 1 FROM json_each(?8) j
+                          WHERE NOT EXISTS (SELECT 1 FROM dataset_tags x JOIN tags t ON t.id = x.tag_id
+                                            WHERE x.dataset_id = d.id AND t.slug = j.value))
+          AND NOT EXISTS (SELECT 1 FROM json_each(?9) j
+                          WHERE NOT EXISTS (SELECT 1 FROM dataset_columns c
+                                            WHERE c.dataset_id = d.id
+                                              AND c.name = j.value COLLATE NOCASE))
+        ORDER BY rank
+        "#,
+    )
+    .bind(fts)
+    .bind(params.sector.as_deref())
+    .bind(params.organization_id)
+    .bind(hl_start)
+    .bind(hl_end)
+    .bind(filters.sectors.as_deref())
+    .bind(filters.orgs.as_deref())
+    .bind(filters.tags.as_deref())
+    .bind(filters.columns.as_deref())
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
 pub async fn get_datasets(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ListQuery>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
-        let fts = build_fts_query(search);
-        sqlx::query_as::<_, Dataset>(
-            r#"
-            SELECT
-                d.id, d.title, d.description, d.about_dataset, d.image_url,
-                d.likes_count, d.downloads_count, d.views_count,
-                o.name AS source_org,
-                o.name AS source_organisation,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                u.full_name AS uploaded_by,
-                d.license, d.geographical_coverage, d.author, d.data_quality_score,
-                d.dataset_type, d.frequency, d.time_granularity, d.year_range,
-                d.data_collected_at, d.visibility, d.hosted, d.data_type, d.data_collection_method,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM dataset_tags dt JOIN tags t ON t.id = dt.tag_id
-                   WHERE dt.dataset_id = d.id) AS tags_csv
-            FROM datasets d
-            JOIN datasets_fts f ON f.rowid = d.id
-            LEFT JOIN organizations o ON o.id = d.organization_id
-            LEFT JOIN sectors s       ON s.id = d.sector_id
-            LEFT JOIN users u         ON u.id = d.uploaded_by_user_id
-            WHERE datasets_fts MATCH ?1
-              AND d.deleted_at IS NULL
-              AND (?2 IS NULL OR s.slug = ?2)
-              AND (?3 IS NULL OR d.organization_id = ?3)
-            ORDER BY rank
-            "#,
-        )
-        .bind(fts)
-        .bind(params.sector.as_deref())
-        .bind(params.organization_id)
-        .fetch_all(&state.db)
-        .await?
-    } else {
-        sqlx::query_as::<_, Dataset>(
-            r#"
-            SELECT
-                d.id, d.title, d.description, d.about_dataset, d.image_url,
-                d.likes_count, d.downloads_count, d.views_count,
-                o.name AS source_org,
-                o.name AS source_organisation,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                u.full_name AS uploaded_by,
-                d.license, d.geographical_coverage, d.author, d.data_quality_score,
-                d.dataset_type, d.frequency, d.time_granularity, d.year_range,
-                d.data_collected_at, d.visibility, d.hosted, d.data_type, d.data_collection_method,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM dataset_tags dt JOIN tags t ON t.id = dt.tag_id
-                   WHERE dt.dataset_id = d.id) AS tags_csv
-            FROM datasets d
-            LEFT JOIN organizations o ON o.id = d.organization_id
-            LEFT JOIN sectors s       ON s.id = d.sector_id
-            LEFT JOIN users u         ON u.id = d.uploaded_by_user_id
-            WHERE d.deleted_at IS NULL
-              AND (?1 IS NULL OR s.slug = ?1)
-              AND (?2 IS NULL OR d.organization_id = ?2)
-            ORDER BY d.id
-            "#,
-        )
-        .bind(params.sector.as_deref())
-        .bind(params.organization_id)
-        .fetch_all(&state.db)
-        .await?
+) -> Result<(HeaderMap, Json<SearchResults>), AppError> {
+    let log = SearchLog::start("datasets", Some(CURRENT_USER_ID));
+    let query = params
+        .search
+        .as_deref()
+        .map(SearchQuery::parse)
+        .transpose()?;
+    let filters = query.as_ref().map(SearchQuery::filters).unwrap_or_default();
+
+    let (mut rows, did_you_mean) = match query.filter(SearchQuery::has_terms) {
+        Some(query) => {
+            let (hl_start, hl_end) = highlight_markers(&params)?;
+            search::search_with_fallback(&state.db, SearchIndex::Datasets, &query, |fts| {
+                search_datasets(&state.db, fts, &params, &filters, &hl_start, &hl_end)
+            })
+            .await?
+        }
+        None => {
+            let rows = sqlx::query_as::<_, Dataset>(
+                r#"
+                SELECT
+                    d.id, d.title, d.description, d.about_dataset, d.image_url,
+                    d.likes_count, d.downloads_count, d.views_count, d.rating_average, d.rating_count,
+                    o.name AS source_org,
+                    o.name AS source_organisation,
+                    s.name AS sector,
+                    s.slug AS sector_slug,
+                    u.full_name AS uploaded_by,
+                    d.license, d.geographical_coverage, d.author, d.data_quality_score,
+                    d.dataset_type, d.frequency, d.time_granularity, d.year_range,
+                    d.data_collected_at, d.visibility, d.hosted, d.data_type, d.data_collection_method,
+                    (SELECT GROUP_CONCAT(t.name)
+                       FROM dataset_tags dt JOIN tags t ON t.id = dt.tag_id
+                       WHERE dt.dataset_id = d.id) AS tags_csv
+                FROM datasets d
+                LEFT JOIN organizations o ON o.id = d.organization_id
+                LEFT JOIN sectors s       ON s.id = d.sector_id
+                LEFT JOIN users u         ON u.id = d.uploaded_by_user_id
+                WHERE d.deleted_at IS NULL
+                  AND (?1 IS NULL OR s.slug = ?1)
+                  AND (?2 IS NULL OR d.organization_id = ?2)
+                  AND (?3 IS NULL OR EXISTS (SELECT 1 FROM json_each(?3) j
+                                       WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+                  AND (?4 IS NULL OR EXISTS (SELECT 1 FROM json_each(?4) j
+                                       WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+                  AND NOT EXISTS (SELECT 1 FROM json_each(?5) j
+                                  WHERE NOT EXISTS (SELECT 1 FROM dataset_tags x JOIN tags t ON t.id = x.tag_id
+                                                    WHERE x.dataset_id = d.id AND t.slug = j.value))
+                  AND NOT EXISTS (SELECT 1 FROM json_each(?6) j
+                                  WHERE NOT EXISTS (SELECT 1 FROM dataset_columns c
+                                                    WHERE c.dataset_id = d.id
+                                                      AND c.name = j.value COLLATE NOCASE))
+                ORDER BY d.id
+                "#,
+            )
+            .bind(params.sector.as_deref())
+            .bind(params.organization_id)
+            .bind(filters.sectors.as_deref())
+            .bind(filters.orgs.as_deref())
+            .bind(filters.tags.as_deref())
+            .bind(filters.columns.as_deref())
+            .fetch_all(&state.db)
+            .await?;
+            (rows, None)
+        }
     };
 
-    let json: Vec<serde_json::Value> = rows.into_iter().map(dataset_to_json).collect();
-    Ok(Json(serde_json::json!(json)))
+    match params.sort {
+        Some(ListSort::Rating) => sort_by_rating(&mut rows, |r| (r.rating_average, r.rating_count)),
+        Some(ListSort::Trending) => {
+            sort_by_trending(&state.db, ArtifactKind::Dataset, &mut rows, |r| r.id).await?
+        }
+        None => {}
+    }
+    let items: Vec<_> = rows.into_iter().map(dataset_to_json).collect();
+    let headers = log_list_search(
+        &state.db,
+        log,
+        &params,
+        items.len(),
+        did_you_mean.as_deref(),
+    )
+    .await;
+    Ok((
+        headers,
+        Json(SearchResults {
+            items,
+            did_you_mean,
+        }),
+    ))
 }
 
 pub async fn get_dataset_by_id(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let row = sqlx::query_as::<_, Dataset>(
         r#"
         SELECT
             d.id, d.title, d.description, d.about_dataset, d.image_url,
-            d.likes_count, d.downloads_count, d.views_count,
+            d.likes_count, d.downloads_count, d.views_count, d.rating_average, d.rating_count,
             o.name AS source_org,
             o.name AS source_organisation,
             s.name AS sector,
@@ -213,437 +479,3978 @@ pub async fn get_dataset_by_id(
     .fetch_optional(&state.db)
     .await?
     .ok_or(AppError::NotFound)?;
+    record_view(&state, ArtifactKind::Dataset, id, &headers).await;
 
-    Ok(Json(dataset_to_json(row)))
+    let files = list_dataset_files(&state.db, id).await?;
+    let columns = list_dataset_columns(&state.db, id).await?;
+    let liked = liked_by_me(&state.db, ArtifactKind::Dataset, id).await?;
+    let rating = rating_summary(&state.db, ArtifactKind::Dataset, id).await?;
+    let review = my_review(&state.db, ArtifactKind::Dataset, id).await?;
+    let mut v = dataset_to_json(row);
+    if let Some(obj) = v.as_object_mut() {
+        obj.insert("files".to_string(), serde_json::json!(files));
+        obj.insert("columns".to_string(), serde_json::json!(columns));
+        obj.insert("liked_by_me".to_string(), serde_json::json!(liked));
+        obj.insert("rating".to_string(), serde_json::json!(rating));
+        obj.insert("my_review".to_string(), serde_json::json!(review));
+    }
+    Ok(Json(v))
 }
 
 // =============================================================================
-// MODELS
+// DATASET FILES  (multipart upload into the configured storage backend)
 // =============================================================================
 
-pub async fn get_models(
-    State(state): State<Arc<AppState>>,
-    Query(params): Query<ListQuery>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
-        let fts = build_fts_query(search);
-        sqlx::query_as::<_, Model>(
-            r#"
-            SELECT
-                m.id, m.title, m.description, m.about_model, m.image_url,
-                m.likes_count, m.downloads_count, m.views_count,
-                o.name AS source_org,
-                o.name AS source_organization,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                u.full_name AS created_by,
-                m.license, m.hosted_by, m.model_type, m.model_format,
-                m.visibility, m.size, m.model_updated_at,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
-                   WHERE mt.model_id = m.id) AS tags_csv
-            FROM models m
-            JOIN models_fts f ON f.rowid = m.id
-            LEFT JOIN organizations o ON o.id = m.organization_id
-            LEFT JOIN sectors s       ON s.id = m.sector_id
-            LEFT JOIN users u         ON u.id = m.created_by_user_id
-            WHERE models_fts MATCH ?1
-              AND m.deleted_at IS NULL
-              AND (?2 IS NULL OR s.slug = ?2)
-            ORDER BY rank
-            "#,
-        )
-        .bind(fts)
-        .bind(params.sector.as_deref())
-        .fetch_all(&state.db)
-        .await?
-    } else {
-        sqlx::query_as::<_, Model>(
-            r#"
-            SELECT
-                m.id, m.title, m.description, m.about_model, m.image_url,
-                m.likes_count, m.downloads_count, m.views_count,
-                o.name AS source_org,
-                o.name AS source_organization,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                u.full_name AS created_by,
-                m.license, m.hosted_by, m.model_type, m.model_format,
-                m.visibility, m.size, m.model_updated_at,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
-                   WHERE mt.model_id = m.id) AS tags_csv
-            FROM models m
-            LEFT JOIN organizations o ON o.id = m.organization_id
-            LEFT JOIN sectors s       ON s.id = m.sector_id
-            LEFT JOIN users u         ON u.id = m.created_by_user_id
-            WHERE m.deleted_at IS NULL
-              AND (?1 IS NULL OR s.slug = ?1)
-            ORDER BY m.id
-            "#,
-        )
-        .bind(params.sector.as_deref())
-        .fetch_all(&state.db)
-        .await?
-    };
+const MAX_FILE_NAME_LEN: usize = 255;
+const DEFAULT_MEDIA_TYPE: &str = "application/octet-stream";
 
-    let json: Vec<serde_json::Value> = rows.into_iter().map(model_to_json).collect();
-    Ok(Json(serde_json::json!(json)))
+/// The dataset's uploader or an admin may manage its files.
+async fn require_dataset_editor(db: &SqlitePool, dataset_id: i64) -> Result<(), AppError> {
+    let uploader: Option<(Option<i64>,)> = sqlx::query_as(
+        "SELECT uploaded_by_user_id FROM datasets WHERE id = ?1 AND deleted_at IS NULL",
+    )
+    .bind(dataset_id)
+    .fetch_optional(db)
+    .await?;
+    match uploader {
+        None => Err(AppError::NotFound),
+        Some((Some(user_id),)) if user_id == CURRENT_USER_ID => Ok(()),
+        Some(_) => require_admin(db).await,
+    }
 }
 
-pub async fn get_model_by_id(
-    State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let row = sqlx::query_as::<_, Model>(
+/// Drop any client-side directory and control characters from an upload's file name.
+fn clean_file_name(raw: &str) -> Option<String> {
+    let base = raw.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = base.chars().filter(|c| !c.is_control()).collect();
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name.chars().count() <= MAX_FILE_NAME_LEN;
+    valid.then(|| name.to_string())
+}
+
+/// `datasets/{id}/{random}-{name}`, with the name reduced to URL-safe characters.
+fn dataset_file_key(dataset_id: i64, name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!(
+        "datasets/{dataset_id}/{:016x}-{safe}",
+        rand::random::<u64>()
+    )
+}
+
+fn multipart_error(e: MultipartError) -> AppError {
+    AppError::ValidationError(e.body_text())
+}
+
+async fn list_dataset_files(
+    db: &SqlitePool,
+    dataset_id: i64,
+) -> Result<Vec<DatasetFile>, AppError> {
+    let rows = sqlx::query_as::<_, DatasetFile>(
         r#"
-        SELECT
-            m.id, m.title, m.description, m.about_model, m.image_url,
-            m.likes_count, m.downloads_count, m.views_count,
-            o.name AS source_org,
-            o.name AS source_organization,
-            s.name AS sector,
-            s.slug AS sector_slug,
-            u.full_name AS created_by,
-            m.license, m.hosted_by, m.model_type, m.model_format,
-            m.visibility, m.size, m.model_updated_at,
-            (SELECT GROUP_CONCAT(t.name)
-               FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
-               WHERE mt.model_id = m.id) AS tags_csv
-        FROM models m
-        LEFT JOIN organizations o ON o.id = m.organization_id
-        LEFT JOIN sectors s       ON s.id = m.sector_id
-        LEFT JOIN users u         ON u.id = m.created_by_user_id
-        WHERE m.id = ?1 AND m.deleted_at IS NULL
+        SELECT f.id, f.dataset_id, f.name, f.size_bytes, f.media_type, f.sha256,
+               COALESCE(p.status, 'pending') AS profile_status, f.created_at
+        FROM dataset_files f
+        LEFT JOIN dataset_file_profiles p ON p.file_id = f.id
+        WHERE f.dataset_id = ?1
+        ORDER BY f.name
         "#,
     )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or(AppError::NotFound)?;
-
-    Ok(Json(model_to_json(row)))
+    .bind(dataset_id)
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
 }
 
-// =============================================================================
-// USECASES
-// =============================================================================
+pub async fn get_dataset_files(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<DatasetFile>>, AppError> {
+    let exists: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM datasets WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(list_dataset_files(&state.db, id).await?))
+}
 
-pub async fn get_usecases(
+/// Accepts one or more multipart `file` fields. Each file is streamed to a
+/// temp file while hashed, then stored; files stored before a failing one stay.
+pub async fn upload_dataset_files(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<ListQuery>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
-        let fts = build_fts_query(search);
-        sqlx::query_as::<_, UseCase>(
-            r#"
-            SELECT
-                u.id, u.title, u.description, u.about_use_case, u.image_url,
-                o.name AS source_org,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
-                   WHERE ut.usecase_id = u.id) AS tags_csv
-            FROM usecases u
-            JOIN usecases_fts f ON f.rowid = u.id
-            LEFT JOIN organizations o ON o.id = u.organization_id
-            LEFT JOIN sectors s       ON s.id = u.sector_id
-            WHERE usecases_fts MATCH ?1
-              AND u.deleted_at IS NULL
-              AND (?2 IS NULL OR s.slug = ?2)
-            ORDER BY rank
-            "#,
+    Path(id): Path<i64>,
+    mut multipart: Multipart,
+) -> Result<Json<Vec<DatasetFile>>, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+
+    let mut uploaded = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let name = field.file_name().and_then(clean_file_name).ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Each file needs a name of at most {MAX_FILE_NAME_LEN} characters"
+            ))
+        })?;
+        let media_type = field
+            .content_type()
+            .filter(|t| !t.is_empty())
+            .unwrap_or(DEFAULT_MEDIA_TYPE)
+            .to_string();
+
+        let existing: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM dataset_files WHERE dataset_id = ?1 AND name = ?2",
         )
-        .bind(fts)
-        .bind(params.sector.as_deref())
-        .fetch_all(&state.db)
-        .await?
-    } else {
-        sqlx::query_as::<_, UseCase>(
+        .bind(id)
+        .bind(&name)
+        .fetch_one(&state.db)
+        .await?;
+        if existing.0 > 0 {
+            return Err(AppError::ValidationError(format!(
+                "A file named '{name}' already exists for this dataset"
+            )));
+        }
+
+        let tmp = TempFile::new();
+        let mut out = tokio::fs::File::create(&tmp.path).await?;
+        let mut hasher = Sha256::new();
+        let mut size: u64 = 0;
+        while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+            size += chunk.len() as u64;
+            if size > state.config.upload_max_bytes {
+                return Err(AppError::ValidationError(format!(
+                    "File '{name}' exceeds the upload limit of {} bytes",
+                    state.config.upload_max_bytes
+                )));
+            }
+            hasher.update(&chunk);
+            out.write_all(&chunk).await?;
+        }
+        out.flush().await?;
+        drop(out);
+
+        let sha256 = hex::encode(hasher.finalize());
+        let key = dataset_file_key(id, &name);
+        state
+            .storage
+            .put(&key, &tmp.path, size, &sha256, &media_type)
+            .await?;
+
+        let row = sqlx::query_as::<_, DatasetFile>(
             r#"
-            SELECT
-                u.id, u.title, u.description, u.about_use_case, u.image_url,
-                o.name AS source_org,
-                s.name AS sector,
-                s.slug AS sector_slug,
-                (SELECT GROUP_CONCAT(t.name)
-                   FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
-                   WHERE ut.usecase_id = u.id) AS tags_csv
-            FROM usecases u
-            LEFT JOIN organizations o ON o.id = u.organization_id
-            LEFT JOIN sectors s       ON s.id = u.sector_id
-            WHERE u.deleted_at IS NULL
-              AND (?1 IS NULL OR s.slug = ?1)
-            ORDER BY u.id
+            INSERT INTO dataset_files
+                (dataset_id, name, size_bytes, media_type, sha256, storage_key, uploaded_by_user_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            RETURNING id, dataset_id, name, size_bytes, media_type, sha256,
+                      'pending' AS profile_status, created_at
             "#,
         )
-        .bind(params.sector.as_deref())
-        .fetch_all(&state.db)
-        .await?
-    };
+        .bind(id)
+        .bind(&name)
+        .bind(size as i64)
+        .bind(&media_type)
+        .bind(&sha256)
+        .bind(&key)
+        .bind(CURRENT_USER_ID)
+        .fetch_one(&state.db)
+        .await;
+        match row {
+            Ok(row) => uploaded.push(row),
+            Err(e) => {
+                // Don't leave an orphaned object behind a failed insert.
+                if let Err(del) = state.storage.delete(&key).await {
+                    tracing::warn!("failed to remove orphaned upload {key}: {del}");
+                }
+                return Err(e.into());
+            }
+        }
+    }
 
-    let json: Vec<serde_json::Value> = rows.into_iter().map(usecase_to_json).collect();
-    Ok(Json(serde_json::json!(json)))
+    if uploaded.is_empty() {
+        return Err(AppError::ValidationError(
+            "No files in upload (expected multipart field 'file')".to_string(),
+        ));
+    }
+    Ok(Json(uploaded))
 }
 
-pub async fn get_usecase_by_id(
+pub async fn delete_dataset_file(
     State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let row = sqlx::query_as::<_, UseCase>(
+    Path((id, file_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+
+    let (key,): (String,) =
+        sqlx::query_as("SELECT storage_key FROM dataset_files WHERE id = ?1 AND dataset_id = ?2")
+            .bind(file_id)
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+    // Storage first: if it fails the row stays and the delete can be retried.
+    state.storage.delete(&key).await?;
+    sqlx::query("DELETE FROM dataset_files WHERE id = ?1")
+        .bind(file_id)
+        .execute(&state.db)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Statistics from the profiling job; `status` is "pending" until it has run.
+pub async fn get_dataset_file_profile(
+    State(state): State<Arc<AppState>>,
+    Path((id, file_id)): Path<(i64, i64)>,
+) -> Result<Json<FileProfile>, AppError> {
+    let mut profile = sqlx::query_as::<_, FileProfile>(
         r#"
-        SELECT
-            u.id, u.title, u.description, u.about_use_case, u.image_url,
-            o.name AS source_org,
-            s.name AS sector,
-            s.slug AS sector_slug,
-            (SELECT GROUP_CONCAT(t.name)
-               FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
-               WHERE ut.usecase_id = u.id) AS tags_csv
-        FROM usecases u
-        LEFT JOIN organizations o ON o.id = u.organization_id
-        LEFT JOIN sectors s       ON s.id = u.sector_id
-        WHERE u.id = ?1 AND u.deleted_at IS NULL
+        SELECT f.id AS file_id, COALESCE(p.status, 'pending') AS status, p.format, p.row_count,
+               COALESCE(p.columns, '[]') AS columns,
+               COALESCE(p.sample_rows, '[]') AS sample_rows,
+               p.error, p.profiled_at
+        FROM dataset_files f
+        JOIN datasets d ON d.id = f.dataset_id AND d.deleted_at IS NULL
+        LEFT JOIN dataset_file_profiles p ON p.file_id = f.id
+        WHERE f.id = ?1 AND f.dataset_id = ?2
         "#,
     )
+    .bind(file_id)
     .bind(id)
     .fetch_optional(&state.db)
     .await?
     .ok_or(AppError::NotFound)?;
+    profile.columns = serde_json::from_str(&profile.columns_json)?;
+    profile.sample_rows = serde_json::from_str(&profile.sample_rows_json)?;
+    Ok(Json(profile))
+}
 
-    Ok(Json(usecase_to_json(row)))
+/// Queue the file for profiling again (e.g. after a failure).
+pub async fn reprofile_dataset_file(
+    State(state): State<Arc<AppState>>,
+    Path((id, file_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+    let exists: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM dataset_files WHERE id = ?1 AND dataset_id = ?2")
+            .bind(file_id)
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    let mut tx = state.db.begin().await?;
+    sqlx::query("DELETE FROM dataset_file_profiles WHERE file_id = ?1")
+        .bind(file_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "UPDATE dataset_files SET profile_attempts = 0, profile_retry_at = NULL WHERE id = ?1",
+    )
+    .bind(file_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(StatusCode::ACCEPTED)
 }
 
 // =============================================================================
-// TOOLKIT  (no tags, no sector — just text search)
+// DATA DICTIONARY  (per-dataset column metadata; names feed the column: qualifier)
 // =============================================================================
 
-pub async fn get_toolkit(
-    State(state): State<Arc<AppState>>,
-    Query(params): Query<ListQuery>,
-) -> Result<Json<Vec<Toolkit>>, AppError> {
-    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
-        let pattern = format!("%{}%", search.to_lowercase());
-        sqlx::query_as::<_, Toolkit>(
-            r#"
-            SELECT id, title, description, image_url, overview, key_capabilities,
-                   why_it_is_included, resources_on_getting_started,
-                   license_and_compliance, screenshots_and_ui_previews,
-                   versioning_and_community_info
-            FROM toolkit
-            WHERE deleted_at IS NULL
-              AND (LOWER(title) LIKE ?1 OR LOWER(description) LIKE ?1 OR LOWER(overview) LIKE ?1)
-            ORDER BY id
-            "#,
-        )
-        .bind(pattern)
-        .fetch_all(&state.db)
-        .await?
-    } else {
-        sqlx::query_as::<_, Toolkit>(
-            r#"
-            SELECT id, title, description, image_url, overview, key_capabilities,
-                   why_it_is_included, resources_on_getting_started,
-                   license_and_compliance, screenshots_and_ui_previews,
-                   versioning_and_community_info
-            FROM toolkit
-            WHERE deleted_at IS NULL
-            ORDER BY id
-            "#,
-        )
-        .fetch_all(&state.db)
-        .await?
-    };
+const DATASET_COLUMN_SELECT: &str = r#"
+    SELECT id, dataset_id, position, name, data_type, unit, description, nullable,
+           example_values, created_at, updated_at
+    FROM dataset_columns
+"#;
+const MAX_COLUMN_NAME_LEN: usize = 128;
+const MAX_COLUMN_UNIT_LEN: usize = 32;
+const MAX_COLUMN_DESCRIPTION_LEN: usize = 2000;
+const MAX_EXAMPLE_VALUES: usize = 10;
+const MAX_EXAMPLE_VALUE_LEN: usize = 200;
 
-    Ok(Json(rows))
+pub fn validate_column_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_COLUMN_NAME_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Column name must be between 1 and {MAX_COLUMN_NAME_LEN} characters"
+        )));
+    }
+    Ok(name.to_string())
 }
 
-pub async fn get_toolkit_by_id(
-    State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<Json<Toolkit>, AppError> {
-    let row = sqlx::query_as::<_, Toolkit>(
-        r#"
-        SELECT id, title, description, image_url, overview, key_capabilities,
-               why_it_is_included, resources_on_getting_started,
-               license_and_compliance, screenshots_and_ui_previews,
-               versioning_and_community_info
-        FROM toolkit
-        WHERE id = ?1 AND deleted_at IS NULL
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or(AppError::NotFound)?;
+/// Trimmed unit; empty means "no unit".
+fn validate_column_unit(unit: &str) -> Result<String, AppError> {
+    let unit = unit.trim();
+    if unit.chars().count() > MAX_COLUMN_UNIT_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Unit must be at most {MAX_COLUMN_UNIT_LEN} characters"
+        )));
+    }
+    Ok(unit.to_string())
+}
 
-    Ok(Json(row))
+fn validate_column_description(description: &str) -> Result<String, AppError> {
+    let description = description.trim();
+    if description.chars().count() > MAX_COLUMN_DESCRIPTION_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Description must be at most {MAX_COLUMN_DESCRIPTION_LEN} characters"
+        )));
+    }
+    Ok(description.to_string())
 }
 
-// =============================================================================
+/// Checks the sample values and returns them as a JSON array.
+fn validate_example_values(values: &[String]) -> Result<String, AppError> {
+    if values.len() > MAX_EXAMPLE_VALUES {
+        return Err(AppError::ValidationError(format!(
+            "At most {MAX_EXAMPLE_VALUES} example values per column"
+        )));
+    }
+    if values
+        .iter()
+        .any(|v| v.chars().count() > MAX_EXAMPLE_VALUE_LEN)
+    {
+        return Err(AppError::ValidationError(format!(
+            "Example values must be at most {MAX_EXAMPLE_VALUE_LEN} characters"
+        )));
+    }
+    Ok(serde_json::to_string(values)?)
+}
+
+fn with_example_values(mut column: DatasetColumn) -> Result<DatasetColumn, AppError> {
+    column.example_values = serde_json::from_str(&column.example_values_json)?;
+    Ok(column)
+}
+
+async fn list_dataset_columns(
+    db: &SqlitePool,
+    dataset_id: i64,
+) -> Result<Vec<DatasetColumn>, AppError> {
+    let rows = sqlx::query_as::<_, DatasetColumn>(&format!(
+        "{DATASET_COLUMN_SELECT} WHERE dataset_id = ?1 ORDER BY position, id"
+    ))
+    .bind(dataset_id)
+    .fetch_all(db)
+    .await?;
+    rows.into_iter().map(with_example_values).collect()
+}
+
+async fn find_dataset_column(
+    db: &SqlitePool,
+    dataset_id: i64,
+    column_id: i64,
+) -> Result<DatasetColumn, AppError> {
+    let column = sqlx::query_as::<_, DatasetColumn>(&format!(
+        "{DATASET_COLUMN_SELECT} WHERE id = ?1 AND dataset_id = ?2"
+    ))
+    .bind(column_id)
+    .bind(dataset_id)
+    .fetch_optional(db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    with_example_values(column)
+}
+
+/// Names are unique per dataset ignoring case; `except` skips the column being renamed.
+async fn ensure_column_name_free(
+    db: &SqlitePool,
+    dataset_id: i64,
+    name: &str,
+    except: Option<i64>,
+) -> Result<(), AppError> {
+    let (taken,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FROM dataset_columns
+        WHERE dataset_id = ?1 AND name = ?2 COLLATE NOCASE AND (?3 IS NULL OR id <> ?3)
+        "#,
+    )
+    .bind(dataset_id)
+    .bind(name)
+    .bind(except)
+    .fetch_one(db)
+    .await?;
+    if taken > 0 {
+        return Err(AppError::ValidationError(format!(
+            "A column named '{name}' already exists for this dataset"
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_dataset_columns(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<DatasetColumn>>, AppError> {
+    let exists: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM datasets WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(list_dataset_columns(&state.db, id).await?))
+}
+
+pub async fn create_dataset_column(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateDatasetColumn>,
+) -> Result<Json<DatasetColumn>, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+    let name = validate_column_name(&payload.name)?;
+    let unit = payload
+        .unit
+        .as_deref()
+        .map(validate_column_unit)
+        .transpose()?
+        .filter(|u| !u.is_empty());
+    let description = validate_column_description(payload.description.as_deref().unwrap_or(""))?;
+    let examples = validate_example_values(payload.example_values.as_deref().unwrap_or(&[]))?;
+    ensure_column_name_free(&state.db, id, &name, None).await?;
+
+    let (column_id,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO dataset_columns
+            (dataset_id, position, name, data_type, unit, description, nullable, example_values)
+        VALUES (?1,
+                COALESCE(?2, (SELECT COALESCE(MAX(position), 0) + 1
+                              FROM dataset_columns WHERE dataset_id = ?1)),
+                ?3, ?4, ?5, ?6, ?7, ?8)
+        RETURNING id
+        "#,
+    )
+    .bind(id)
+    .bind(payload.position)
+    .bind(&name)
+    .bind(payload.data_type.as_str())
+    .bind(unit)
+    .bind(description)
+    .bind(payload.nullable.unwrap_or(true))
+    .bind(examples)
+    .fetch_one(&state.db)
+    .await?;
+    Ok(Json(find_dataset_column(&state.db, id, column_id).await?))
+}
+
+pub async fn update_dataset_column(
+    State(state): State<Arc<AppState>>,
+    Path((id, column_id)): Path<(i64, i64)>,
+    Json(payload): Json<UpdateDatasetColumn>,
+) -> Result<Json<DatasetColumn>, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+    let name = payload
+        .name
+        .as_deref()
+        .map(validate_column_name)
+        .transpose()?;
+    let unit = payload
+        .unit
+        .as_deref()
+        .map(validate_column_unit)
+        .transpose()?;
+    let description = payload
+        .description
+        .as_deref()
+        .map(validate_column_description)
+        .transpose()?;
+    let examples = payload
+        .example_values
+        .as_deref()
+        .map(validate_example_values)
+        .transpose()?;
+    if let Some(name) = &name {
+        ensure_column_name_free(&state.db, id, name, Some(column_id)).await?;
+    }
+
+    let res = sqlx::query(
+        r#"
+        UPDATE dataset_columns SET
+            name           = COALESCE(?1, name),
+            data_type      = COALESCE(?2, data_type),
+            unit           = CASE WHEN ?3 IS NULL THEN unit ELSE NULLIF(?3, '') END,
+            description    = COALESCE(?4, description),
+            nullable       = COALESCE(?5, nullable),
+            example_values = COALESCE(?6, example_values),
+            position       = COALESCE(?7, position),
+            updated_at     = datetime('now')
+        WHERE id = ?8 AND dataset_id = ?9
+        "#,
+    )
+    .bind(name)
+    .bind(payload.data_type.map(ColumnType::as_str))
+    .bind(unit)
+    .bind(description)
+    .bind(payload.nullable)
+    .bind(examples)
+    .bind(payload.position)
+    .bind(column_id)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(find_dataset_column(&state.db, id, column_id).await?))
+}
+
+pub async fn delete_dataset_column(
+    State(state): State<Arc<AppState>>,
+    Path((id, column_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, AppError> {
+    require_dataset_editor(&state.db, id).await?;
+    let res = sqlx::query("DELETE FROM dataset_columns WHERE id = ?1 AND dataset_id = ?2")
+        .bind(column_id)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// =============================================================================
+// DOWNLOADS  (Range-aware file streaming; per-user download events)
+// =============================================================================
+
+/// Parse a single-range `Range` header against a body of `size` bytes.
+/// `Ok(None)` means serve the whole body (no header, or a form we don't
+/// handle such as multiple ranges); `Err(())` means unsatisfiable (416).
+fn parse_range(header: Option<&str>, size: u64) -> Result<Option<(u64, u64)>, ()> {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let (first, last) = spec.split_once('-').ok_or(())?;
+    let (first, last) = match (first.trim(), last.trim()) {
+        ("", suffix) => {
+            let n: u64 = suffix.parse().map_err(|_| ())?;
+            if n == 0 {
+                return Err(());
+            }
+            (size.saturating_sub(n), size.checked_sub(1).ok_or(())?)
+        }
+        (first, "") => (
+            first.parse().map_err(|_| ())?,
+            size.checked_sub(1).ok_or(())?,
+        ),
+        (first, last) => {
+            let first: u64 = first.parse().map_err(|_| ())?;
+            let last: u64 = last.parse().map_err(|_| ())?;
+            (first, last.min(size.saturating_sub(1)))
+        }
+    };
+    if first > last || first >= size {
+        return Err(());
+    }
+    Ok(Some((first, last)))
+}
+
+/// Ranged requests from offset 0 shorter than this (e.g. `bytes=0-0`) are
+/// clients probing for range support or file type, not downloads.
+const DOWNLOAD_PROBE_BYTES: u64 = 1024;
+
+/// Whether serving `range` of a `size`-byte file counts as a download: the
+/// whole file, or a range from the start (a resumable client's first chunk)
+/// that is more than a probe. Later chunks and HEAD requests never count.
+fn counts_as_download(range: Option<(u64, u64)>, size: u64) -> bool {
+    match range {
+        None => true,
+        Some((first, last)) => first == 0 && (last + 1 >= DOWNLOAD_PROBE_BYTES || last + 1 == size),
+    }
+}
+
+/// Log a download for the current user and bump the artifact's counter atomically.
+async fn record_download(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    id: i64,
+    dataset_file_id: Option<i64>,
+) -> Result<i64, AppError> {
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        "INSERT INTO download_events (user_id, artifact_kind, artifact_id, dataset_file_id)
+         VALUES (?1, ?2, ?3, ?4)",
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(kind.as_str())
+    .bind(id)
+    .bind(dataset_file_id)
+    .execute(&mut *tx)
+    .await?;
+    let (count,): (i64,) = sqlx::query_as(&format!(
+        "UPDATE {} SET downloads_count = downloads_count + 1 WHERE id = ?1 RETURNING downloads_count",
+        kind.table()
+    ))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(count)
+}
+
+/// Streams a stored dataset file. A request that starts at byte 0 counts as
+/// a download; ranged requests resuming mid-file don't count again.
+pub async fn download_dataset_file(
+    State(state): State<Arc<AppState>>,
+    Path((id, file_id)): Path<(i64, i64)>,
+    method: Method,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let (name, size, media_type, sha256, key): (String, i64, String, String, String) =
+        sqlx::query_as(
+            r#"
+        SELECT f.name, f.size_bytes, f.media_type, f.sha256, f.storage_key
+        FROM dataset_files f
+        JOIN datasets d ON d.id = f.dataset_id
+        WHERE f.id = ?1 AND f.dataset_id = ?2 AND d.deleted_at IS NULL
+        "#,
+        )
+        .bind(file_id)
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let size = size as u64;
+
+    let range = headers.get(header::RANGE).and_then(|v| v.to_str().ok());
+    let Ok(range) = parse_range(range, size) else {
+        return Ok((
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(header::CONTENT_RANGE, format!("bytes */{size}"))],
+        )
+            .into_response());
+    };
+
+    // HEAD gets the same headers without touching storage or the counters.
+    let body = if method == Method::HEAD {
+        Body::empty()
+    } else {
+        let stream = state.storage.get(&key, range).await?;
+        if counts_as_download(range, size) {
+            record_download(&state.db, ArtifactKind::Dataset, id, Some(file_id)).await?;
+        }
+        Body::from_stream(stream)
+    };
+
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        name.replace(['"', '\\'], "_")
+    );
+    let mut response = Response::builder()
+        .header(header::CONTENT_TYPE, media_type)
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ETAG, format!("\"{sha256}\""));
+    response = match range {
+        Some((first, last)) => response
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {first}-{last}/{size}"),
+            )
+            .header(header::CONTENT_LENGTH, last - first + 1),
+        None => response.header(header::CONTENT_LENGTH, size),
+    };
+    response
+        .body(body)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Models are hosted externally (see `hosted_by`), so the client reports the
+/// hand-off here instead of streaming bytes through us.
+pub async fn record_model_download(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let exists: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM models WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    let downloads_count = record_download(&state.db, ArtifactKind::Model, id, None).await?;
+    Ok(Json(
+        serde_json::json!({ "downloads_count": downloads_count }),
+    ))
+}
+
+// =============================================================================
+// MODELS
+// =============================================================================
+
+/// FTS branch of `get_models`; run by `search_with_fallback` once per query variant.
+async fn search_models(
+    db: &SqlitePool,
+    fts: String,
+    params: &ListQuery,
+    filters: &QueryFilters,
+    hl_start: &str,
+    hl_end: &str,
+) -> Result<Vec<Model>, AppError> {
+    let rows = sqlx::query_as::<_, Model>(
+        r#"
+        SELECT
+            m.id, m.title, m.description, m.about_model, m.image_url,
+            m.likes_count, m.downloads_count, m.views_count, m.rating_average, m.rating_count,
+            o.name AS source_org,
+            o.name AS source_organization,
+            s.name AS sector,
+            s.slug AS sector_slug,
+            u.full_name AS created_by,
+            m.license, m.hosted_by, m.model_type, m.model_format,
+            m.visibility, m.size, m.model_updated_at,
+            (SELECT GROUP_CONCAT(t.name)
+               FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
+               WHERE mt.model_id = m.id) AS tags_csv,
+            CASE WHEN highlight(models_fts, 0, ?3, ?4) <> m.title
+                 THEN highlight(models_fts, 0, ?3, ?4) END AS match_title,
+            CASE WHEN highlight(models_fts, 1, ?3, ?4) <> m.description
+                 THEN snippet(models_fts, 1, ?3, ?4, '…', 24) END AS match_description,
+            CASE WHEN highlight(models_fts, 2, ?3, ?4) <> COALESCE(m.about_model, '')
+                 THEN snippet(models_fts, 2, ?3, ?4, '…', 24) END AS match_about,
+            CASE WHEN highlight(models_fts, 3, ?3, ?4) <> COALESCE(m.tags_text, '')
+                 THEN highlight(models_fts, 3, ?3, ?4) END AS match_tags
+        FROM models m
+        JOIN models_fts f ON f.rowid = m.id
+        LEFT JOIN organizations o ON o.id = m.organization_id
+        LEFT JOIN sectors s       ON s.id = m.sector_id
+        LEFT JOIN users u         ON u.id = m.created_by_user_id
+        WHERE models_fts MATCH ?1
+          AND m.deleted_at IS NULL
+          AND (?2 IS NULL OR s.slug = ?2)
+          AND (?5 IS NULL OR EXISTS (SELECT 1 FROM json_each(?5) j
+                               WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+          AND (?6 IS NULL OR EXISTS (SELECT 1 FROM json_each(?6) j
+                               WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+          AND NOT EXISTS (SELECT 1 FROM json_each(?7) j
+                          WHERE NOT EXISTS (SELECT 1 FROM model_tags x JOIN tags t ON t.id = x.tag_id
+                                            WHERE x.model_id = m.id AND t.slug = j.value))
+        ORDER BY rank
+        "#,
+    )
+    .bind(fts)
+    .bind(params.sector.as_deref())
+    .bind(hl_start)
+    .bind(hl_end)
+    .bind(filters.sectors.as_deref())
+    .bind(filters.orgs.as_deref())
+    .bind(filters.tags.as_deref())
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+pub async fn get_models(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListQuery>,
+) -> Result<(HeaderMap, Json<SearchResults>), AppError> {
+    let log = SearchLog::start("models", Some(CURRENT_USER_ID));
+    let query = params
+        .search
+        .as_deref()
+        .map(SearchQuery::parse)
+        .transpose()?;
+    let filters = query.as_ref().map(SearchQuery::filters).unwrap_or_default();
+    reject_column_filter(&filters)?;
+
+    let (mut rows, did_you_mean) = match query.filter(SearchQuery::has_terms) {
+        Some(query) => {
+            let (hl_start, hl_end) = highlight_markers(&params)?;
+            search::search_with_fallback(&state.db, SearchIndex::Models, &query, |fts| {
+                search_models(&state.db, fts, &params, &filters, &hl_start, &hl_end)
+            })
+            .await?
+        }
+        None => {
+            let rows = sqlx::query_as::<_, Model>(
+                r#"
+                SELECT
+                    m.id, m.title, m.description, m.about_model, m.image_url,
+                    m.likes_count, m.downloads_count, m.views_count, m.rating_average, m.rating_count,
+                    o.name AS source_org,
+                    o.name AS source_organization,
+                    s.name AS sector,
+                    s.slug AS sector_slug,
+                    u.full_name AS created_by,
+                    m.license, m.hosted_by, m.model_type, m.model_format,
+                    m.visibility, m.size, m.model_updated_at,
+                    (SELECT GROUP_CONCAT(t.name)
+                       FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
+                       WHERE mt.model_id = m.id) AS tags_csv
+                FROM models m
+                LEFT JOIN organizations o ON o.id = m.organization_id
+                LEFT JOIN sectors s       ON s.id = m.sector_id
+                LEFT JOIN users u         ON u.id = m.created_by_user_id
+                WHERE m.deleted_at IS NULL
+                  AND (?1 IS NULL OR s.slug = ?1)
+                  AND (?2 IS NULL OR EXISTS (SELECT 1 FROM json_each(?2) j
+                                       WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+                  AND (?3 IS NULL OR EXISTS (SELECT 1 FROM json_each(?3) j
+                                       WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+                  AND NOT EXISTS (SELECT 1 FROM json_each(?4) j
+                                  WHERE NOT EXISTS (SELECT 1 FROM model_tags x JOIN tags t ON t.id = x.tag_id
+                                                    WHERE x.model_id = m.id AND t.slug = j.value))
+                ORDER BY m.id
+                "#,
+            )
+            .bind(params.sector.as_deref())
+            .bind(filters.sectors.as_deref())
+            .bind(filters.orgs.as_deref())
+            .bind(filters.tags.as_deref())
+            .fetch_all(&state.db)
+            .await?;
+            (rows, None)
+        }
+    };
+
+    match params.sort {
+        Some(ListSort::Rating) => sort_by_rating(&mut rows, |r| (r.rating_average, r.rating_count)),
+        Some(ListSort::Trending) => {
+            sort_by_trending(&state.db, ArtifactKind::Model, &mut rows, |r| r.id).await?
+        }
+        None => {}
+    }
+    let items: Vec<_> = rows.into_iter().map(model_to_json).collect();
+    let headers = log_list_search(
+        &state.db,
+        log,
+        &params,
+        items.len(),
+        did_you_mean.as_deref(),
+    )
+    .await;
+    Ok((
+        headers,
+        Json(SearchResults {
+            items,
+            did_you_mean,
+        }),
+    ))
+}
+
+pub async fn get_model_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let row = sqlx::query_as::<_, Model>(
+        r#"
+        SELECT
+            m.id, m.title, m.description, m.about_model, m.image_url,
+            m.likes_count, m.downloads_count, m.views_count, m.rating_average, m.rating_count,
+            o.name AS source_org,
+            o.name AS source_organization,
+            s.name AS sector,
+            s.slug AS sector_slug,
+            u.full_name AS created_by,
+            m.license, m.hosted_by, m.model_type, m.model_format,
+            m.visibility, m.size, m.model_updated_at,
+            (SELECT GROUP_CONCAT(t.name)
+               FROM model_tags mt JOIN tags t ON t.id = mt.tag_id
+               WHERE mt.model_id = m.id) AS tags_csv
+        FROM models m
+        LEFT JOIN organizations o ON o.id = m.organization_id
+        LEFT JOIN sectors s       ON s.id = m.sector_id
+        LEFT JOIN users u         ON u.id = m.created_by_user_id
+        WHERE m.id = ?1 AND m.deleted_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    record_view(&state, ArtifactKind::Model, id, &headers).await;
+
+    let liked = liked_by_me(&state.db, ArtifactKind::Model, id).await?;
+    let rating = rating_summary(&state.db, ArtifactKind::Model, id).await?;
+    let review = my_review(&state.db, ArtifactKind::Model, id).await?;
+    let mut v = model_to_json(row);
+    if let Some(obj) = v.as_object_mut() {
+        obj.insert("liked_by_me".to_string(), serde_json::json!(liked));
+        obj.insert("rating".to_string(), serde_json::json!(rating));
+        obj.insert("my_review".to_string(), serde_json::json!(review));
+    }
+    Ok(Json(v))
+}
+
+// =============================================================================
+// USECASES
+// =============================================================================
+
+/// FTS branch of `get_usecases`; run by `search_with_fallback` once per query variant.
+async fn search_usecases(
+    db: &SqlitePool,
+    fts: String,
+    params: &ListQuery,
+    filters: &QueryFilters,
+    hl_start: &str,
+    hl_end: &str,
+) -> Result<Vec<UseCase>, AppError> {
+    let rows = sqlx::query_as::<_, UseCase>(
+        r#"
+        SELECT
+            u.id, u.title, u.description, u.about_use_case, u.image_url, u.likes_count, u.views_count,
+            o.name AS source_org,
+            s.name AS sector,
+            s.slug AS sector_slug,
+            (SELECT GROUP_CONCAT(t.name)
+               FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
+               WHERE ut.usecase_id = u.id) AS tags_csv,
+            CASE WHEN highlight(usecases_fts, 0, ?3, ?4) <> u.title
+                 THEN highlight(usecases_fts, 0, ?3, ?4) END AS match_title,
+            CASE WHEN highlight(usecases_fts, 1, ?3, ?4) <> u.description
+                 THEN snippet(usecases_fts, 1, ?3, ?4, '…', 24) END AS match_description,
+            CASE WHEN highlight(usecases_fts, 2, ?3, ?4) <> COALESCE(u.about_use_case, '')
+                 THEN snippet(usecases_fts, 2, ?3, ?4, '…', 24) END AS match_about,
+            CASE WHEN highlight(usecases_fts, 3, ?3, ?4) <> COALESCE(u.tags_text, '')
+                 THEN highlight(usecases_fts, 3, ?3, ?4) END AS match_tags
+        FROM usecases u
+        JOIN usecases_fts f ON f.rowid = u.id
+        LEFT JOIN organizations o ON o.id = u.organization_id
+        LEFT JOIN sectors s       ON s.id = u.sector_id
+        WHERE usecases_fts MATCH ?1
+          AND u.deleted_at IS NULL
+          AND (?2 IS NULL OR s.slug = ?2)
+          AND (?5 IS NULL OR EXISTS (SELECT 1 FROM json_each(?5) j
+                               WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+          AND (?6 IS NULL OR EXISTS (SELECT 1 FROM json_each(?6) j
+                               WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+          AND NOT EXISTS (SELECT 1 FROM json_each(?7) j
+                          WHERE NOT EXISTS (SELECT 1 FROM usecase_tags x JOIN tags t ON t.id = x.tag_id
+                                            WHERE x.usecase_id = u.id AND t.slug = j.value))
+        ORDER BY rank
+        "#,
+    )
+    .bind(fts)
+    .bind(params.sector.as_deref())
+    .bind(hl_start)
+    .bind(hl_end)
+    .bind(filters.sectors.as_deref())
+    .bind(filters.orgs.as_deref())
+    .bind(filters.tags.as_deref())
+    .fetch_all(db)
+    .await?;
+    Ok(rows)
+}
+
+pub async fn get_usecases(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListQuery>,
+) -> Result<(HeaderMap, Json<SearchResults>), AppError> {
+    let log = SearchLog::start("usecases", Some(CURRENT_USER_ID));
+    let query = params
+        .search
+        .as_deref()
+        .map(SearchQuery::parse)
+        .transpose()?;
+    let filters = query.as_ref().map(SearchQuery::filters).unwrap_or_default();
+    reject_column_filter(&filters)?;
+    if params.sort == Some(ListSort::Rating) {
+        return Err(AppError::ValidationError(
+            "sort=rating only applies to datasets and models".to_string(),
+        ));
+    }
+
+    let (mut rows, did_you_mean) = match query.filter(SearchQuery::has_terms) {
+        Some(query) => {
+            let (hl_start, hl_end) = highlight_markers(&params)?;
+            search::search_with_fallback(&state.db, SearchIndex::UseCases, &query, |fts| {
+                search_usecases(&state.db, fts, &params, &filters, &hl_start, &hl_end)
+            })
+            .await?
+        }
+        None => {
+            let rows = sqlx::query_as::<_, UseCase>(
+                r#"
+                SELECT
+                    u.id, u.title, u.description, u.about_use_case, u.image_url, u.likes_count, u.views_count,
+                    o.name AS source_org,
+                    s.name AS sector,
+                    s.slug AS sector_slug,
+                    (SELECT GROUP_CONCAT(t.name)
+                       FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
+                       WHERE ut.usecase_id = u.id) AS tags_csv
+                FROM usecases u
+                LEFT JOIN organizations o ON o.id = u.organization_id
+                LEFT JOIN sectors s       ON s.id = u.sector_id
+                WHERE u.deleted_at IS NULL
+                  AND (?1 IS NULL OR s.slug = ?1)
+                  AND (?2 IS NULL OR EXISTS (SELECT 1 FROM json_each(?2) j
+                                       WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+                  AND (?3 IS NULL OR EXISTS (SELECT 1 FROM json_each(?3) j
+                                       WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+                  AND NOT EXISTS (SELECT 1 FROM json_each(?4) j
+                                  WHERE NOT EXISTS (SELECT 1 FROM usecase_tags x JOIN tags t ON t.id = x.tag_id
+                                                    WHERE x.usecase_id = u.id AND t.slug = j.value))
+                ORDER BY u.id
+                "#,
+            )
+            .bind(params.sector.as_deref())
+            .bind(filters.sectors.as_deref())
+            .bind(filters.orgs.as_deref())
+            .bind(filters.tags.as_deref())
+            .fetch_all(&state.db)
+            .await?;
+            (rows, None)
+        }
+    };
+
+    if params.sort == Some(ListSort::Trending) {
+        sort_by_trending(&state.db, ArtifactKind::UseCase, &mut rows, |r| r.id).await?;
+    }
+    let items: Vec<_> = rows.into_iter().map(usecase_to_json).collect();
+    let headers = log_list_search(
+        &state.db,
+        log,
+        &params,
+        items.len(),
+        did_you_mean.as_deref(),
+    )
+    .await;
+    Ok((
+        headers,
+        Json(SearchResults {
+            items,
+            did_you_mean,
+        }),
+    ))
+}
+
+pub async fn get_usecase_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let row = sqlx::query_as::<_, UseCase>(
+        r#"
+        SELECT
+            u.id, u.title, u.description, u.about_use_case, u.image_url, u.likes_count, u.views_count,
+            o.name AS source_org,
+            s.name AS sector,
+            s.slug AS sector_slug,
+            (SELECT GROUP_CONCAT(t.name)
+               FROM usecase_tags ut JOIN tags t ON t.id = ut.tag_id
+               WHERE ut.usecase_id = u.id) AS tags_csv
+        FROM usecases u
+        LEFT JOIN organizations o ON o.id = u.organization_id
+        LEFT JOIN sectors s       ON s.id = u.sector_id
+        WHERE u.id = ?1 AND u.deleted_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    record_view(&state, ArtifactKind::UseCase, id, &headers).await;
+
+    let liked = liked_by_me(&state.db, ArtifactKind::UseCase, id).await?;
+    let mut v = usecase_to_json(row);
+    if let Some(obj) = v.as_object_mut() {
+        obj.insert("liked_by_me".to_string(), serde_json::json!(liked));
+    }
+    Ok(Json(v))
+}
+
+// =============================================================================
+// TOOLKIT  (FTS5 search, no tags, no sector)
+// =============================================================================
+
+pub async fn get_toolkit(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListQuery>,
+) -> Result<(HeaderMap, Json<Vec<Toolkit>>), AppError> {
+    let log = SearchLog::start("toolkit", Some(CURRENT_USER_ID));
+    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        let fts = Synonyms::load(&state.db).await?.expand(search);
+        sqlx::query_as::<_, Toolkit>(
+            r#"
+            SELECT t.id, t.title, t.description, t.image_url, t.overview, t.key_capabilities,
+                   t.why_it_is_included, t.resources_on_getting_started,
+                   t.license_and_compliance, t.screenshots_and_ui_previews,
+                   t.versioning_and_community_info
+            FROM toolkit t
+            JOIN toolkit_fts f ON f.rowid = t.id
+            WHERE toolkit_fts MATCH ?1
+              AND t.deleted_at IS NULL
+            ORDER BY rank
+            "#,
+        )
+        .bind(fts)
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        sqlx::query_as::<_, Toolkit>(
+            r#"
+            SELECT id, title, description, image_url, overview, key_capabilities,
+                   why_it_is_included, resources_on_getting_started,
+                   license_and_compliance, screenshots_and_ui_previews,
+                   versioning_and_community_info
+            FROM toolkit
+            WHERE deleted_at IS NULL
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&state.db)
+        .await?
+    };
+
+    let headers = log_list_search(&state.db, log, &params, rows.len(), None).await;
+    Ok((headers, Json(rows)))
+}
+
+pub async fn get_toolkit_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Toolkit>, AppError> {
+    let row = sqlx::query_as::<_, Toolkit>(
+        r#"
+        SELECT id, title, description, image_url, overview, key_capabilities,
+               why_it_is_included, resources_on_getting_started,
+               license_and_compliance, screenshots_and_ui_previews,
+               versioning_and_community_info
+        FROM toolkit
+        WHERE id = ?1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(row))
+}
+
+// =============================================================================
 // ARTICLES  (FTS5 search, no tags)
 // =============================================================================
 
-pub async fn get_articles(
+pub async fn get_articles(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Article>>, AppError> {
+    let rows = sqlx::query_as::<_, Article>(
+        r#"
+        SELECT id, title, description, content, image_url, likes_count, author, read_time,
+               category, disclaimer, published_at
+        FROM articles
+        WHERE deleted_at IS NULL
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+pub async fn get_article_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let row = sqlx::query_as::<_, Article>(
+        r#"
+        SELECT id, title, description, content, image_url, likes_count, author, read_time,
+               category, disclaimer, published_at
+        FROM articles
+        WHERE id = ?1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let liked = liked_by_me(&state.db, ArtifactKind::Article, id).await?;
+    let mut v = serde_json::to_value(&row)?;
+    if let Some(obj) = v.as_object_mut() {
+        obj.insert("liked_by_me".to_string(), serde_json::json!(liked));
+    }
+    Ok(Json(v))
+}
+
+// =============================================================================
+// TUTORIALS  (FTS5 search)
+// =============================================================================
+
+pub async fn get_tutorials(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListQuery>,
+) -> Result<(HeaderMap, Json<Vec<Tutorial>>), AppError> {
+    let log = SearchLog::start("tutorials", Some(CURRENT_USER_ID));
+    let rows = if let Some(search) = params.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        let fts = Synonyms::load(&state.db).await?.expand(search);
+        sqlx::query_as::<_, Tutorial>(
+            r#"
+            SELECT t.id, t.title, t.description, t.duration, t.video_url, t.uploaded_date
+            FROM tutorials t
+            JOIN tutorials_fts f ON f.rowid = t.id
+            WHERE tutorials_fts MATCH ?1
+              AND t.deleted_at IS NULL
+            ORDER BY rank
+            "#,
+        )
+        .bind(fts)
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        sqlx::query_as::<_, Tutorial>(
+            r#"
+            SELECT id, title, description, duration, video_url, uploaded_date
+            FROM tutorials
+            WHERE deleted_at IS NULL
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&state.db)
+        .await?
+    };
+    let headers = log_list_search(&state.db, log, &params, rows.len(), None).await;
+    Ok((headers, Json(rows)))
+}
+
+// =============================================================================
+// SEMANTIC SEARCH  (hybrid bm25 + embedding similarity across artifact types)
+// =============================================================================
+
+const SEMANTIC_DEFAULT_LIMIT: i64 = 20;
+const SEMANTIC_MAX_LIMIT: i64 = 100;
+
+pub async fn semantic_search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SemanticSearchQuery>,
+) -> Result<(HeaderMap, Json<Vec<SemanticHit>>), AppError> {
+    let log = SearchLog::start("search", Some(CURRENT_USER_ID));
+    let Some(q) = params.q.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return Err(AppError::ValidationError(
+            "Query parameter 'q' is required".to_string(),
+        ));
+    };
+    let kinds = parse_kinds(params.types.as_deref())?;
+    let limit = params
+        .limit
+        .unwrap_or(SEMANTIC_DEFAULT_LIMIT)
+        .clamp(1, SEMANTIC_MAX_LIMIT) as usize;
+
+    let hits = embeddings::hybrid_search(
+        &state.db,
+        state.embedder.as_ref(),
+        q,
+        &kinds,
+        limit,
+        state.config.search_hybrid_alpha,
+    )
+    .await?;
+
+    let filters = serde_json::json!({ "types": params.types });
+    let search_id = log.record(&state.db, q, filters, hits.len(), None).await;
+    Ok((search_log::header(search_id), Json(hits)))
+}
+
+// =============================================================================
+// RELATED ARTIFACTS  (shared tags, sector/org, embedding similarity; cross-type)
+// =============================================================================
+
+const RELATED_DEFAULT_LIMIT: i64 = 10;
+const RELATED_MAX_LIMIT: i64 = 50;
+
+async fn related_for(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    params: RelatedQuery,
+) -> Result<Json<Vec<RelatedItem>>, AppError> {
+    let kinds = parse_kinds(params.types.as_deref())?;
+    let limit = params
+        .limit
+        .unwrap_or(RELATED_DEFAULT_LIMIT)
+        .clamp(1, RELATED_MAX_LIMIT) as usize;
+
+    let items =
+        related::related(&state.db, state.embedder.as_ref(), kind, id, &kinds, limit).await?;
+    Ok(Json(items))
+}
+
+pub async fn get_dataset_related(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<RelatedQuery>,
+) -> Result<Json<Vec<RelatedItem>>, AppError> {
+    related_for(&state, ArtifactKind::Dataset, id, params).await
+}
+
+pub async fn get_model_related(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<RelatedQuery>,
+) -> Result<Json<Vec<RelatedItem>>, AppError> {
+    related_for(&state, ArtifactKind::Model, id, params).await
+}
+
+pub async fn get_usecase_related(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<RelatedQuery>,
+) -> Result<Json<Vec<RelatedItem>>, AppError> {
+    related_for(&state, ArtifactKind::UseCase, id, params).await
+}
+
+// =============================================================================
+// TRENDING  (decayed recent-activity scores, refreshed in the background; see trending.rs)
+// =============================================================================
+
+const TRENDING_DEFAULT_LIMIT: i64 = 20;
+const TRENDING_MAX_LIMIT: i64 = 100;
+
+/// Stable sort by trending score, highest first; entries without recent activity keep their order at the end.
+async fn sort_by_trending<T>(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    rows: &mut [T],
+    id: impl Fn(&T) -> i64,
+) -> Result<(), AppError> {
+    let scores = trending::scores(db, kind).await?;
+    let score = |row: &T| scores.get(&id(row)).copied().unwrap_or(0.0);
+    rows.sort_by(|a, b| score(b).total_cmp(&score(a)));
+    Ok(())
+}
+
+pub async fn get_trending(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TrendingQuery>,
+) -> Result<Json<Vec<TrendingItem>>, AppError> {
+    let kind = params
+        .kind
+        .as_deref()
+        .map(str::parse::<ArtifactKind>)
+        .transpose()
+        .map_err(AppError::ValidationError)?;
+    if kind == Some(ArtifactKind::Article) {
+        return Err(AppError::ValidationError(
+            "Articles have no trending score; type must be dataset, model or usecase".to_string(),
+        ));
+    }
+    let limit = params
+        .limit
+        .unwrap_or(TRENDING_DEFAULT_LIMIT)
+        .clamp(1, TRENDING_MAX_LIMIT);
+
+    let rows = sqlx::query_as::<_, TrendingItem>(
+        r#"
+        WITH entries AS (
+            SELECT 'dataset' AS kind, id, title, description, image_url, sector_id, deleted_at
+            FROM datasets
+            UNION ALL
+            SELECT 'model', id, title, description, image_url, sector_id, deleted_at
+            FROM models
+            UNION ALL
+            SELECT 'usecase', id, title, description, image_url, sector_id, deleted_at
+            FROM usecases
+        )
+        SELECT e.kind, e.id, e.title, e.description, e.image_url,
+               s.name AS sector, s.slug AS sector_slug,
+               t.score, t.views, t.likes, t.downloads
+        FROM trending_scores t
+        JOIN entries e ON e.kind = t.artifact_kind AND e.id = t.artifact_id
+        LEFT JOIN sectors s ON s.id = e.sector_id
+        WHERE e.deleted_at IS NULL
+          AND (?1 IS NULL OR t.artifact_kind = ?1)
+          AND (?2 IS NULL OR s.slug = ?2)
+        ORDER BY t.score DESC, e.kind, e.id DESC
+        LIMIT ?3
+        "#,
+    )
+    .bind(kind.map(ArtifactKind::as_str))
+    .bind(params.sector.as_deref())
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+// =============================================================================
+// LIKES  (one per user per artifact; counters kept in step transactionally)
+// =============================================================================
+
+async fn liked_by_me(db: &SqlitePool, kind: ArtifactKind, id: i64) -> Result<bool, AppError> {
+    let (liked,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM likes
+                        WHERE user_id = ?1 AND artifact_kind = ?2 AND artifact_id = ?3)",
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(kind.as_str())
+    .bind(id)
+    .fetch_one(db)
+    .await?;
+    Ok(liked)
+}
+
+/// Like (`liked = true`) or unlike an artifact. Idempotent: repeating either
+/// leaves the counter unchanged.
+async fn set_like(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    liked: bool,
+) -> Result<Json<LikeState>, AppError> {
+    let table = kind.table();
+    let mut tx = state.db.begin().await?;
+
+    let exists: Option<(i64,)> = sqlx::query_as(&format!(
+        "SELECT id FROM {table} WHERE id = ?1 AND deleted_at IS NULL"
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let (change_sql, counter_sql) = if liked {
+        (
+            "INSERT OR IGNORE INTO likes (user_id, artifact_kind, artifact_id) VALUES (?1, ?2, ?3)",
+            format!("UPDATE {table} SET likes_count = likes_count + 1 WHERE id = ?1"),
+        )
+    } else {
+        (
+            "DELETE FROM likes WHERE user_id = ?1 AND artifact_kind = ?2 AND artifact_id = ?3",
+            format!("UPDATE {table} SET likes_count = MAX(likes_count - 1, 0) WHERE id = ?1"),
+        )
+    };
+    let changed = sqlx::query(change_sql)
+        .bind(CURRENT_USER_ID)
+        .bind(kind.as_str())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    if changed.rows_affected() > 0 {
+        sqlx::query(&counter_sql).bind(id).execute(&mut *tx).await?;
+    }
+
+    let (likes_count,): (i64,) =
+        sqlx::query_as(&format!("SELECT likes_count FROM {table} WHERE id = ?1"))
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+    tx.commit().await?;
+    Ok(Json(LikeState { liked, likes_count }))
+}
+
+pub async fn like_dataset(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Dataset, id, true).await
+}
+
+pub async fn unlike_dataset(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Dataset, id, false).await
+}
+
+pub async fn like_model(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Model, id, true).await
+}
+
+pub async fn unlike_model(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Model, id, false).await
+}
+
+pub async fn like_usecase(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::UseCase, id, true).await
+}
+
+pub async fn unlike_usecase(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::UseCase, id, false).await
+}
+
+pub async fn like_article(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Article, id, true).await
+}
+
+pub async fn unlike_article(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<LikeState>, AppError> {
+    set_like(&state, ArtifactKind::Article, id, false).await
+}
+
+pub async fn get_user_likes(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<LikedItem>>, AppError> {
+    let rows = sqlx::query_as::<_, LikedItem>(
+        r#"
+        SELECT l.artifact_kind AS kind, l.artifact_id AS id,
+               COALESCE(d.title, m.title, u.title, a.title) AS title,
+               COALESCE(d.description, m.description, u.description, a.description) AS description,
+               COALESCE(d.image_url, m.image_url, u.image_url, a.image_url) AS image_url,
+               COALESCE(d.likes_count, m.likes_count, u.likes_count, a.likes_count) AS likes_count,
+               l.created_at AS liked_at
+        FROM likes l
+        LEFT JOIN datasets d ON l.artifact_kind = 'dataset' AND d.id = l.artifact_id AND d.deleted_at IS NULL
+        LEFT JOIN models m   ON l.artifact_kind = 'model'   AND m.id = l.artifact_id AND m.deleted_at IS NULL
+        LEFT JOIN usecases u ON l.artifact_kind = 'usecase' AND u.id = l.artifact_id AND u.deleted_at IS NULL
+        LEFT JOIN articles a ON l.artifact_kind = 'article' AND a.id = l.artifact_id AND a.deleted_at IS NULL
+        WHERE l.user_id = ?1
+          AND COALESCE(d.id, m.id, u.id, a.id) IS NOT NULL
+        ORDER BY l.created_at DESC, l.artifact_kind, l.artifact_id DESC
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+// =============================================================================
+// REVIEWS  (1-5 stars plus optional text; one per user per dataset/model)
+// =============================================================================
+
+const MAX_REVIEW_LEN: usize = 5000;
+
+/// Recompute the denormalized rating columns after a review changes.
+async fn refresh_rating(
+    tx: &mut sqlx::SqliteConnection,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<(), AppError> {
+    sqlx::query(&format!(
+        r#"
+        UPDATE {table} SET
+            rating_average = (SELECT AVG(rating) FROM reviews
+                              WHERE artifact_kind = ?1 AND artifact_id = ?2),
+            rating_count   = (SELECT COUNT(*) FROM reviews
+                              WHERE artifact_kind = ?1 AND artifact_id = ?2)
+        WHERE id = ?2
+        "#,
+        table = kind.table()
+    ))
+    .bind(kind.as_str())
+    .bind(id)
+    .execute(tx)
+    .await?;
+    Ok(())
+}
+
+async fn rating_summary(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<RatingSummary, AppError> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT rating, COUNT(*) FROM reviews
+        WHERE artifact_kind = ?1 AND artifact_id = ?2
+        GROUP BY rating
+        "#,
+    )
+    .bind(kind.as_str())
+    .bind(id)
+    .fetch_all(db)
+    .await?;
+
+    let mut distribution = [0; 5];
+    for (rating, n) in rows {
+        if let Some(slot) = usize::try_from(rating - 1)
+            .ok()
+            .and_then(|i| distribution.get_mut(i))
+        {
+            *slot = n;
+        }
+    }
+    let count: i64 = distribution.iter().sum();
+    let total: i64 = distribution
+        .iter()
+        .zip(1..)
+        .map(|(n, stars)| n * stars)
+        .sum();
+    Ok(RatingSummary {
+        average: (count > 0).then(|| total as f64 / count as f64),
+        count,
+        distribution,
+    })
+}
+
+const REVIEW_SELECT: &str = r#"
+    SELECT r.id, r.user_id AS author_id, u.username AS author, r.rating, r.body,
+           r.created_at, r.updated_at
+    FROM reviews r
+    JOIN users u ON u.id = r.user_id
+"#;
+
+async fn my_review(
+    db: &SqlitePool,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<Option<Review>, AppError> {
+    let row = sqlx::query_as::<_, Review>(&format!(
+        "{REVIEW_SELECT} WHERE r.user_id = ?1 AND r.artifact_kind = ?2 AND r.artifact_id = ?3"
+    ))
+    .bind(CURRENT_USER_ID)
+    .bind(kind.as_str())
+    .bind(id)
+    .fetch_optional(db)
+    .await?;
+    Ok(row)
+}
+
+/// Best average first, more ratings breaking ties; unrated entries last. The
+/// sort is stable, so otherwise equal entries keep their relevance / id order.
+fn sort_by_rating<T>(rows: &mut [T], key: impl Fn(&T) -> (Option<f64>, i64)) {
+    rows.sort_by(|a, b| {
+        let (a, b) = (key(a), key(b));
+        b.0.unwrap_or(-1.0)
+            .total_cmp(&a.0.unwrap_or(-1.0))
+            .then(b.1.cmp(&a.1))
+    });
+}
+
+async fn list_reviews(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<Json<Vec<Review>>, AppError> {
+    let rows = sqlx::query_as::<_, Review>(&format!(
+        "{REVIEW_SELECT} WHERE r.artifact_kind = ?1 AND r.artifact_id = ?2
+         ORDER BY r.updated_at DESC, r.id DESC"
+    ))
+    .bind(kind.as_str())
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+/// Create or replace the current user's review.
+async fn upsert_review(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    payload: UpsertReview,
+) -> Result<Json<Review>, AppError> {
+    if !(1..=5).contains(&payload.rating) {
+        return Err(AppError::ValidationError(
+            "Rating must be between 1 and 5".to_string(),
+        ));
+    }
+    let body = payload
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    if body.is_some_and(|b| b.chars().count() > MAX_REVIEW_LEN) {
+        return Err(AppError::ValidationError(format!(
+            "Reviews are limited to {MAX_REVIEW_LEN} characters"
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let exists: Option<(i64,)> = sqlx::query_as(&format!(
+        "SELECT id FROM {} WHERE id = ?1 AND deleted_at IS NULL",
+        kind.table()
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO reviews (user_id, artifact_kind, artifact_id, rating, body)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        ON CONFLICT (user_id, artifact_kind, artifact_id) DO UPDATE SET
+            rating     = excluded.rating,
+            body       = excluded.body,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(kind.as_str())
+    .bind(id)
+    .bind(payload.rating)
+    .bind(body)
+    .execute(&mut *tx)
+    .await?;
+    refresh_rating(&mut tx, kind, id).await?;
+    tx.commit().await?;
+
+    let review = my_review(&state.db, kind, id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(review))
+}
+
+async fn remove_review(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+) -> Result<StatusCode, AppError> {
+    let mut tx = state.db.begin().await?;
+    let res = sqlx::query(
+        "DELETE FROM reviews WHERE user_id = ?1 AND artifact_kind = ?2 AND artifact_id = ?3",
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(kind.as_str())
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    refresh_rating(&mut tx, kind, id).await?;
+    tx.commit().await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_dataset_reviews(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Review>>, AppError> {
+    list_reviews(&state, ArtifactKind::Dataset, id).await
+}
+
+pub async fn put_dataset_review(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpsertReview>,
+) -> Result<Json<Review>, AppError> {
+    upsert_review(&state, ArtifactKind::Dataset, id, payload).await
+}
+
+pub async fn delete_dataset_review(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    remove_review(&state, ArtifactKind::Dataset, id).await
+}
+
+pub async fn get_model_reviews(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Review>>, AppError> {
+    list_reviews(&state, ArtifactKind::Model, id).await
+}
+
+pub async fn put_model_review(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpsertReview>,
+) -> Result<Json<Review>, AppError> {
+    upsert_review(&state, ArtifactKind::Model, id, payload).await
+}
+
+pub async fn delete_model_review(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    remove_review(&state, ArtifactKind::Model, id).await
+}
+
+// =============================================================================
+// COMMENTS  (threaded, markdown bodies; author edit/delete, admin hide/lock)
+// =============================================================================
+
+const MAX_COMMENT_LEN: usize = 10_000;
+const COMMENT_EXCERPT_LEN: usize = 140;
+const COMMENTS_DEFAULT_PER_PAGE: i64 = 20;
+const COMMENTS_MAX_PER_PAGE: i64 = 100;
+
+/// Binds ?1: whether the viewer is an admin (who still sees hidden bodies).
+const COMMENT_SELECT: &str = r#"
+    SELECT c.id, c.parent_id, c.root_id, c.user_id AS author_id, u.username AS author,
+           CASE WHEN c.deleted_at IS NOT NULL THEN NULL
+                WHEN c.hidden_at IS NOT NULL AND NOT ?1 THEN NULL
+                ELSE c.body END AS body,
+           c.deleted_at IS NOT NULL AS is_deleted,
+           c.hidden_at IS NOT NULL AS is_hidden,
+           c.locked_at IS NOT NULL AS is_locked,
+           c.edited_at, c.created_at
+    FROM comments c
+    JOIN users u ON u.id = c.user_id
+"#;
+
+/// Deleted comments only stay in the listing as placeholders for their replies.
+const COMMENT_LISTED: &str = "(c.deleted_at IS NULL OR EXISTS (
+    SELECT 1 FROM comments r WHERE r.parent_id = c.id AND r.deleted_at IS NULL))";
+
+fn validate_comment_body(body: &str) -> Result<&str, AppError> {
+    let body = body.trim();
+    if body.is_empty() || body.chars().count() > MAX_COMMENT_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Comments must be between 1 and {MAX_COMMENT_LEN} characters"
+        )));
+    }
+    Ok(body)
+}
+
+async fn fetch_comment(db: &SqlitePool, id: i64) -> Result<Comment, AppError> {
+    let admin = is_admin(db).await?;
+    let row = sqlx::query_as::<_, Comment>(&format!("{COMMENT_SELECT} WHERE c.id = ?2"))
+        .bind(admin)
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(row)
+}
+
+/// Whether the thread `comment_id` belongs to (or starts) is locked.
+async fn thread_locked(db: &SqlitePool, comment_id: i64) -> Result<bool, AppError> {
+    let (locked,): (bool,) = sqlx::query_as(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM comments c
+                       JOIN comments t ON t.id = COALESCE(c.root_id, c.id)
+                       WHERE c.id = ?1 AND t.locked_at IS NOT NULL)
+        "#,
+    )
+    .bind(comment_id)
+    .fetch_one(db)
+    .await?;
+    Ok(locked)
+}
+
+async fn list_comments(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    params: CommentQuery,
+) -> Result<Json<CommentPage>, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let per_page = params
+        .per_page
+        .unwrap_or(COMMENTS_DEFAULT_PER_PAGE)
+        .clamp(1, COMMENTS_MAX_PER_PAGE);
+    let admin = is_admin(&state.db).await?;
+
+    let (total_threads,): (i64,) = sqlx::query_as(&format!(
+        r#"
+        SELECT COUNT(*) FROM comments c
+        WHERE c.artifact_kind = ?1 AND c.artifact_id = ?2 AND c.parent_id IS NULL
+          AND {COMMENT_LISTED}
+        "#
+    ))
+    .bind(kind.as_str())
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let roots = sqlx::query_as::<_, Comment>(&format!(
+        r#"
+        {COMMENT_SELECT}
+        WHERE c.artifact_kind = ?2 AND c.artifact_id = ?3 AND c.parent_id IS NULL
+          AND {COMMENT_LISTED}
+        ORDER BY c.created_at DESC, c.id DESC
+        LIMIT ?4 OFFSET ?5
+        "#
+    ))
+    .bind(admin)
+    .bind(kind.as_str())
+    .bind(id)
+    .bind(per_page)
+    .bind((page - 1) * per_page)
+    .fetch_all(&state.db)
+    .await?;
+
+    let root_ids: Vec<i64> = roots.iter().map(|c| c.id).collect();
+    let mut replies = sqlx::query_as::<_, Comment>(&format!(
+        r#"
+        {COMMENT_SELECT}
+        WHERE c.root_id IN (SELECT value FROM json_each(?2))
+          AND {COMMENT_LISTED}
+        ORDER BY c.created_at, c.id
+        "#
+    ))
+    .bind(admin)
+    .bind(serde_json::json!(root_ids).to_string())
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut comments = Vec::with_capacity(roots.len() + replies.len());
+    for root in roots {
+        let root_id = root.id;
+        comments.push(root);
+        let (thread, rest): (Vec<_>, Vec<_>) = replies
+            .into_iter()
+            .partition(|r| r.root_id == Some(root_id));
+        comments.extend(thread);
+        replies = rest;
+    }
+
+    Ok(Json(CommentPage {
+        comments,
+        total_threads,
+        page,
+        per_page,
+    }))
+}
+
+/// Tell the parent comment's author and the entry's owner about a new comment.
+/// Failures are logged, never surfaced to the commenter.
+async fn notify_comment(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    parent_id: Option<i64>,
+    body: &str,
+) {
+    let owner_column = match kind {
+        ArtifactKind::Dataset => Some("uploaded_by_user_id"),
+        ArtifactKind::Model => Some("created_by_user_id"),
+        ArtifactKind::UseCase | ArtifactKind::Article => None,
+    };
+    let result: Result<(), AppError> = async {
+        let (author,): (String,) = sqlx::query_as("SELECT username FROM users WHERE id = ?1")
+            .bind(CURRENT_USER_ID)
+            .fetch_one(&state.db)
+            .await?;
+        let excerpt: String = body.chars().take(COMMENT_EXCERPT_LEN).collect();
+        let mut notified = vec![CURRENT_USER_ID];
+
+        if let Some(parent_id) = parent_id {
+            let (parent_author,): (i64,) =
+                sqlx::query_as("SELECT user_id FROM comments WHERE id = ?1")
+                    .bind(parent_id)
+                    .fetch_one(&state.db)
+                    .await?;
+            if !notified.contains(&parent_author) {
+                let title = format!("{author} replied to your comment");
+                notifications::notify(
+                    &state.db,
+                    &state.notifier,
+                    parent_author,
+                    "comment_reply",
+                    &title,
+                    Some(&excerpt),
+                    Some((kind, id)),
+                )
+                .await?;
+                notified.push(parent_author);
+            }
+        }
+
+        if let Some(column) = owner_column {
+            let owner: Option<(Option<i64>,)> = sqlx::query_as(&format!(
+                "SELECT {column} FROM {} WHERE id = ?1",
+                kind.table()
+            ))
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+            if let Some((Some(owner),)) = owner {
+                if !notified.contains(&owner) {
+                    let title = format!("{author} commented on your {}", kind.as_str());
+                    notifications::notify(
+                        &state.db,
+                        &state.notifier,
+                        owner,
+                        "comment",
+                        &title,
+                        Some(&excerpt),
+                        Some((kind, id)),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!("failed to send comment notifications: {e}");
+    }
+}
+
+async fn create_comment(
+    state: &AppState,
+    kind: ArtifactKind,
+    id: i64,
+    payload: CreateComment,
+) -> Result<Json<Comment>, AppError> {
+    let body = validate_comment_body(&payload.body)?;
+
+    let exists: Option<(i64,)> = sqlx::query_as(&format!(
+        "SELECT id FROM {} WHERE id = ?1 AND deleted_at IS NULL",
+        kind.table()
+    ))
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let root_id = match payload.parent_id {
+        None => None,
+        Some(parent_id) => {
+            let (root_id,): (i64,) = sqlx::query_as(
+                r#"
+                SELECT COALESCE(root_id, id) FROM comments
+                WHERE id = ?1 AND artifact_kind = ?2 AND artifact_id = ?3 AND deleted_at IS NULL
+                "#,
+            )
+            .bind(parent_id)
+            .bind(kind.as_str())
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError("parent_id is not a comment on this item".to_string())
+            })?;
+            if thread_locked(&state.db, root_id).await? {
+                return Err(AppError::ValidationError(
+                    "This thread is locked".to_string(),
+                ));
+            }
+            Some(root_id)
+        }
+    };
+
+    let (comment_id,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO comments (artifact_kind, artifact_id, user_id, parent_id, root_id, body)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        RETURNING id
+        "#,
+    )
+    .bind(kind.as_str())
+    .bind(id)
+    .bind(CURRENT_USER_ID)
+    .bind(payload.parent_id)
+    .bind(root_id)
+    .bind(body)
+    .fetch_one(&state.db)
+    .await?;
+    notify_comment(state, kind, id, payload.parent_id, body).await;
+    Ok(Json(fetch_comment(&state.db, comment_id).await?))
+}
+
+pub async fn get_dataset_comments(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<CommentQuery>,
+) -> Result<Json<CommentPage>, AppError> {
+    list_comments(&state, ArtifactKind::Dataset, id, params).await
+}
+
+pub async fn create_dataset_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateComment>,
+) -> Result<Json<Comment>, AppError> {
+    create_comment(&state, ArtifactKind::Dataset, id, payload).await
+}
+
+pub async fn get_model_comments(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<CommentQuery>,
+) -> Result<Json<CommentPage>, AppError> {
+    list_comments(&state, ArtifactKind::Model, id, params).await
+}
+
+pub async fn create_model_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateComment>,
+) -> Result<Json<Comment>, AppError> {
+    create_comment(&state, ArtifactKind::Model, id, payload).await
+}
+
+pub async fn get_usecase_comments(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<CommentQuery>,
+) -> Result<Json<CommentPage>, AppError> {
+    list_comments(&state, ArtifactKind::UseCase, id, params).await
+}
+
+pub async fn create_usecase_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateComment>,
+) -> Result<Json<Comment>, AppError> {
+    create_comment(&state, ArtifactKind::UseCase, id, payload).await
+}
+
+pub async fn get_article_comments(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<CommentQuery>,
+) -> Result<Json<CommentPage>, AppError> {
+    list_comments(&state, ArtifactKind::Article, id, params).await
+}
+
+pub async fn create_article_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateComment>,
+) -> Result<Json<Comment>, AppError> {
+    create_comment(&state, ArtifactKind::Article, id, payload).await
+}
+
+/// Authors may edit their own comments until the thread is locked.
+pub async fn update_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateComment>,
+) -> Result<Json<Comment>, AppError> {
+    let body = validate_comment_body(&payload.body)?;
+    let (author_id,): (i64,) =
+        sqlx::query_as("SELECT user_id FROM comments WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    if author_id != CURRENT_USER_ID {
+        return Err(AppError::Forbidden);
+    }
+    if thread_locked(&state.db, id).await? {
+        return Err(AppError::ValidationError(
+            "This thread is locked".to_string(),
+        ));
+    }
+
+    sqlx::query("UPDATE comments SET body = ?1, edited_at = datetime('now') WHERE id = ?2")
+        .bind(body)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    Ok(Json(fetch_comment(&state.db, id).await?))
+}
+
+/// Soft delete by the author or an admin; replies stay, under a placeholder.
+pub async fn delete_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    let (author_id,): (i64,) =
+        sqlx::query_as("SELECT user_id FROM comments WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    if author_id != CURRENT_USER_ID {
+        require_admin(&state.db).await?;
+    }
+
+    sqlx::query("UPDATE comments SET body = '', deleted_at = datetime('now') WHERE id = ?1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Admin: hide/unhide a comment, lock/unlock the thread it starts.
+pub async fn moderate_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<ModerateComment>,
+) -> Result<Json<Comment>, AppError> {
+    require_admin(&state.db).await?;
+    let (parent_id,): (Option<i64>,) =
+        sqlx::query_as("SELECT parent_id FROM comments WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    if payload.locked.is_some() && parent_id.is_some() {
+        return Err(AppError::ValidationError(
+            "Only top-level comments can be locked".to_string(),
+        ));
+    }
+
+    // Re-hiding or re-locking keeps the original timestamp and moderator.
+    sqlx::query(
+        r#"
+        UPDATE comments SET
+            hidden_at         = CASE ?1 WHEN 1 THEN COALESCE(hidden_at, datetime('now'))
+                                        WHEN 0 THEN NULL ELSE hidden_at END,
+            hidden_by_user_id = CASE ?1 WHEN 1 THEN COALESCE(hidden_by_user_id, ?3)
+                                        WHEN 0 THEN NULL ELSE hidden_by_user_id END,
+            locked_at         = CASE ?2 WHEN 1 THEN COALESCE(locked_at, datetime('now'))
+                                        WHEN 0 THEN NULL ELSE locked_at END,
+            locked_by_user_id = CASE ?2 WHEN 1 THEN COALESCE(locked_by_user_id, ?3)
+                                        WHEN 0 THEN NULL ELSE locked_by_user_id END
+        WHERE id = ?4
+        "#,
+    )
+    .bind(payload.hidden)
+    .bind(payload.locked)
+    .bind(CURRENT_USER_ID)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+    Ok(Json(fetch_comment(&state.db, id).await?))
+}
+
+// =============================================================================
+// SEARCH SUGGESTIONS  (prefix completions over search_suggest)
+// =============================================================================
+
+const SUGGEST_DEFAULT_LIMIT: i64 = 10;
+const SUGGEST_MAX_LIMIT: i64 = 25;
+const SUGGEST_PER_KIND: i64 = 5;
+
+pub async fn get_search_suggestions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SuggestQuery>,
+) -> Result<Json<Vec<Suggestion>>, AppError> {
+    let Some(q) = params.q.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return Ok(Json(Vec::new()));
+    };
+    let limit = params
+        .limit
+        .unwrap_or(SUGGEST_DEFAULT_LIMIT)
+        .clamp(1, SUGGEST_MAX_LIMIT);
+
+    // Lookups (sectors, tags, orgs) come first since picking one narrows the
+    // catalog; artifacts within a kind are ordered by popularity.
+    let rows = sqlx::query_as::<_, Suggestion>(
+        r#"
+        WITH hits AS (
+            SELECT s.kind, s.item_id AS id, s.label, s.slug, s.rank AS score,
+                   COALESCE(d.views_count + d.downloads_count,
+                            m.views_count + m.downloads_count, 0) AS popularity
+            FROM search_suggest s
+            LEFT JOIN datasets d ON s.kind = 'dataset' AND d.id = s.item_id
+            LEFT JOIN models m   ON s.kind = 'model'   AND m.id = s.item_id
+            WHERE search_suggest MATCH ?1
+        ),
+        ranked AS (
+            SELECT kind, id, label, slug,
+                   ROW_NUMBER() OVER (PARTITION BY kind ORDER BY popularity DESC, score) AS rn
+            FROM hits
+        )
+        SELECT kind, id, label, slug
+        FROM ranked
+        WHERE rn <= ?2
+        ORDER BY CASE kind
+                     WHEN 'sector'       THEN 0
+                     WHEN 'tag'          THEN 1
+                     WHEN 'organization' THEN 2
+                     WHEN 'dataset'      THEN 3
+                     WHEN 'model'        THEN 4
+                     ELSE 5
+                 END,
+                 rn
+        LIMIT ?3
+        "#,
+    )
+    .bind(build_fts_prefix_query(q))
+    .bind(SUGGEST_PER_KIND)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+// =============================================================================
+// SEARCH SYNONYMS  (admin-managed, applied both ways during query expansion)
+// =============================================================================
+
+const MAX_SYNONYM_LEN: usize = 100;
+
+pub async fn get_search_synonyms(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<SearchSynonym>>, AppError> {
+    require_admin(&state.db).await?;
+    let rows = sqlx::query_as::<_, SearchSynonym>(
+        "SELECT id, term, synonym, created_at FROM search_synonyms ORDER BY term, synonym",
+    )
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+pub async fn create_search_synonym(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateSearchSynonym>,
+) -> Result<Json<SearchSynonym>, AppError> {
+    require_admin(&state.db).await?;
+
+    // Stored normalized so lookups can compare against lowercased query tokens.
+    let normalize = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    let term = normalize(&payload.term);
+    let synonym = normalize(&payload.synonym);
+    if term.is_empty() || synonym.is_empty() {
+        return Err(AppError::ValidationError(
+            "Term and synonym cannot be empty".to_string(),
+        ));
+    }
+    if term.len() > MAX_SYNONYM_LEN || synonym.len() > MAX_SYNONYM_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Term and synonym must be at most {MAX_SYNONYM_LEN} characters"
+        )));
+    }
+    if term == synonym {
+        return Err(AppError::ValidationError(
+            "Term and synonym must differ".to_string(),
+        ));
+    }
+
+    let existing: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM search_synonyms
+         WHERE (term = ?1 AND synonym = ?2) OR (term = ?2 AND synonym = ?1)",
+    )
+    .bind(&term)
+    .bind(&synonym)
+    .fetch_one(&state.db)
+    .await?;
+    if existing.0 > 0 {
+        return Err(AppError::ValidationError(
+            "Synonym pair already exists".to_string(),
+        ));
+    }
+
+    let row = sqlx::query_as::<_, SearchSynonym>(
+        r#"
+        INSERT INTO search_synonyms (term, synonym) VALUES (?1, ?2)
+        RETURNING id, term, synonym, created_at
+        "#,
+    )
+    .bind(&term)
+    .bind(&synonym)
+    .fetch_one(&state.db)
+    .await?;
+    Ok(Json(row))
+}
+
+pub async fn delete_search_synonym(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    require_admin(&state.db).await?;
+    let res = sqlx::query("DELETE FROM search_synonyms WHERE id = ?1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// =============================================================================
+// SEARCH ANALYTICS  (click logging; admin reports over search_events)
+// =============================================================================
+
+const CLICK_KINDS: [&str; 6] = [
+    "dataset", "model", "usecase", "article", "toolkit", "tutorial",
+];
+const REPORT_DEFAULT_DAYS: i64 = 30;
+const REPORT_MAX_DAYS: i64 = 365;
+const REPORT_DEFAULT_LIMIT: i64 = 50;
+const REPORT_MAX_LIMIT: i64 = 500;
+
+pub async fn record_search_click(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateSearchClick>,
+) -> Result<StatusCode, AppError> {
+    if !CLICK_KINDS.contains(&payload.kind.as_str()) {
+        return Err(AppError::ValidationError(format!(
+            "Unknown artifact type '{}'",
+            payload.kind
+        )));
+    }
+    if payload.position.is_some_and(|p| p < 1) {
+        return Err(AppError::ValidationError(
+            "Position must be 1 or greater".to_string(),
+        ));
+    }
+
+    let res = sqlx::query(
+        r#"
+        INSERT INTO search_clicks (search_event_id, artifact_kind, artifact_id, position)
+        SELECT id, ?2, ?3, ?4 FROM search_events WHERE id = ?1
+        "#,
+    )
+    .bind(payload.search_id)
+    .bind(&payload.kind)
+    .bind(payload.id)
+    .bind(payload.position)
+    .execute(&state.db)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// (`datetime('now', ?)` modifier for the report window, row limit).
+fn report_window(params: &SearchReportQuery) -> (String, i64) {
+    let days = params
+        .days
+        .unwrap_or(REPORT_DEFAULT_DAYS)
+        .clamp(1, REPORT_MAX_DAYS);
+    let limit = params
+        .limit
+        .unwrap_or(REPORT_DEFAULT_LIMIT)
+        .clamp(1, REPORT_MAX_LIMIT);
+    (format!("-{days} days"), limit)
+}
+
+pub async fn get_top_search_queries(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchReportQuery>,
+) -> Result<Json<Vec<TopSearchQuery>>, AppError> {
+    require_admin(&state.db).await?;
+    let (since, limit) = report_window(&params);
+
+    let rows = sqlx::query_as::<_, TopSearchQuery>(
+        r#"
+        WITH e AS (
+            SELECT se.normalized_query, se.result_count,
+                   EXISTS (SELECT 1 FROM search_clicks c WHERE c.search_event_id = se.id) AS clicked
+            FROM search_events se
+            WHERE se.created_at >= datetime('now', ?1)
+              AND (?2 IS NULL OR se.surface = ?2)
+        )
+        SELECT normalized_query AS query,
+               COUNT(*) AS searches,
+               SUM(result_count = 0) AS zero_result_searches,
+               AVG(result_count) AS avg_results,
+               SUM(clicked) AS clicked_searches,
+               CAST(SUM(clicked) AS REAL) / COUNT(*) AS click_through_rate
+        FROM e
+        GROUP BY normalized_query
+        ORDER BY searches DESC, query
+        LIMIT ?3
+        "#,
+    )
+    .bind(since)
+    .bind(params.surface.as_deref())
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+/// Queries that found nothing — including ones only rescued by "did you mean",
+/// since those still point at vocabulary the catalog is missing.
+pub async fn get_zero_result_queries(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchReportQuery>,
+) -> Result<Json<Vec<ZeroResultQuery>>, AppError> {
+    require_admin(&state.db).await?;
+    let (since, limit) = report_window(&params);
+
+    let rows = sqlx::query_as::<_, ZeroResultQuery>(
+        r#"
+        SELECT normalized_query AS query,
+               COUNT(*) AS searches,
+               GROUP_CONCAT(DISTINCT surface) AS surfaces,
+               MAX(created_at) AS last_searched_at
+        FROM search_events
+        WHERE created_at >= datetime('now', ?1)
+          AND (?2 IS NULL OR surface = ?2)
+          AND (result_count = 0 OR corrected_query IS NOT NULL)
+        GROUP BY normalized_query
+        ORDER BY searches DESC, last_searched_at DESC
+        LIMIT ?3
+        "#,
+    )
+    .bind(since)
+    .bind(params.surface.as_deref())
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+/// Click-through per surface, plus an `all` row across every surface.
+pub async fn get_search_click_through(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchReportQuery>,
+) -> Result<Json<Vec<ClickThroughStats>>, AppError> {
+    require_admin(&state.db).await?;
+    let (since, _) = report_window(&params);
+
+    let rows = sqlx::query_as::<_, ClickThroughStats>(
+        r#"
+        WITH e AS (
+            SELECT se.surface, se.latency_ms,
+                   EXISTS (SELECT 1 FROM search_clicks c WHERE c.search_event_id = se.id) AS clicked,
+                   (SELECT MIN(c.position) FROM search_clicks c WHERE c.search_event_id = se.id) AS first_position
+            FROM search_events se
+            WHERE se.created_at >= datetime('now', ?1)
+              AND (?2 IS NULL OR se.surface = ?2)
+        )
+        SELECT surface,
+               COUNT(*) AS searches,
+               SUM(clicked) AS clicked_searches,
+               CAST(SUM(clicked) AS REAL) / COUNT(*) AS click_through_rate,
+               AVG(first_position) AS avg_click_position,
+               AVG(latency_ms) AS avg_latency_ms
+        FROM e
+        GROUP BY surface
+        UNION ALL
+        SELECT 'all',
+               COUNT(*),
+               COALESCE(SUM(clicked), 0),
+               COALESCE(CAST(SUM(clicked) AS REAL) / COUNT(*), 0.0),
+               AVG(first_position),
+               COALESCE(AVG(latency_ms), 0.0)
+        FROM e
+        ORDER BY searches DESC
+        "#,
+    )
+    .bind(since)
+    .bind(params.surface.as_deref())
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+// =============================================================================
+// PLATFORM ANALYTICS  (admin reports over the event tables; see analytics.rs)
+// =============================================================================
+
+pub async fn get_analytics_summary(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let summary = analytics::summary(&state.db, &range).await?;
+    Ok(match params.format.unwrap_or_default() {
+        AnalyticsFormat::Json => Json(summary).into_response(),
+        AnalyticsFormat::Csv => analytics::respond(vec![summary], params.format, "summary", &range),
+    })
+}
+
+pub async fn get_analytics_active_users(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let rows = analytics::active_users(&state.db, &range).await?;
+    Ok(analytics::respond(
+        rows,
+        params.format,
+        "active-users",
+        &range,
+    ))
+}
+
+pub async fn get_analytics_registrations(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let rows = analytics::registrations(&state.db, &range).await?;
+    Ok(analytics::respond(
+        rows,
+        params.format,
+        "registrations",
+        &range,
+    ))
+}
+
+pub async fn get_analytics_top_artifacts(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let metric = params.metric.unwrap_or_default();
+    let rows = analytics::top_artifacts(
+        &state.db,
+        &range,
+        metric,
+        params.kind.as_deref(),
+        analytics::limit(&params),
+    )
+    .await?;
+    let report = match metric {
+        AnalyticsMetric::Views => "top-viewed",
+        AnalyticsMetric::Downloads => "top-downloaded",
+    };
+    Ok(analytics::respond(rows, params.format, report, &range))
+}
+
+pub async fn get_analytics_downloads_by_sector(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let rows = analytics::downloads_by(
+        &state.db,
+        &range,
+        analytics::Breakdown::Sector,
+        analytics::limit(&params),
+    )
+    .await?;
+    Ok(analytics::respond(
+        rows,
+        params.format,
+        "downloads-by-sector",
+        &range,
+    ))
+}
+
+pub async fn get_analytics_downloads_by_organization(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let rows = analytics::downloads_by(
+        &state.db,
+        &range,
+        analytics::Breakdown::Organization,
+        analytics::limit(&params),
+    )
+    .await?;
+    Ok(analytics::respond(
+        rows,
+        params.format,
+        "downloads-by-organization",
+        &range,
+    ))
+}
+
+pub async fn get_analytics_chat(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state.db).await?;
+    let range = analytics::Range::from_query(&params)?;
+    let rows = analytics::chat_usage(&state.db, &range).await?;
+    Ok(analytics::respond(rows, params.format, "chat", &range))
+}
+
+// =============================================================================
+// WEBHOOKS  (admin subscriptions to catalog events; dispatch lives in webhooks.rs)
+// =============================================================================
+
+const WEBHOOK_SELECT: &str =
+    "SELECT id, url, description, events, active, created_at, updated_at FROM webhooks";
+const DELIVERIES_DEFAULT_LIMIT: i64 = 50;
+const DELIVERIES_MAX_LIMIT: i64 = 200;
+
+fn validate_webhook_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim();
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(url.to_string()),
+        _ => Err(AppError::ValidationError(
+            "url must be an absolute http(s) URL".to_string(),
+        )),
+    }
+}
+
+/// Checks the names against `webhooks::EVENTS` and returns them as a JSON array.
+fn validate_webhook_events(events: &[String]) -> Result<String, AppError> {
+    if events.is_empty() {
+        return Err(AppError::ValidationError(
+            "Subscribe to at least one event".to_string(),
+        ));
+    }
+    if let Some(unknown) = events
+        .iter()
+        .find(|e| !webhooks::EVENTS.contains(&e.as_str()))
+    {
+        return Err(AppError::ValidationError(format!(
+            "Unknown event '{unknown}'; expected one of {}",
+            webhooks::EVENTS.join(", ")
+        )));
+    }
+    let mut events = events.to_vec();
+    events.sort();
+    events.dedup();
+    Ok(serde_json::to_string(&events)?)
+}
+
+fn with_events(mut webhook: Webhook) -> Result<Webhook, AppError> {
+    webhook.events = serde_json::from_str(&webhook.events_json)?;
+    Ok(webhook)
+}
+
+async fn find_webhook(db: &SqlitePool, id: i64) -> Result<Webhook, AppError> {
+    let webhook = sqlx::query_as::<_, Webhook>(&format!("{WEBHOOK_SELECT} WHERE id = ?1"))
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    with_events(webhook)
+}
+
+pub async fn get_webhooks(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Webhook>>, AppError> {
+    require_admin(&state.db).await?;
+    let rows = sqlx::query_as::<_, Webhook>(&format!("{WEBHOOK_SELECT} ORDER BY id"))
+        .fetch_all(&state.db)
+        .await?;
+    let rows = rows
+        .into_iter()
+        .map(with_events)
+        .collect::<Result<_, _>>()?;
+    Ok(Json(rows))
+}
+
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateWebhook>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    require_admin(&state.db).await?;
+    let url = validate_webhook_url(&payload.url)?;
+    let events = validate_webhook_events(&payload.events)?;
+    let secret = webhooks::new_secret();
+
+    let (id,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO webhooks (url, description, events, secret, active, created_by_user_id)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        RETURNING id
+        "#,
+    )
+    .bind(&url)
+    .bind(&payload.description)
+    .bind(&events)
+    .bind(&secret)
+    .bind(payload.active.unwrap_or(true))
+    .bind(CURRENT_USER_ID)
+    .fetch_one(&state.db)
+    .await?;
+
+    // The secret is shown once; receivers need it to verify X-Aikosh-Signature.
+    let mut value = serde_json::to_value(find_webhook(&state.db, id).await?)?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("secret".to_string(), serde_json::json!(secret));
+    }
+    Ok(Json(value))
+}
+
+pub async fn update_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateWebhook>,
+) -> Result<Json<Webhook>, AppError> {
+    require_admin(&state.db).await?;
+    let url = payload
+        .url
+        .as_deref()
+        .map(validate_webhook_url)
+        .transpose()?;
+    let events = payload
+        .events
+        .as_deref()
+        .map(validate_webhook_events)
+        .transpose()?;
+
+    let res = sqlx::query(
+        r#"
+        UPDATE webhooks SET
+            url         = COALESCE(?1, url),
+            events      = COALESCE(?2, events),
+            description = COALESCE(?3, description),
+            active      = COALESCE(?4, active),
+            updated_at  = datetime('now')
+        WHERE id = ?5
+        "#,
+    )
+    .bind(url)
+    .bind(events)
+    .bind(&payload.description)
+    .bind(payload.active)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(find_webhook(&state.db, id).await?))
+}
+
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    require_admin(&state.db).await?;
+    let res = sqlx::query("DELETE FROM webhooks WHERE id = ?1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+const DELIVERY_COLUMNS: &str = r#"
+    id, webhook_id, event, payload, status, attempts, next_attempt_at, last_attempt_at,
+    response_status, response_body, error, redelivery_of_id, created_at
+"#;
+
+fn with_payload(mut delivery: WebhookDelivery) -> Result<WebhookDelivery, AppError> {
+    delivery.payload = serde_json::from_str(&delivery.payload_json)?;
+    Ok(delivery)
+}
+
+/// Queues a delivery for the dispatcher's next tick.
+async fn queue_delivery(
+    db: &SqlitePool,
+    webhook_id: i64,
+    event: &str,
+    payload: &str,
+    redelivery_of_id: Option<i64>,
+) -> Result<WebhookDelivery, AppError> {
+    let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+        r#"
+        INSERT INTO webhook_deliveries (webhook_id, event, payload, redelivery_of_id)
+        VALUES (?1, ?2, ?3, ?4)
+        RETURNING {DELIVERY_COLUMNS}
+        "#
+    ))
+    .bind(webhook_id)
+    .bind(event)
+    .bind(payload)
+    .bind(redelivery_of_id)
+    .fetch_one(db)
+    .await?;
+    with_payload(delivery)
+}
+
+pub async fn get_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(params): Query<DeliveryQuery>,
+) -> Result<Json<Vec<WebhookDelivery>>, AppError> {
+    require_admin(&state.db).await?;
+    find_webhook(&state.db, id).await?;
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "pending" | "succeeded" | "failed") {
+            return Err(AppError::ValidationError(
+                "status must be pending, succeeded or failed".to_string(),
+            ));
+        }
+    }
+    let limit = params
+        .limit
+        .unwrap_or(DELIVERIES_DEFAULT_LIMIT)
+        .clamp(1, DELIVERIES_MAX_LIMIT);
+    let rows = sqlx::query_as::<_, WebhookDelivery>(&format!(
+        r#"
+        SELECT {DELIVERY_COLUMNS}
+        FROM webhook_deliveries
+        WHERE webhook_id = ?1
+          AND (?2 IS NULL OR status = ?2)
+          AND (?3 IS NULL OR id < ?3)
+        ORDER BY id DESC
+        LIMIT ?4
+        "#
+    ))
+    .bind(id)
+    .bind(&params.status)
+    .bind(params.before)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+    let rows = rows
+        .into_iter()
+        .map(with_payload)
+        .collect::<Result<_, _>>()?;
+    Ok(Json(rows))
+}
+
+pub async fn ping_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    require_admin(&state.db).await?;
+    let webhook = find_webhook(&state.db, id).await?;
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let payload = webhooks::payload(
+        webhooks::PING_EVENT,
+        &now,
+        serde_json::json!({ "webhook_id": webhook.id, "events": webhook.events }),
+    );
+    let delivery =
+        queue_delivery(&state.db, webhook.id, webhooks::PING_EVENT, &payload, None).await?;
+    Ok(Json(delivery))
+}
+
+/// Sends the original payload again as a new delivery; the old one keeps its log.
+pub async fn redeliver_webhook_delivery(
+    State(state): State<Arc<AppState>>,
+    Path((id, delivery_id)): Path<(i64, i64)>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    require_admin(&state.db).await?;
+    let original = sqlx::query_as::<_, WebhookDelivery>(&format!(
+        "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE id = ?1 AND webhook_id = ?2"
+    ))
+    .bind(delivery_id)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let delivery = queue_delivery(
+        &state.db,
+        id,
+        &original.event,
+        &original.payload_json,
+        Some(original.id),
+    )
+    .await?;
+    Ok(Json(delivery))
+}
+
+// =============================================================================
+// SECTORS / ORGANIZATIONS  (filter chip endpoints)
+// =============================================================================
+
+pub async fn get_sectors(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Sector>>, AppError> {
+    let rows = sqlx::query_as::<_, Sector>(
+        "SELECT id, name, slug, display_order FROM sectors ORDER BY display_order, name",
+    )
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+pub async fn get_organizations(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Organization>>, AppError> {
+    let rows = sqlx::query_as::<_, Organization>(
+        r#"
+        SELECT id, name, slug
+        FROM organizations
+        WHERE deleted_at IS NULL
+        ORDER BY name
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+// =============================================================================
+// USER  (hardcoded user_id=1 until JWT in Step 4)
+// =============================================================================
+
+pub async fn get_user_profile(State(state): State<Arc<AppState>>) -> Result<Json<User>, AppError> {
+    let row = sqlx::query_as::<_, User>(
+        r#"
+        SELECT id, email, username, full_name, bio, employee_id, profile_picture_url, role
+        FROM users
+        WHERE id = ?1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(Json(row))
+}
+
+pub async fn update_user_profile(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<UpdateUserProfile>,
+) -> Result<Json<User>, AppError> {
+    if let Some(name) = payload.full_name.as_deref() {
+        if name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Full name cannot be empty".to_string(),
+            ));
+        }
+    }
+
+    // COALESCE pattern: if a field is NULL in the payload, keep the existing value.
+    sqlx::query(
+        r#"
+        UPDATE users SET
+            full_name           = COALESCE(?1, full_name),
+            bio                 = COALESCE(?2, bio),
+            employee_id         = COALESCE(?3, employee_id),
+            profile_picture_url = COALESCE(?4, profile_picture_url),
+            updated_at          = datetime('now')
+        WHERE id = ?5 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(payload.full_name)
+    .bind(payload.bio)
+    .bind(payload.employee_id)
+    .bind(payload.profile_picture_url)
+    .bind(CURRENT_USER_ID)
+    .execute(&state.db)
+    .await?;
+
+    let row = sqlx::query_as::<_, User>(
+        r#"
+        SELECT id, email, username, full_name, bio, employee_id, profile_picture_url, role
+        FROM users
+        WHERE id = ?1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(row))
+}
+
+// =============================================================================
+// NOTIFICATIONS  (list / mark read / unread count; live SSE stream)
+// =============================================================================
+
+const NOTIFICATIONS_DEFAULT_LIMIT: i64 = 20;
+const NOTIFICATIONS_MAX_LIMIT: i64 = 100;
+/// How often an idle stream re-checks the table, in case a writer didn't wake it.
+const NOTIFICATION_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+async fn unread_count(db: &SqlitePool) -> Result<UnreadCount, AppError> {
+    let (unread,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM notifications WHERE user_id = ?1 AND read_at IS NULL")
+            .bind(CURRENT_USER_ID)
+            .fetch_one(db)
+            .await?;
+    Ok(UnreadCount { unread })
+}
+
+/// Newest first; page back with `before` = the last id seen.
+pub async fn get_notifications(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<NotificationQuery>,
+) -> Result<Json<Vec<Notification>>, AppError> {
+    let limit = params
+        .limit
+        .unwrap_or(NOTIFICATIONS_DEFAULT_LIMIT)
+        .clamp(1, NOTIFICATIONS_MAX_LIMIT);
+    let rows = sqlx::query_as::<_, Notification>(
+        r#"
+        SELECT id, kind, title, body, artifact_kind, artifact_id, read_at, created_at
+        FROM notifications
+        WHERE user_id = ?1
+          AND (?2 = 0 OR read_at IS NULL)
+          AND (?3 IS NULL OR id < ?3)
+        ORDER BY id DESC
+        LIMIT ?4
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(params.unread.unwrap_or(false))
+    .bind(params.before)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+pub async fn get_unread_notification_count(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<UnreadCount>, AppError> {
+    Ok(Json(unread_count(&state.db).await?))
+}
+
+pub async fn mark_notification_read(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<UnreadCount>, AppError> {
+    let res = sqlx::query(
+        r#"
+        UPDATE notifications SET read_at = COALESCE(read_at, datetime('now'))
+        WHERE id = ?1 AND user_id = ?2
+        "#,
+    )
+    .bind(id)
+    .bind(CURRENT_USER_ID)
+    .execute(&state.db)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(unread_count(&state.db).await?))
+}
+
+pub async fn mark_all_notifications_read(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<UnreadCount>, AppError> {
+    sqlx::query(
+        "UPDATE notifications SET read_at = datetime('now') WHERE user_id = ?1 AND read_at IS NULL",
+    )
+    .bind(CURRENT_USER_ID)
+    .execute(&state.db)
+    .await?;
+    Ok(Json(unread_count(&state.db).await?))
+}
+
+/// Live feed of notifications created after the stream opened, as
+/// `notification` events, each followed by an `unread` event with the new
+/// count. History comes from `get_notifications`.
+pub async fn notification_stream(
+    State(state): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let mut wake = state.notifier.subscribe();
+
+    let stream = async_stream::stream! {
+        let mut last_id: i64 = match sqlx::query_scalar(
+            "SELECT COALESCE(MAX(id), 0) FROM notifications WHERE user_id = ?1",
+        )
+        .bind(CURRENT_USER_ID)
+        .fetch_one(&state.db)
+        .await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                tracing::error!("Failed to open notification stream: {}", e);
+                return;
+            }
+        };
+
+        loop {
+            tokio::select! {
+                _ = notifications::woken(&mut wake, CURRENT_USER_ID) => {}
+                _ = sleep(NOTIFICATION_POLL_INTERVAL) => {}
+            }
+
+            let rows = sqlx::query_as::<_, Notification>(
+                r#"
+                SELECT id, kind, title, body, artifact_kind, artifact_id, read_at, created_at
+                FROM notifications
+                WHERE user_id = ?1 AND id > ?2
+                ORDER BY id
+                "#,
+            )
+            .bind(CURRENT_USER_ID)
+            .bind(last_id)
+            .fetch_all(&state.db)
+            .await;
+            let rows = match rows {
+                Ok(rows) => rows,
+                Err(e) => {
+                    tracing::warn!("Failed to fetch new notifications: {}", e);
+                    continue;
+                }
+            };
+            if rows.is_empty() {
+                continue;
+            }
+
+            for n in &rows {
+                last_id = n.id;
+                if let Ok(event) = Event::default().event("notification").json_data(n) {
+                    yield Ok(event);
+                }
+            }
+            if let Ok(count) = unread_count(&state.db).await {
+                if let Ok(event) = Event::default().event("unread").json_data(&count) {
+                    yield Ok(event);
+                }
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(
+        axum::response::sse::KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive"),
+    )
+}
+
+// =============================================================================
+// EMAIL DIGESTS  (opt-in daily/weekly summaries; sending lives in digests.rs)
+// =============================================================================
+
+async fn digest_preferences(db: &SqlitePool) -> Result<DigestPreferences, AppError> {
+    let prefs = sqlx::query_as::<_, DigestPreferences>(
+        r#"
+        SELECT COALESCE(p.frequency, 'off') AS frequency, u.email, p.last_sent_at,
+               CASE p.frequency
+                   WHEN 'daily'  THEN datetime(COALESCE(p.last_sent_at, p.opted_in_at), '+1 day')
+                   WHEN 'weekly' THEN datetime(COALESCE(p.last_sent_at, p.opted_in_at), '+7 days')
+               END AS next_digest_at
+        FROM users u
+        LEFT JOIN digest_preferences p ON p.user_id = u.id
+        WHERE u.id = ?1
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .fetch_one(db)
+    .await?;
+    Ok(prefs)
+}
+
+pub async fn get_digest_preferences(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<DigestPreferences>, AppError> {
+    Ok(Json(digest_preferences(&state.db).await?))
+}
+
+/// Opting in starts a fresh window; turning digests off forgets the last one.
+pub async fn update_digest_preferences(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<UpdateDigestPreferences>,
+) -> Result<Json<DigestPreferences>, AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO digest_preferences (user_id, frequency, opted_in_at)
+        VALUES (?1, ?2, CASE WHEN ?2 = 'off' THEN NULL ELSE datetime('now') END)
+        ON CONFLICT(user_id) DO UPDATE SET
+            opted_in_at  = CASE WHEN excluded.frequency = 'off' THEN NULL
+                                WHEN frequency = 'off' THEN datetime('now')
+                                ELSE opted_in_at END,
+            last_sent_at = CASE WHEN excluded.frequency = 'off' THEN NULL
+                                ELSE last_sent_at END,
+            frequency    = excluded.frequency,
+            updated_at   = datetime('now')
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .bind(payload.frequency.as_str())
+    .execute(&state.db)
+    .await?;
+    Ok(Json(digest_preferences(&state.db).await?))
+}
+
+/// Renders the digest the user would get for the window open so far, without sending it.
+pub async fn preview_digest(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DigestPreviewQuery>,
+) -> Result<Json<DigestPreview>, AppError> {
+    let prefs = digest_preferences(&state.db).await?;
+    let frequency = params.frequency.unwrap_or(match prefs.frequency.as_str() {
+        "weekly" => DigestFrequency::Weekly,
+        _ => DigestFrequency::Daily,
+    });
+    let (period_start, period_end): (String, String) =
+        sqlx::query_as("SELECT COALESCE(?1, datetime('now', ?2)), datetime('now')")
+            .bind(&prefs.last_sent_at)
+            .bind(digests::period(frequency))
+            .fetch_one(&state.db)
+            .await?;
+    let (full_name,): (String,) = sqlx::query_as("SELECT full_name FROM users WHERE id = ?1")
+        .bind(CURRENT_USER_ID)
+        .fetch_one(&state.db)
+        .await?;
+
+    let digest = digests::collect(
+        &state.db,
+        CURRENT_USER_ID,
+        frequency,
+        period_start,
+        period_end,
+    )
+    .await?;
+    let email = digests::render(
+        &digest,
+        &prefs.email,
+        &full_name,
+        &state.config.frontend_origin,
+    );
+    Ok(Json(DigestPreview {
+        item_count: digest.items.len(),
+        period_start: digest.period_start,
+        period_end: digest.period_end,
+        subject: email.subject,
+        text: email.text,
+        html: email.html,
+    }))
+}
+
+// =============================================================================
+// SAVED SEARCHES  (per-user ListQuery bookmarks; new matches become notifications)
+// =============================================================================
+
+const MAX_SAVED_SEARCH_NAME_LEN: usize = 100;
+
+pub async fn get_saved_searches(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Article>>, AppError> {
-    let rows = sqlx::query_as::<_, Article>(
+) -> Result<Json<Vec<SavedSearch>>, AppError> {
+    let rows = sqlx::query_as::<_, SavedSearch>(
         r#"
-        SELECT id, title, description, content, image_url, author, read_time,
-               category, disclaimer, published_at
-        FROM articles
-        WHERE deleted_at IS NULL
-        ORDER BY id
+        SELECT id, name, kind, search, sector, organization_id, last_checked_at, created_at
+        FROM saved_searches
+        WHERE user_id = ?1
+        ORDER BY created_at DESC, id DESC
         "#,
     )
+    .bind(CURRENT_USER_ID)
     .fetch_all(&state.db)
     .await?;
     Ok(Json(rows))
 }
 
-pub async fn get_article_by_id(
+pub async fn create_saved_search(
     State(state): State<Arc<AppState>>,
-    Path(id): Path<i64>,
-) -> Result<Json<Article>, AppError> {
-    let row = sqlx::query_as::<_, Article>(
+    Json(payload): Json<CreateSavedSearch>,
+) -> Result<Json<SavedSearch>, AppError> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_SAVED_SEARCH_NAME_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Name must be between 1 and {MAX_SAVED_SEARCH_NAME_LEN} characters"
+        )));
+    }
+    if SearchIndex::for_kind(payload.kind).is_none() {
+        return Err(AppError::ValidationError(
+            "Saved searches support datasets, models and usecases".to_string(),
+        ));
+    }
+    if payload.organization_id.is_some() && payload.kind != ArtifactKind::Dataset {
+        return Err(AppError::ValidationError(
+            "organization_id is only supported for datasets".to_string(),
+        ));
+    }
+    let search = payload
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    // Reject what the list endpoint would reject, so the checker never has to.
+    if let Some(search) = search {
+        let query = SearchQuery::parse(search)?;
+        if payload.kind != ArtifactKind::Dataset {
+            reject_column_filter(&query.filters())?;
+        }
+    }
+    let sector = payload
+        .sector
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let existing: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM saved_searches WHERE user_id = ?1 AND name = ?2")
+            .bind(CURRENT_USER_ID)
+            .bind(name)
+            .fetch_one(&state.db)
+            .await?;
+    if existing.0 > 0 {
+        return Err(AppError::ValidationError(
+            "A saved search with this name already exists".to_string(),
+        ));
+    }
+
+    let row = sqlx::query_as::<_, SavedSearch>(
         r#"
-        SELECT id, title, description, content, image_url, author, read_time,
-               category, disclaimer, published_at
-        FROM articles
-        WHERE id = ?1 AND deleted_at IS NULL
+        INSERT INTO saved_searches (user_id, name, kind, search, sector, organization_id)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        RETURNING id, name, kind, search, sector, organization_id, last_checked_at, created_at
         "#,
     )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or(AppError::NotFound)?;
-
+    .bind(CURRENT_USER_ID)
+    .bind(name)
+    .bind(payload.kind.as_str())
+    .bind(search)
+    .bind(sector)
+    .bind(payload.organization_id)
+    .fetch_one(&state.db)
+    .await?;
     Ok(Json(row))
 }
 
+pub async fn delete_saved_search(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    let res = sqlx::query("DELETE FROM saved_searches WHERE id = ?1 AND user_id = ?2")
+        .bind(id)
+        .bind(CURRENT_USER_ID)
+        .execute(&state.db)
+        .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // =============================================================================
-// TUTORIALS
+// COLLECTIONS  (user-curated, ordered lists; public ones are shared by slug)
 // =============================================================================
 
-pub async fn get_tutorials(
-    State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Tutorial>>, AppError> {
-    let rows = sqlx::query_as::<_, Tutorial>(
+/// (artifact kind, table) for everything a collection can hold.
+const COLLECTION_KINDS: [(&str, &str); 5] = [
+    ("dataset", "datasets"),
+    ("model", "models"),
+    ("usecase", "usecases"),
+    ("article", "articles"),
+    ("toolkit", "toolkit"),
+];
+const MAX_COLLECTION_NAME_LEN: usize = 100;
+const MAX_COLLECTION_NOTE_LEN: usize = 2000;
+
+const COLLECTION_SELECT: &str = r#"
+    SELECT c.id, c.name, c.slug, c.description, c.visibility, u.username AS owner,
+           c.forked_from_id,
+           (SELECT COUNT(*) FROM collection_items i WHERE i.collection_id = c.id) AS item_count,
+           c.created_at, c.updated_at
+    FROM collections c
+    JOIN users u ON u.id = c.user_id
+"#;
+
+fn validate_collection_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_COLLECTION_NAME_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Name must be between 1 and {MAX_COLLECTION_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+fn validate_visibility(visibility: &str) -> Result<(), AppError> {
+    match visibility {
+        "private" | "public" => Ok(()),
+        _ => Err(AppError::ValidationError(
+            "Visibility must be 'private' or 'public'".to_string(),
+        )),
+    }
+}
+
+fn validate_collection_note(note: &str) -> Result<(), AppError> {
+    if note.chars().count() > MAX_COLLECTION_NOTE_LEN {
+        return Err(AppError::ValidationError(format!(
+            "Notes are limited to {MAX_COLLECTION_NOTE_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+/// `{slugified-name}-{random}`; the suffix keeps slugs unique and unguessable.
+fn collection_slug(name: &str) -> String {
+    let base: String = search::slugify(name).chars().take(60).collect();
+    let base = base.trim_end_matches('-');
+    let base = if base.is_empty() { "collection" } else { base };
+    format!("{base}-{:08x}", rand::random::<u32>())
+}
+
+/// Resolve a slug to a collection id. Private collections are only visible to
+/// their owner; changing one (`write`) always requires ownership.
+async fn find_collection(db: &SqlitePool, slug: &str, write: bool) -> Result<i64, AppError> {
+    let (id, owner, visibility): (i64, i64, String) =
+        sqlx::query_as("SELECT id, user_id, visibility FROM collections WHERE slug = ?1")
+            .bind(slug)
+            .fetch_optional(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    if owner != CURRENT_USER_ID {
+        if visibility != "public" {
+            return Err(AppError::NotFound);
+        }
+        if write {
+            return Err(AppError::Forbidden);
+        }
+    }
+    Ok(id)
+}
+
+async fn fetch_collection(db: &SqlitePool, id: i64) -> Result<Collection, AppError> {
+    let row = sqlx::query_as::<_, Collection>(&format!("{COLLECTION_SELECT} WHERE c.id = ?1"))
+        .bind(id)
+        .fetch_one(db)
+        .await?;
+    Ok(row)
+}
+
+/// The collection with its items in order. Items whose artifact has since been
+/// deleted are left out.
+async fn collection_detail(db: &SqlitePool, id: i64) -> Result<serde_json::Value, AppError> {
+    let collection = fetch_collection(db, id).await?;
+    let items = sqlx::query_as::<_, CollectionItem>(
         r#"
-        SELECT id, title, description, duration, video_url, uploaded_date
-        FROM tutorials
-        WHERE deleted_at IS NULL
-        ORDER BY id
+        SELECT i.id, i.artifact_kind AS kind, i.artifact_id, i.position, i.note,
+               COALESCE(d.title, m.title, uc.title, a.title, t.title) AS title,
+               COALESCE(d.description, m.description, uc.description, a.description, t.description) AS description,
+               COALESCE(d.image_url, m.image_url, uc.image_url, a.image_url, t.image_url) AS image_url,
+               i.added_at
+        FROM collection_items i
+        LEFT JOIN datasets d  ON i.artifact_kind = 'dataset' AND d.id = i.artifact_id AND d.deleted_at IS NULL
+        LEFT JOIN models m    ON i.artifact_kind = 'model'   AND m.id = i.artifact_id AND m.deleted_at IS NULL
+        LEFT JOIN usecases uc ON i.artifact_kind = 'usecase' AND uc.id = i.artifact_id AND uc.deleted_at IS NULL
+        LEFT JOIN articles a  ON i.artifact_kind = 'article' AND a.id = i.artifact_id AND a.deleted_at IS NULL
+        LEFT JOIN toolkit t   ON i.artifact_kind = 'toolkit' AND t.id = i.artifact_id AND t.deleted_at IS NULL
+        WHERE i.collection_id = ?1
+          AND COALESCE(d.id, m.id, uc.id, a.id, t.id) IS NOT NULL
+        ORDER BY i.position, i.id
         "#,
     )
-    .fetch_all(&state.db)
+    .bind(id)
+    .fetch_all(db)
     .await?;
-    Ok(Json(rows))
+
+    let mut v = serde_json::to_value(&collection)?;
+    if let Some(obj) = v.as_object_mut() {
+        obj.insert("items".to_string(), serde_json::json!(items));
+    }
+    Ok(v)
 }
 
-// =============================================================================
-// SECTORS / ORGANIZATIONS  (filter chip endpoints)
-// =============================================================================
+async fn touch_collection(db: &SqlitePool, id: i64) -> Result<(), AppError> {
+    sqlx::query("UPDATE collections SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(id)
+        .execute(db)
+        .await?;
+    Ok(())
+}
 
-pub async fn get_sectors(
+pub async fn get_user_collections(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Sector>>, AppError> {
-    let rows = sqlx::query_as::<_, Sector>(
-        "SELECT id, name, slug, display_order FROM sectors ORDER BY display_order, name",
-    )
+) -> Result<Json<Vec<Collection>>, AppError> {
+    let rows = sqlx::query_as::<_, Collection>(&format!(
+        "{COLLECTION_SELECT} WHERE c.user_id = ?1 ORDER BY c.updated_at DESC, c.id DESC"
+    ))
+    .bind(CURRENT_USER_ID)
     .fetch_all(&state.db)
     .await?;
     Ok(Json(rows))
 }
 
-pub async fn get_organizations(
+pub async fn create_collection(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Organization>>, AppError> {
-    let rows = sqlx::query_as::<_, Organization>(
+    Json(payload): Json<CreateCollection>,
+) -> Result<Json<Collection>, AppError> {
+    let name = validate_collection_name(&payload.name)?;
+    let visibility = payload.visibility.as_deref().unwrap_or("private");
+    validate_visibility(visibility)?;
+    let description = payload
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let (id,): (i64,) = sqlx::query_as(
         r#"
-        SELECT id, name, slug
-        FROM organizations
-        WHERE deleted_at IS NULL
-        ORDER BY name
+        INSERT INTO collections (user_id, name, slug, description, visibility)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        RETURNING id
         "#,
     )
-    .fetch_all(&state.db)
+    .bind(CURRENT_USER_ID)
+    .bind(name)
+    .bind(collection_slug(name))
+    .bind(description)
+    .bind(visibility)
+    .fetch_one(&state.db)
     .await?;
-    Ok(Json(rows))
+    Ok(Json(fetch_collection(&state.db, id).await?))
 }
 
-// =============================================================================
-// USER  (hardcoded user_id=1 until JWT in Step 4)
-// =============================================================================
+pub async fn get_collection(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = find_collection(&state.db, &slug, false).await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
+}
 
-pub async fn get_user_profile(State(state): State<Arc<AppState>>) -> Result<Json<User>, AppError> {
-    let row = sqlx::query_as::<_, User>(
+pub async fn update_collection(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<UpdateCollection>,
+) -> Result<Json<Collection>, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+    let name = payload
+        .name
+        .as_deref()
+        .map(validate_collection_name)
+        .transpose()?;
+    if let Some(visibility) = payload.visibility.as_deref() {
+        validate_visibility(visibility)?;
+    }
+
+    // COALESCE keeps fields missing from the payload; an empty description clears it.
+    sqlx::query(
         r#"
-        SELECT id, email, username, full_name, bio, employee_id, profile_picture_url, role
-        FROM users
-        WHERE id = ?1 AND deleted_at IS NULL
+        UPDATE collections SET
+            name        = COALESCE(?1, name),
+            description = CASE WHEN ?2 IS NULL THEN description ELSE NULLIF(TRIM(?2), '') END,
+            visibility  = COALESCE(?3, visibility),
+            updated_at  = datetime('now')
+        WHERE id = ?4
         "#,
     )
-    .bind(CURRENT_USER_ID)
+    .bind(name)
+    .bind(payload.description)
+    .bind(payload.visibility)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+    Ok(Json(fetch_collection(&state.db, id).await?))
+}
+
+pub async fn delete_collection(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+    sqlx::query("DELETE FROM collections WHERE id = ?1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn add_collection_item(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<AddCollectionItem>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+    let Some((kind, table)) = COLLECTION_KINDS
+        .into_iter()
+        .find(|(kind, _)| *kind == payload.kind)
+    else {
+        return Err(AppError::ValidationError(format!(
+            "Unknown artifact type '{}'",
+            payload.kind
+        )));
+    };
+    let note = payload
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    if let Some(note) = note {
+        validate_collection_note(note)?;
+    }
+
+    let exists: Option<(i64,)> = sqlx::query_as(&format!(
+        "SELECT id FROM {table} WHERE id = ?1 AND deleted_at IS NULL"
+    ))
+    .bind(payload.id)
     .fetch_optional(&state.db)
-    .await?
-    .ok_or(AppError::NotFound)?;
-    Ok(Json(row))
+    .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let res = sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO collection_items (collection_id, artifact_kind, artifact_id, position, note)
+        SELECT ?1, ?2, ?3, COALESCE(MAX(position), 0) + 1, ?4
+        FROM collection_items WHERE collection_id = ?1
+        "#,
+    )
+    .bind(id)
+    .bind(kind)
+    .bind(payload.id)
+    .bind(note)
+    .execute(&state.db)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::ValidationError(
+            "This item is already in the collection".to_string(),
+        ));
+    }
+    touch_collection(&state.db, id).await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
 }
 
-pub async fn update_user_profile(
+pub async fn update_collection_item(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<UpdateUserProfile>,
-) -> Result<Json<User>, AppError> {
-    if let Some(name) = payload.full_name.as_deref() {
-        if name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Full name cannot be empty".to_string(),
-            ));
-        }
+    Path((slug, item_id)): Path<(String, i64)>,
+    Json(payload): Json<UpdateCollectionItem>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+    let set_note = payload.note.is_some();
+    let note = payload
+        .note
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(note) = &note {
+        validate_collection_note(note)?;
     }
 
-    // COALESCE pattern: if a field is NULL in the payload, keep the existing value.
-    sqlx::query(
+    let res = sqlx::query(
         r#"
-        UPDATE users SET
-            full_name           = COALESCE(?1, full_name),
-            bio                 = COALESCE(?2, bio),
-            employee_id         = COALESCE(?3, employee_id),
-            profile_picture_url = COALESCE(?4, profile_picture_url),
-            updated_at          = datetime('now')
-        WHERE id = ?5 AND deleted_at IS NULL
+        UPDATE collection_items SET note = CASE WHEN ?4 THEN ?1 ELSE note END
+        WHERE id = ?2 AND collection_id = ?3
         "#,
     )
-    .bind(payload.full_name)
-    .bind(payload.bio)
-    .bind(payload.employee_id)
-    .bind(payload.profile_picture_url)
-    .bind(CURRENT_USER_ID)
+    .bind(note)
+    .bind(item_id)
+    .bind(id)
+    .bind(set_note)
     .execute(&state.db)
     .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    touch_collection(&state.db, id).await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
+}
 
-    let row = sqlx::query_as::<_, User>(
+pub async fn delete_collection_item(
+    State(state): State<Arc<AppState>>,
+    Path((slug, item_id)): Path<(String, i64)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+    let res = sqlx::query("DELETE FROM collection_items WHERE id = ?1 AND collection_id = ?2")
+        .bind(item_id)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    if res.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    touch_collection(&state.db, id).await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
+}
+
+pub async fn reorder_collection(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<ReorderCollection>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = find_collection(&state.db, &slug, true).await?;
+
+    let mut tx = state.db.begin().await?;
+    let mut current: Vec<i64> =
+        sqlx::query_scalar("SELECT id FROM collection_items WHERE collection_id = ?1")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+    let mut requested = payload.item_ids.clone();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err(AppError::ValidationError(
+            "item_ids must list every item in the collection exactly once".to_string(),
+        ));
+    }
+
+    for (position, item_id) in payload.item_ids.iter().enumerate() {
+        sqlx::query("UPDATE collection_items SET position = ?1 WHERE id = ?2")
+            .bind(position as i64 + 1)
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    sqlx::query("UPDATE collections SET updated_at = datetime('now') WHERE id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
+}
+
+/// Copy a public (or your own) collection into a new private one you own,
+/// items and notes included.
+pub async fn fork_collection(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    payload: Option<Json<ForkCollection>>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let source_id = find_collection(&state.db, &slug, false).await?;
+    let source = fetch_collection(&state.db, source_id).await?;
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let name = match payload.name.as_deref() {
+        Some(name) => validate_collection_name(name)?.to_string(),
+        None => {
+            let name: String = source
+                .name
+                .chars()
+                .take(MAX_COLLECTION_NAME_LEN - " (copy)".len())
+                .collect();
+            format!("{name} (copy)")
+        }
+    };
+
+    let mut tx = state.db.begin().await?;
+    let (id,): (i64,) = sqlx::query_as(
         r#"
-        SELECT id, email, username, full_name, bio, employee_id, profile_picture_url, role
-        FROM users
-        WHERE id = ?1 AND deleted_at IS NULL
+        INSERT INTO collections (user_id, name, slug, description, visibility, forked_from_id)
+        VALUES (?1, ?2, ?3, ?4, 'private', ?5)
+        RETURNING id
         "#,
     )
     .bind(CURRENT_USER_ID)
-    .fetch_one(&state.db)
+    .bind(&name)
+    .bind(collection_slug(&name))
+    .bind(&source.description)
+    .bind(source_id)
+    .fetch_one(&mut *tx)
     .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO collection_items (collection_id, artifact_kind, artifact_id, position, note)
+        SELECT ?1, artifact_kind, artifact_id, position, note
+        FROM collection_items
+        WHERE collection_id = ?2
+        "#,
+    )
+    .bind(id)
+    .bind(source_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Json(collection_detail(&state.db, id).await?))
+}
 
-    Ok(Json(row))
+// =============================================================================
+// FOLLOWS / FEED  (organizations, sectors, tags and users; see feed.rs)
+// =============================================================================
+
+const FEED_DEFAULT_LIMIT: i64 = 20;
+const FEED_MAX_LIMIT: i64 = 100;
+
+/// (table, liveness condition) for each followable kind.
+fn follow_target(kind: &str) -> Result<(&'static str, &'static str), AppError> {
+    match kind {
+        "organization" => Ok(("organizations", "deleted_at IS NULL")),
+        "sector" => Ok(("sectors", "1")),
+        "tag" => Ok(("tags", "1")),
+        "user" => Ok(("users", "deleted_at IS NULL")),
+        _ => Err(AppError::ValidationError(format!(
+            "Unknown follow type '{kind}'"
+        ))),
+    }
+}
+
+async fn set_follow(
+    state: &AppState,
+    kind: &str,
+    id: i64,
+    following: bool,
+) -> Result<Json<FollowState>, AppError> {
+    let (table, live) = follow_target(kind)?;
+    if kind == "user" && id == CURRENT_USER_ID {
+        return Err(AppError::ValidationError(
+            "You cannot follow yourself".to_string(),
+        ));
+    }
+    let exists: Option<(i64,)> =
+        sqlx::query_as(&format!("SELECT id FROM {table} WHERE id = ?1 AND {live}"))
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let sql = if following {
+        "INSERT OR IGNORE INTO follows (user_id, target_kind, target_id) VALUES (?1, ?2, ?3)"
+    } else {
+        "DELETE FROM follows WHERE user_id = ?1 AND target_kind = ?2 AND target_id = ?3"
+    };
+    sqlx::query(sql)
+        .bind(CURRENT_USER_ID)
+        .bind(kind)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    let (followers_count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM follows WHERE target_kind = ?1 AND target_id = ?2")
+            .bind(kind)
+            .bind(id)
+            .fetch_one(&state.db)
+            .await?;
+    Ok(Json(FollowState {
+        following,
+        followers_count,
+    }))
+}
+
+pub async fn follow(
+    State(state): State<Arc<AppState>>,
+    Path((kind, id)): Path<(String, i64)>,
+) -> Result<Json<FollowState>, AppError> {
+    set_follow(&state, &kind, id, true).await
+}
+
+pub async fn unfollow(
+    State(state): State<Arc<AppState>>,
+    Path((kind, id)): Path<(String, i64)>,
+) -> Result<Json<FollowState>, AppError> {
+    set_follow(&state, &kind, id, false).await
+}
+
+pub async fn get_user_follows(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Follow>>, AppError> {
+    let rows = sqlx::query_as::<_, Follow>(
+        r#"
+        SELECT f.target_kind AS kind, f.target_id AS id,
+               COALESCE(o.name, s.name, t.name, u.full_name) AS name,
+               COALESCE(o.slug, s.slug, t.slug, u.username) AS slug,
+               f.created_at AS followed_at
+        FROM follows f
+        LEFT JOIN organizations o ON f.target_kind = 'organization' AND o.id = f.target_id
+                                 AND o.deleted_at IS NULL
+        LEFT JOIN sectors s       ON f.target_kind = 'sector' AND s.id = f.target_id
+        LEFT JOIN tags t          ON f.target_kind = 'tag' AND t.id = f.target_id
+        LEFT JOIN users u         ON f.target_kind = 'user' AND u.id = f.target_id
+                                 AND u.deleted_at IS NULL
+        WHERE f.user_id = ?1
+          AND COALESCE(o.id, s.id, t.id, u.id) IS NOT NULL
+        ORDER BY f.created_at DESC, f.target_kind, f.target_id
+        "#,
+    )
+    .bind(CURRENT_USER_ID)
+    .fetch_all(&state.db)
+    .await?;
+    Ok(Json(rows))
+}
+
+pub async fn get_feed(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<FeedQuery>,
+) -> Result<Json<FeedPage>, AppError> {
+    let limit = params
+        .limit
+        .unwrap_or(FEED_DEFAULT_LIMIT)
+        .clamp(1, FEED_MAX_LIMIT);
+    let page = feed::feed(&state.db, CURRENT_USER_ID, params.cursor.as_deref(), limit).await?;
+    Ok(Json(page))
 }
 
 // =============================================================================
-// DASHBOARD  (computed live from the DB, hardcoded user_id=1)
+// DASHBOARD  (per-user activity, contributions and recommendations; see dashboard.rs)
 // =============================================================================
 
 pub async fn get_dashboard(
     State(state): State<Arc<AppState>>,
+    Query(params): Query<DashboardQuery>,
 ) -> Result<Json<Dashboard>, AppError> {
     // User row
     let user: (String, String, i64, Option<String>) = sqlx::query_as(
@@ -653,33 +4460,24 @@ pub async fn get_dashboard(
     .fetch_one(&state.db)
     .await?;
 
-    // Total counts of artifacts in the platform (since views aren't tracked per-user yet,
-    // we surface platform totals here — same pattern as the original dashboard.json).
-    let counts: (i64, i64, i64) = sqlx::query_as(
-        "SELECT
-            (SELECT COUNT(*) FROM datasets WHERE deleted_at IS NULL),
-            (SELECT COUNT(*) FROM models   WHERE deleted_at IS NULL),
-            (SELECT COUNT(*) FROM usecases WHERE deleted_at IS NULL)",
-    )
-    .fetch_one(&state.db)
-    .await?;
-
-    let downloads: (i64, i64) = (0, 0);
+    let window = params.window.unwrap_or_default();
+    let (viewed, downloaded, liked) =
+        dashboard::activity_counts(&state.db, CURRENT_USER_ID, window).await?;
 
     Ok(Json(Dashboard {
         greeting: format!("Hi {}", user.0),
         role: user.1,
         login_streak: user.2,
-        artifacts_viewed: ArtifactCounts {
-            datasets: counts.0,
-            models: counts.1,
-            use_cases: counts.2,
-        },
-        artifacts_downloaded: DownloadCounts {
-            datasets: downloads.0,
-            models: downloads.1,
-        },
         last_login: user.3,
+        window,
+        artifacts_viewed: viewed,
+        artifacts_downloaded: downloaded,
+        artifacts_liked: liked,
+        contributions: dashboard::contributions(&state.db, CURRENT_USER_ID).await?,
+        timeline: dashboard::timeline(&state.db, CURRENT_USER_ID).await?,
+        recently_viewed: dashboard::recently_viewed(&state.db, CURRENT_USER_ID).await?,
+        recommended: dashboard::recommendations(&state.db, CURRENT_USER_ID).await?,
+        heatmap: dashboard::heatmap(&state.db, CURRENT_USER_ID).await?,
     }))
 }
 
@@ -692,6 +4490,7 @@ pub async fn chat_stream(
     Json(payload): Json<ChatMessage>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
     let client = reqwest::Client::new();
+    let log = ChatLog::start(Some(CURRENT_USER_ID), &payload.message);
     let python_request = PythonChatRequest {
         question: payload.message,
     };
@@ -708,6 +4507,7 @@ pub async fn chat_stream(
                 if response.status().is_success() {
                     match response.json::<PythonChatResponse>().await {
                         Ok(chat_response) => {
+                            log.record(&state.db, Some(&chat_response.answer)).await;
                             let words: Vec<&str> = chat_response.answer.split_whitespace().collect();
                             for (index, word) in words.iter().enumerate() {
                                 let text = if index == 0 {
@@ -723,18 +4523,21 @@ pub async fn chat_stream(
                             tracing::error!("Failed to parse Python response: {}", e);
                             let error_msg = "Sorry, I encountered an error processing the response.";
                             yield Ok(Event::default().data(error_msg));
+                            log.record(&state.db, None).await;
                         }
                     }
                 } else {
                     tracing::error!("Python chatbot returned error: {}", response.status());
                     let error_msg = "Sorry, the chatbot service is currently unavailable.";
                     yield Ok(Event::default().data(error_msg));
+                    log.record(&state.db, None).await;
                 }
             }
             Err(e) => {
                 tracing::error!("Failed to connect to Python chatbot: {}", e);
                 let error_msg = "Sorry, I couldn't connect to the chatbot service. Please make sure it's running.";
                 yield Ok(Event::default().data(error_msg));
+                log.record(&state.db, None).await;
             }
         }
     };
@@ -745,3 +4548,73 @@ pub async fn chat_stream(
             .text("keep-alive"),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_and_suffix_ranges() {
+        assert_eq!(parse_range(Some("bytes=0-"), 100), Ok(Some((0, 99))));
+        assert_eq!(parse_range(Some("bytes=40-"), 100), Ok(Some((40, 99))));
+        assert_eq!(parse_range(Some("bytes=-10"), 100), Ok(Some((90, 99))));
+        // A suffix longer than the file is the whole file.
+        assert_eq!(parse_range(Some("bytes=-500"), 100), Ok(Some((0, 99))));
+        assert_eq!(parse_range(Some("bytes=-0"), 100), Err(()));
+    }
+
+    #[test]
+    fn closed_ranges_clamp_to_eof() {
+        assert_eq!(parse_range(Some("bytes=0-0"), 100), Ok(Some((0, 0))));
+        assert_eq!(parse_range(Some(" bytes=10-19 "), 100), Ok(Some((10, 19))));
+        assert_eq!(parse_range(Some("bytes=10-500"), 100), Ok(Some((10, 99))));
+        assert_eq!(parse_range(Some("bytes=100-200"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=100-"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=20-10"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=abc"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=a-b"), 100), Err(()));
+    }
+
+    #[test]
+    fn zero_size_file() {
+        assert_eq!(parse_range(None, 0), Ok(None));
+        assert_eq!(parse_range(Some("bytes=0-"), 0), Err(()));
+        assert_eq!(parse_range(Some("bytes=0-0"), 0), Err(()));
+        assert_eq!(parse_range(Some("bytes=-5"), 0), Err(()));
+    }
+
+    #[test]
+    fn unhandled_forms_serve_the_whole_file() {
+        assert_eq!(parse_range(None, 100), Ok(None));
+        assert_eq!(parse_range(Some("bytes=0-9,20-29"), 100), Ok(None));
+        assert_eq!(parse_range(Some("items=0-9"), 100), Ok(None));
+    }
+
+    #[test]
+    fn probes_and_later_chunks_are_not_downloads() {
+        let size = 10 * DOWNLOAD_PROBE_BYTES;
+        assert!(counts_as_download(None, size));
+        assert!(counts_as_download(Some((0, size - 1)), size));
+        assert!(!counts_as_download(Some((0, 0)), size));
+        assert!(!counts_as_download(
+            Some((0, DOWNLOAD_PROBE_BYTES - 2)),
+            size
+        ));
+        assert!(counts_as_download(
+            Some((0, DOWNLOAD_PROBE_BYTES - 1)),
+            size
+        ));
+        assert!(!counts_as_download(Some((1, size - 1)), size));
+        assert!(!counts_as_download(
+            Some((DOWNLOAD_PROBE_BYTES, size - 1)),
+            size
+        ));
+    }
+
+    #[test]
+    fn whole_small_files_count_even_below_the_probe_size() {
+        assert!(counts_as_download(Some((0, 0)), 1));
+        assert!(counts_as_download(Some((0, 99)), 100));
+        assert!(!counts_as_download(Some((0, 9)), 100));
+    }
+}
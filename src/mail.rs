@@ -0,0 +1,155 @@
+//! Outgoing email.
+//!
+//! A `Mailer` delivers one message with a plain-text and an HTML body. Two
+//! transports: `FileMailer` writes each message as an `.eml` file (open it in
+//! any mail client, or just read it) so local setups never send real mail, and
+//! `HttpMailer` POSTs the message as JSON to a relay that does the sending.
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::config::{Config, MailerKind};
+use crate::errors::AppError;
+
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, Result<(), AppError>>;
+}
+
+pub fn from_config(config: &Config) -> Arc<dyn Mailer> {
+    match config.mailer {
+        MailerKind::File => Arc::new(FileMailer {
+            dir: PathBuf::from(&config.mail_file_dir),
+            from: config.mail_from.clone(),
+        }),
+        MailerKind::Http => Arc::new(HttpMailer {
+            client: reqwest::Client::new(),
+            // Presence is checked in Config::from_env.
+            url: config.mail_http_url.clone().unwrap_or_default(),
+            api_key: config.mail_http_api_key.clone(),
+            from: config.mail_from.clone(),
+        }),
+    }
+}
+
+// =============================================================================
+// FILE SINK (local testing)
+// =============================================================================
+
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+/// RFC 2047 Q-encoding for header values that aren't plain ASCII.
+fn encode_header(value: &str) -> String {
+    if value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        return value.to_string();
+    }
+    let mut out = String::from("=?UTF-8?Q?");
+    for b in value.bytes() {
+        match b {
+            b' ' => out.push('_'),
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' => out.push(b as char),
+            _ => out.push_str(&format!("={b:02X}")),
+        }
+    }
+    out.push_str("?=");
+    out
+}
+
+/// A multipart/alternative RFC 5322 message with 8-bit UTF-8 bodies.
+fn to_eml(from: &str, email: &Email) -> String {
+    let boundary = format!("aikosh-{}", hex::encode(rand::random::<[u8; 12]>()));
+    let part = |content_type: &str, body: &str| {
+        format!(
+            "--{boundary}\r\n\
+             Content-Type: {content_type}; charset=utf-8\r\n\
+             Content-Transfer-Encoding: 8bit\r\n\r\n\
+             {}\r\n",
+            body.replace('\n', "\r\n")
+        )
+    };
+    format!(
+        "From: {from}\r\n\
+         To: {to}\r\n\
+         Subject: {subject}\r\n\
+         Date: {date}\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: multipart/alternative; boundary=\"{boundary}\"\r\n\r\n\
+         {text}{html}--{boundary}--\r\n",
+        to = email.to,
+        subject = encode_header(&email.subject),
+        date = chrono::Utc::now().to_rfc2822(),
+        text = part("text/plain", &email.text),
+        html = part("text/html", &email.html),
+    )
+}
+
+impl Mailer for FileMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            let name = format!(
+                "{}-{}.eml",
+                chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+                hex::encode(rand::random::<[u8; 4]>())
+            );
+            let path = self.dir.join(name);
+            tokio::fs::write(&path, to_eml(&self.from, email)).await?;
+            tracing::debug!("wrote email to {}", path.display());
+            Ok(())
+        })
+    }
+}
+
+// =============================================================================
+// HTTP RELAY
+// =============================================================================
+
+pub struct HttpMailer {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+    from: String,
+}
+
+#[derive(Serialize)]
+struct RelayMessage<'a> {
+    from: &'a str,
+    to: &'a str,
+    subject: &'a str,
+    text: &'a str,
+    html: &'a str,
+}
+
+impl Mailer for HttpMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&RelayMessage {
+                from: &self.from,
+                to: &email.to,
+                subject: &email.subject,
+                text: &email.text,
+                html: &email.html,
+            });
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+            request
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| AppError::Mail(e.to_string()))?;
+            Ok(())
+        })
+    }
+}
@@ -1,12 +1,28 @@
+mod analytics;
 mod config;
+mod dashboard;
+mod digests;
+mod embeddings;
 mod errors;
+mod feed;
 mod handlers;
+mod mail;
 mod models;
+mod notifications;
+mod profiling;
+mod related;
+mod saved_searches;
+mod search;
+mod search_log;
 mod state;
+mod storage;
+mod trending;
+mod webhooks;
 
 use axum::{
+    extract::DefaultBodyLimit,
     http::HeaderValue,
-    routing::{get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::{error::Error, sync::Arc};
@@ -34,26 +50,262 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let cors = CorsLayer::new()
         .allow_origin(frontend_origin)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([search_log::SEARCH_ID_HEADER]);
 
     let shared_state = Arc::new(AppState::init(config).await?);
+    embeddings::spawn_indexer(shared_state.clone());
+    saved_searches::spawn_checker(shared_state.clone());
+    notifications::spawn_follow_checker(shared_state.clone());
+    webhooks::spawn_dispatcher(shared_state.clone());
+    digests::spawn_sender(shared_state.clone());
+    trending::spawn_refresher(shared_state.clone());
+    profiling::spawn_profiler(shared_state.clone());
+    let upload_limit = usize::try_from(shared_state.config.upload_max_bytes).unwrap_or(usize::MAX);
 
     let app = Router::new()
         .route("/health", get(health_check))
         .route("/api/dashboard", get(handlers::get_dashboard))
         .route("/api/datasets", get(handlers::get_datasets))
         .route("/api/datasets/:id", get(handlers::get_dataset_by_id))
+        .route(
+            "/api/datasets/:id/comments",
+            get(handlers::get_dataset_comments).post(handlers::create_dataset_comment),
+        )
+        .route(
+            "/api/datasets/:id/reviews",
+            get(handlers::get_dataset_reviews),
+        )
+        .route(
+            "/api/datasets/:id/review",
+            put(handlers::put_dataset_review).delete(handlers::delete_dataset_review),
+        )
+        .route(
+            "/api/datasets/:id/like",
+            put(handlers::like_dataset).delete(handlers::unlike_dataset),
+        )
+        .route(
+            "/api/datasets/:id/files",
+            get(handlers::get_dataset_files)
+                .post(handlers::upload_dataset_files)
+                .layer(DefaultBodyLimit::max(upload_limit)),
+        )
+        .route(
+            "/api/datasets/:id/files/:file_id",
+            delete(handlers::delete_dataset_file),
+        )
+        .route(
+            "/api/datasets/:id/files/:file_id/download",
+            get(handlers::download_dataset_file),
+        )
+        .route(
+            "/api/datasets/:id/files/:file_id/profile",
+            get(handlers::get_dataset_file_profile).post(handlers::reprofile_dataset_file),
+        )
+        .route(
+            "/api/datasets/:id/columns",
+            get(handlers::get_dataset_columns).post(handlers::create_dataset_column),
+        )
+        .route(
+            "/api/datasets/:id/columns/:column_id",
+            put(handlers::update_dataset_column).delete(handlers::delete_dataset_column),
+        )
+        .route(
+            "/api/datasets/:id/related",
+            get(handlers::get_dataset_related),
+        )
         .route("/api/models", get(handlers::get_models))
         .route("/api/models/:id", get(handlers::get_model_by_id))
+        .route(
+            "/api/models/:id/comments",
+            get(handlers::get_model_comments).post(handlers::create_model_comment),
+        )
+        .route("/api/models/:id/reviews", get(handlers::get_model_reviews))
+        .route(
+            "/api/models/:id/review",
+            put(handlers::put_model_review).delete(handlers::delete_model_review),
+        )
+        .route(
+            "/api/models/:id/like",
+            put(handlers::like_model).delete(handlers::unlike_model),
+        )
+        .route(
+            "/api/models/:id/download",
+            post(handlers::record_model_download),
+        )
+        .route("/api/models/:id/related", get(handlers::get_model_related))
         .route("/api/usecases", get(handlers::get_usecases))
         .route("/api/usecases/:id", get(handlers::get_usecase_by_id))
+        .route(
+            "/api/usecases/:id/comments",
+            get(handlers::get_usecase_comments).post(handlers::create_usecase_comment),
+        )
+        .route(
+            "/api/usecases/:id/like",
+            put(handlers::like_usecase).delete(handlers::unlike_usecase),
+        )
+        .route(
+            "/api/usecases/:id/related",
+            get(handlers::get_usecase_related),
+        )
         .route("/api/tutorials", get(handlers::get_tutorials))
         .route("/api/articles", get(handlers::get_articles))
         .route("/api/articles/:id", get(handlers::get_article_by_id))
+        .route(
+            "/api/articles/:id/comments",
+            get(handlers::get_article_comments).post(handlers::create_article_comment),
+        )
+        .route(
+            "/api/articles/:id/like",
+            put(handlers::like_article).delete(handlers::unlike_article),
+        )
         .route("/api/toolkit", get(handlers::get_toolkit))
         .route("/api/toolkit/:id", get(handlers::get_toolkit_by_id))
         .route("/api/users/profile", get(handlers::get_user_profile))
         .route("/api/users/profile", patch(handlers::update_user_profile))
+        .route("/api/users/profile/likes", get(handlers::get_user_likes))
+        .route(
+            "/api/users/profile/saved-searches",
+            get(handlers::get_saved_searches).post(handlers::create_saved_search),
+        )
+        .route(
+            "/api/users/profile/saved-searches/:id",
+            delete(handlers::delete_saved_search),
+        )
+        .route(
+            "/api/users/profile/collections",
+            get(handlers::get_user_collections).post(handlers::create_collection),
+        )
+        .route(
+            "/api/collections/:slug",
+            get(handlers::get_collection)
+                .patch(handlers::update_collection)
+                .delete(handlers::delete_collection),
+        )
+        .route(
+            "/api/collections/:slug/items",
+            post(handlers::add_collection_item),
+        )
+        .route(
+            "/api/collections/:slug/items/:item_id",
+            patch(handlers::update_collection_item).delete(handlers::delete_collection_item),
+        )
+        .route(
+            "/api/collections/:slug/order",
+            put(handlers::reorder_collection),
+        )
+        .route(
+            "/api/collections/:slug/fork",
+            post(handlers::fork_collection),
+        )
+        .route(
+            "/api/comments/:id",
+            patch(handlers::update_comment).delete(handlers::delete_comment),
+        )
+        .route("/api/admin/comments/:id", patch(handlers::moderate_comment))
+        .route(
+            "/api/follows/:kind/:id",
+            put(handlers::follow).delete(handlers::unfollow),
+        )
+        .route(
+            "/api/users/profile/follows",
+            get(handlers::get_user_follows),
+        )
+        .route(
+            "/api/users/profile/digest",
+            get(handlers::get_digest_preferences).put(handlers::update_digest_preferences),
+        )
+        .route(
+            "/api/users/profile/digest/preview",
+            get(handlers::preview_digest),
+        )
+        .route("/api/feed", get(handlers::get_feed))
+        .route("/api/notifications", get(handlers::get_notifications))
+        .route(
+            "/api/notifications/unread-count",
+            get(handlers::get_unread_notification_count),
+        )
+        .route(
+            "/api/notifications/read-all",
+            post(handlers::mark_all_notifications_read),
+        )
+        .route(
+            "/api/notifications/stream",
+            get(handlers::notification_stream),
+        )
+        .route(
+            "/api/notifications/:id/read",
+            post(handlers::mark_notification_read),
+        )
+        .route("/api/trending", get(handlers::get_trending))
+        .route("/api/search", get(handlers::semantic_search))
+        .route("/api/search/suggest", get(handlers::get_search_suggestions))
+        .route("/api/search/clicks", post(handlers::record_search_click))
+        .route(
+            "/api/admin/search/synonyms",
+            get(handlers::get_search_synonyms).post(handlers::create_search_synonym),
+        )
+        .route(
+            "/api/admin/search/synonyms/:id",
+            delete(handlers::delete_search_synonym),
+        )
+        .route(
+            "/api/admin/search/reports/top-queries",
+            get(handlers::get_top_search_queries),
+        )
+        .route(
+            "/api/admin/search/reports/zero-results",
+            get(handlers::get_zero_result_queries),
+        )
+        .route(
+            "/api/admin/search/reports/click-through",
+            get(handlers::get_search_click_through),
+        )
+        .route(
+            "/api/admin/analytics/summary",
+            get(handlers::get_analytics_summary),
+        )
+        .route(
+            "/api/admin/analytics/active-users",
+            get(handlers::get_analytics_active_users),
+        )
+        .route(
+            "/api/admin/analytics/registrations",
+            get(handlers::get_analytics_registrations),
+        )
+        .route(
+            "/api/admin/analytics/top-artifacts",
+            get(handlers::get_analytics_top_artifacts),
+        )
+        .route(
+            "/api/admin/analytics/downloads-by-sector",
+            get(handlers::get_analytics_downloads_by_sector),
+        )
+        .route(
+            "/api/admin/analytics/downloads-by-organization",
+            get(handlers::get_analytics_downloads_by_organization),
+        )
+        .route(
+            "/api/admin/analytics/chat",
+            get(handlers::get_analytics_chat),
+        )
+        .route(
+            "/api/admin/webhooks",
+            get(handlers::get_webhooks).post(handlers::create_webhook),
+        )
+        .route(
+            "/api/admin/webhooks/:id",
+            patch(handlers::update_webhook).delete(handlers::delete_webhook),
+        )
+        .route(
+            "/api/admin/webhooks/:id/deliveries",
+            get(handlers::get_webhook_deliveries),
+        )
+        .route("/api/admin/webhooks/:id/ping", post(handlers::ping_webhook))
+        .route(
+            "/api/admin/webhooks/:id/deliveries/:delivery_id/redeliver",
+            post(handlers::redeliver_webhook_delivery),
+        )
         .route("/api/sectors", get(handlers::get_sectors))
         .route("/api/organizations", get(handlers::get_organizations))
         .route("/api/chat/stream", post(handlers::chat_stream))
@@ -96,4 +348,4 @@ async fn shutdown_signal() {
             tracing::info!("SIGTERM received, starting graceful shutdown");
         }
     }
-}
\ No newline at end of file
+}
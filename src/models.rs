@@ -3,9 +3,80 @@
 //! Field types match what SQLite returns (i64 for integers, joined strings
 //! for foreign-key references like organization name and sector name).
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 
+// =============================================================================
+// ARTIFACT KINDS
+//
+// Catalog entries that can be referenced generically as (kind, item_id).
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    Dataset,
+    Model,
+    UseCase,
+    Article,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 4] = [
+        ArtifactKind::Dataset,
+        ArtifactKind::Model,
+        ArtifactKind::UseCase,
+        ArtifactKind::Article,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArtifactKind::Dataset => "dataset",
+            ArtifactKind::Model => "model",
+            ArtifactKind::UseCase => "usecase",
+            ArtifactKind::Article => "article",
+        }
+    }
+
+    pub fn table(self) -> &'static str {
+        match self {
+            ArtifactKind::Dataset => "datasets",
+            ArtifactKind::Model => "models",
+            ArtifactKind::UseCase => "usecases",
+            ArtifactKind::Article => "articles",
+        }
+    }
+
+    pub fn fts_table(self) -> &'static str {
+        match self {
+            ArtifactKind::Dataset => "datasets_fts",
+            ArtifactKind::Model => "models_fts",
+            ArtifactKind::UseCase => "usecases_fts",
+            ArtifactKind::Article => "articles_fts",
+        }
+    }
+
+    /// (join table, foreign-key column) linking rows to tags; articles are untagged.
+    pub fn tag_table(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            ArtifactKind::Dataset => Some(("dataset_tags", "dataset_id")),
+            ArtifactKind::Model => Some(("model_tags", "model_id")),
+            ArtifactKind::UseCase => Some(("usecase_tags", "usecase_id")),
+            ArtifactKind::Article => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ArtifactKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ArtifactKind::ALL
+            .into_iter()
+            .find(|k| k.as_str() == s.trim())
+            .ok_or_else(|| format!("Unknown artifact type '{}'", s.trim()))
+    }
+}
 
 // =============================================================================
 // QUERY PARAMS
@@ -14,8 +85,484 @@ use sqlx::FromRow;
 #[derive(Debug, Deserialize, Default)]
 pub struct ListQuery {
     pub search: Option<String>,
-    pub sector: Option<String>,           // sector slug, e.g. "healthcare"
-    pub organization_id: Option<i64>,     // datasets only
+    pub sector: Option<String>,          // sector slug, e.g. "healthcare"
+    pub organization_id: Option<i64>,    // datasets only
+    pub highlight_start: Option<String>, // marker before a matched term, default "<mark>"
+    pub highlight_end: Option<String>,   // marker after a matched term, default "</mark>"
+    pub sort: Option<ListSort>,          // default relevance, then id
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListSort {
+    Rating,   // datasets and models: best average first, more ratings breaking ties; unrated last
+    Trending, // datasets, models and use cases: highest decayed recent-activity score first
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SuggestQuery {
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Envelope for the searchable catalog lists (datasets / models / usecases).
+/// `did_you_mean` is set when the query matched nothing and `items` hold the
+/// results of its spelling-corrected form instead.
+#[derive(Debug, Serialize)]
+pub struct SearchResults {
+    pub items: Vec<serde_json::Value>,
+    pub did_you_mean: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SemanticSearchQuery {
+    pub q: Option<String>,
+    pub types: Option<String>, // comma-separated ArtifactKind list, default all
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RelatedQuery {
+    pub types: Option<String>, // comma-separated ArtifactKind list, default all
+    pub limit: Option<i64>,
+}
+
+// =============================================================================
+// SEARCH MATCHES
+//
+// Filled only by the FTS branch of the list handlers, from highlight()/snippet().
+// A column stays NULL unless the query matched it, so the handler can emit a
+// `matches` object with just the columns that explain the hit.
+// =============================================================================
+
+#[derive(Debug, Default, Serialize, FromRow)]
+pub struct SearchMatches {
+    #[sqlx(rename = "match_title", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[sqlx(rename = "match_description", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[sqlx(rename = "match_about", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+
+    #[sqlx(rename = "match_tags", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+}
+
+impl SearchMatches {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.about.is_none()
+            && self.tags.is_none()
+    }
+}
+
+// =============================================================================
+// TRENDING
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TrendingQuery {
+    #[serde(rename = "type")]
+    pub kind: Option<String>, // dataset, model or usecase; default all three
+    pub sector: Option<String>, // sector slug
+    pub limit: Option<i64>,     // default 20, max 100
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TrendingItem {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub sector: Option<String>,
+    pub sector_slug: Option<String>,
+    pub score: f64,
+    pub views: i64, // raw events behind the score, last 30 days
+    pub likes: i64,
+    pub downloads: i64,
+}
+
+// =============================================================================
+// SEARCH SUGGESTIONS
+//
+// `kind` is one of sector / tag / organization / dataset / model / usecase.
+// `slug` is set for the lookup kinds so the frontend can apply them as filters.
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Suggestion {
+    pub kind: String,
+    pub id: i64,
+    pub label: String,
+    pub slug: Option<String>,
+}
+
+// =============================================================================
+// SEMANTIC SEARCH HITS
+//
+// `score` blends the normalized bm25 keyword score and the cosine similarity
+// of the embeddings; either component is 0 when that side did not match.
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct SemanticHit {
+    pub kind: ArtifactKind,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub score: f64,
+    pub keyword_score: f64,
+    pub similarity: f64,
+}
+
+// =============================================================================
+// RELATED ARTIFACTS
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct RelatedItem {
+    pub kind: ArtifactKind,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub score: f64,
+    pub shared_tags: i64,
+    pub same_sector: bool,
+    pub same_organization: bool,
+    pub similarity: f64,
+}
+
+// =============================================================================
+// SEARCH SYNONYMS (admin)
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SearchSynonym {
+    pub id: i64,
+    pub term: String,
+    pub synonym: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSearchSynonym {
+    pub term: String,
+    pub synonym: String,
+}
+
+// =============================================================================
+// SEARCH ANALYTICS
+// =============================================================================
+
+/// A result opened from a logged search (`search_id` from `X-Search-Id`).
+#[derive(Debug, Deserialize)]
+pub struct CreateSearchClick {
+    pub search_id: i64,
+    pub kind: String, // dataset | model | usecase | article | toolkit | tutorial
+    pub id: i64,
+    pub position: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SearchReportQuery {
+    pub days: Option<i64>,
+    pub surface: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopSearchQuery {
+    pub query: String,
+    pub searches: i64,
+    pub zero_result_searches: i64,
+    pub avg_results: f64,
+    pub clicked_searches: i64,
+    pub click_through_rate: f64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ZeroResultQuery {
+    pub query: String,
+    pub searches: i64,
+    pub surfaces: String,
+    pub last_searched_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ClickThroughStats {
+    pub surface: String,
+    pub searches: i64,
+    pub clicked_searches: i64,
+    pub click_through_rate: f64,
+    pub avg_click_position: Option<f64>,
+    pub avg_latency_ms: f64,
+}
+
+// =============================================================================
+// PLATFORM ANALYTICS (admin)
+//
+// Date ranges are inclusive UTC days; every report can also be had as CSV.
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    pub from: Option<String>, // YYYY-MM-DD, default 29 days before `to`
+    pub to: Option<String>,   // YYYY-MM-DD, default today
+    pub format: Option<AnalyticsFormat>, // json (default) or csv
+    pub kind: Option<String>, // top-artifacts: dataset, model or usecase (views only)
+    pub metric: Option<AnalyticsMetric>, // top-artifacts: views (default) or downloads
+    pub limit: Option<i64>,   // top-artifacts and breakdowns
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsMetric {
+    #[default]
+    Views,
+    Downloads,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct AnalyticsSummary {
+    pub from: String,
+    pub to: String,
+    pub active_users: i64,
+    pub registrations: i64,
+    pub views: i64,
+    pub downloads: i64,
+    pub searches: i64,
+    pub chat_messages: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DailyActiveUsers {
+    pub date: String,
+    pub active_users: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DailyRegistrations {
+    pub date: String,
+    pub registrations: i64,
+    pub total_users: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopArtifact {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub count: i64,
+    pub unique_users: i64, // views also count anonymous sessions
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DownloadBreakdown {
+    pub id: Option<i64>, // NULL groups entries without a sector/organization
+    pub name: String,
+    pub downloads: i64,
+    pub unique_users: i64,
+    pub datasets: i64,
+    pub models: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DailyChatUsage {
+    pub date: String,
+    pub messages: i64,
+    pub users: i64,
+    pub failed: i64,
+    pub avg_latency_ms: Option<f64>,
+}
+
+// =============================================================================
+// SAVED SEARCHES
+//
+// A named `ListQuery` for one catalog kind; a background job turns newly
+// created matches into notifications for the owner.
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub name: String,
+    pub kind: String,
+    pub search: Option<String>,
+    pub sector: Option<String>,
+    pub organization_id: Option<i64>,
+    pub last_checked_at: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedSearch {
+    pub name: String,
+    pub kind: ArtifactKind,
+    pub search: Option<String>,
+    pub sector: Option<String>,
+    pub organization_id: Option<i64>, // datasets only, as in ListQuery
+}
+
+// =============================================================================
+// NOTIFICATIONS
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Notification {
+    pub id: i64,
+    pub kind: String, // saved_search_match, followed_activity, comment, comment_reply
+    pub title: String,
+    pub body: Option<String>,
+    pub artifact_kind: Option<String>,
+    pub artifact_id: Option<i64>,
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    pub unread: Option<bool>, // only unread ones
+    pub before: Option<i64>,  // id of the last notification on the previous page
+    pub limit: Option<i64>,   // default 20, max 100
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnreadCount {
+    pub unread: i64,
+}
+
+// =============================================================================
+// EMAIL DIGESTS
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DigestFrequency::Off => "off",
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DigestPreferences {
+    pub frequency: String, // off, daily, weekly
+    pub email: String,
+    pub last_sent_at: Option<String>,
+    pub next_digest_at: Option<String>, // NULL when off; the job may run up to DIGEST_CHECK_SECS later
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDigestPreferences {
+    pub frequency: DigestFrequency,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DigestPreviewQuery {
+    pub frequency: Option<DigestFrequency>, // default: the saved one, or daily when off
+}
+
+#[derive(Debug, Serialize)]
+pub struct DigestPreview {
+    pub period_start: String,
+    pub period_end: String,
+    pub item_count: usize,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+// =============================================================================
+// WEBHOOKS (admin)
+//
+// `events` and delivery payloads are stored as JSON text; the handler parses
+// them into the serialized fields.
+// The signing secret is only returned once, when the webhook is created.
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub description: Option<String>,
+    #[sqlx(rename = "events")]
+    #[serde(skip)]
+    pub events_json: String,
+    #[sqlx(skip)]
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhook {
+    pub url: String,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhook {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    #[sqlx(rename = "payload")]
+    #[serde(skip)]
+    pub payload_json: String,
+    #[sqlx(skip)]
+    pub payload: serde_json::Value,
+    pub status: String, // pending, succeeded, failed
+    pub attempts: i64,
+    pub next_attempt_at: String,
+    pub last_attempt_at: Option<String>,
+    pub response_status: Option<i64>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub redelivery_of_id: Option<i64>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    pub status: Option<String>,
+    pub before: Option<i64>, // id of the last delivery on the previous page
+    pub limit: Option<i64>,  // default 50, max 200
 }
 
 // =============================================================================
@@ -23,6 +570,7 @@ pub struct ListQuery {
 //
 // `tags_csv` is internal — populated from a SQL GROUP_CONCAT.
 // The handler converts it to a Vec<String> named `tags` before serializing.
+// `matches` is likewise internal and only serialized when non-empty.
 // =============================================================================
 
 #[derive(Debug, Serialize, FromRow)]
@@ -36,6 +584,8 @@ pub struct Dataset {
     pub likes_count: i64,
     pub downloads_count: i64,
     pub views_count: i64,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
 
     pub source_org: Option<String>,
     pub source_organisation: Option<String>,
@@ -58,6 +608,9 @@ pub struct Dataset {
     pub data_collection_method: Option<String>,
 
     pub tags_csv: Option<String>,
+    #[sqlx(flatten)]
+    #[serde(skip)]
+    pub matches: SearchMatches,
 }
 
 #[derive(Debug, Serialize, FromRow)]
@@ -71,6 +624,8 @@ pub struct Model {
     pub likes_count: i64,
     pub downloads_count: i64,
     pub views_count: i64,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
 
     pub source_org: Option<String>,
     pub source_organization: Option<String>,
@@ -87,6 +642,9 @@ pub struct Model {
     pub model_updated_at: Option<String>,
 
     pub tags_csv: Option<String>,
+    #[sqlx(flatten)]
+    #[serde(skip)]
+    pub matches: SearchMatches,
 }
 
 #[derive(Debug, Serialize, FromRow)]
@@ -96,12 +654,363 @@ pub struct UseCase {
     pub description: String,
     pub about_use_case: Option<String>,
     pub image_url: Option<String>,
+    pub likes_count: i64,
+    pub views_count: i64,
 
     pub source_org: Option<String>,
     pub sector: Option<String>,
     pub sector_slug: Option<String>,
 
     pub tags_csv: Option<String>,
+    #[sqlx(flatten)]
+    #[serde(skip)]
+    pub matches: SearchMatches,
+}
+
+// =============================================================================
+// LIKES
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct LikeState {
+    pub liked: bool,
+    pub likes_count: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct LikedItem {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub likes_count: i64,
+    pub liked_at: String,
+}
+
+// =============================================================================
+// FOLLOWS / FEED
+// =============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct FollowState {
+    pub following: bool,
+    pub followers_count: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Follow {
+    pub kind: String, // organization, sector, tag or user
+    pub id: i64,
+    pub name: String,
+    pub slug: String, // username for users
+    pub followed_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub cursor: Option<String>, // `next_cursor` from the previous page
+    pub limit: Option<i64>,     // default 20, max 100
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct FeedItem {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub event: String, // "published" or "updated"
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedPage {
+    pub items: Vec<FeedItem>,
+    pub next_cursor: Option<String>, // None on the last page
+}
+
+// =============================================================================
+// REVIEWS
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Review {
+    pub id: i64,
+    pub author_id: i64,
+    pub author: String, // username
+    pub rating: i64,
+    pub body: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertReview {
+    pub rating: i64,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RatingSummary {
+    pub average: Option<f64>,
+    pub count: i64,
+    pub distribution: [i64; 5], // number of 1..=5 star ratings
+}
+
+// =============================================================================
+// COMMENTS
+//
+// `body` is None for deleted comments, and for hidden ones unless the viewer
+// is an admin; the flags say which.
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CommentQuery {
+    pub page: Option<i64>,     // 1-based, over top-level threads
+    pub per_page: Option<i64>, // default 20, max 100
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Comment {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub root_id: Option<i64>,
+    pub author_id: i64,
+    pub author: String, // username
+    pub body: Option<String>,
+    pub is_deleted: bool,
+    pub is_hidden: bool,
+    pub is_locked: bool, // thread roots only
+    pub edited_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentPage {
+    /// Thread roots on this page, newest first, each followed by its replies
+    /// oldest first; nest them by `parent_id`.
+    pub comments: Vec<Comment>,
+    pub total_threads: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateComment {
+    pub body: String,
+    pub parent_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateComment {
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModerateComment {
+    pub hidden: Option<bool>,
+    pub locked: Option<bool>, // thread roots only
+}
+
+// =============================================================================
+// COLLECTIONS
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Collection {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub visibility: String,
+    pub owner: String, // username
+    pub forked_from_id: Option<i64>,
+    pub item_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct CollectionItem {
+    pub id: i64,
+    pub kind: String,
+    pub artifact_id: i64,
+    pub position: i64,
+    pub note: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub added_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCollection {
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: Option<String>, // "private" (default) or "public"
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCollection {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddCollectionItem {
+    pub kind: String, // dataset, model, usecase, article or toolkit
+    pub id: i64,
+    pub note: Option<String>,
+}
+
+/// An omitted `note` is left unchanged; `null` or `""` clears it.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCollectionItem {
+    #[serde(default, deserialize_with = "present")]
+    pub note: Option<Option<String>>,
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from an omitted field (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderCollection {
+    pub item_ids: Vec<i64>, // every item in the collection, in the new order
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ForkCollection {
+    pub name: Option<String>,
+}
+
+// =============================================================================
+// DATASET FILES
+// =============================================================================
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DatasetFile {
+    pub id: i64,
+    pub dataset_id: i64,
+    pub name: String,
+    pub size_bytes: i64,
+    pub media_type: String,
+    pub sha256: String,
+    pub profile_status: String, // pending, succeeded, failed, unsupported
+    pub created_at: String,
+}
+
+/// Per-column statistics from profiling a file. `min`/`max` compare numbers
+/// numerically and everything else as text; booleans and `other` have none.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub data_type: String, // inferred, same values as DatasetColumn::data_type
+    pub null_count: i64,
+    pub null_ratio: f64,
+    pub distinct_count: i64,
+    pub distinct_capped: bool, // counting stopped at the cap; the real count is higher
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct FileProfile {
+    pub file_id: i64,
+    pub status: String,         // pending, succeeded, failed, unsupported
+    pub format: Option<String>, // csv, tsv, jsonl, parquet
+    pub row_count: Option<i64>,
+    #[sqlx(rename = "columns")]
+    #[serde(skip)]
+    pub columns_json: String,
+    #[sqlx(skip)]
+    pub columns: Vec<ColumnProfile>,
+    #[sqlx(rename = "sample_rows")]
+    #[serde(skip)]
+    pub sample_rows_json: String,
+    #[sqlx(skip)]
+    pub sample_rows: Vec<Vec<Option<String>>>, // aligned with `columns`
+    pub error: Option<String>,
+    pub profiled_at: Option<String>,
+}
+
+// =============================================================================
+// DATA DICTIONARY
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    String,
+    Integer,
+    Decimal,
+    Boolean,
+    Date,
+    Datetime,
+    Other,
+}
+
+impl ColumnType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColumnType::String => "string",
+            ColumnType::Integer => "integer",
+            ColumnType::Decimal => "decimal",
+            ColumnType::Boolean => "boolean",
+            ColumnType::Date => "date",
+            ColumnType::Datetime => "datetime",
+            ColumnType::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DatasetColumn {
+    pub id: i64,
+    pub dataset_id: i64,
+    pub position: i64,
+    pub name: String,
+    pub data_type: String, // string, integer, decimal, boolean, date, datetime, other
+    pub unit: Option<String>,
+    pub description: String,
+    pub nullable: bool,
+    #[sqlx(rename = "example_values")]
+    #[serde(skip)]
+    pub example_values_json: String,
+    #[sqlx(skip)]
+    pub example_values: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDatasetColumn {
+    pub name: String,
+    pub data_type: ColumnType,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+    pub nullable: Option<bool>,
+    pub example_values: Option<Vec<String>>,
+    pub position: Option<i64>, // default: after the last column
+}
+
+/// Omitted fields are left unchanged; an empty `unit` clears it.
+#[derive(Debug, Deserialize)]
+pub struct UpdateDatasetColumn {
+    pub name: Option<String>,
+    pub data_type: Option<ColumnType>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+    pub nullable: Option<bool>,
+    pub example_values: Option<Vec<String>>,
+    pub position: Option<i64>,
 }
 
 // =============================================================================
@@ -115,6 +1024,7 @@ pub struct Article {
     pub description: String,
     pub content: String,
     pub image_url: Option<String>,
+    pub likes_count: i64,
     pub author: String,
     pub read_time: Option<String>,
     pub category: Option<String>,
@@ -192,29 +1102,127 @@ pub struct Organization {
 
 // =============================================================================
 // DASHBOARD (computed per-user)
+//
+// Activity counts cover the selected window; the timeline, recently viewed,
+// contributions and recommendations don't. The heatmap is always the last
+// 30 days, oldest first.
 // =============================================================================
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DashboardWindow {
+    #[serde(rename = "7d")]
+    Week,
+    #[default]
+    #[serde(rename = "30d")]
+    Month,
+    #[serde(rename = "90d")]
+    Quarter,
+    #[serde(rename = "all")]
+    All,
+}
+
+impl DashboardWindow {
+    /// SQLite datetime modifier for the start of the window; None = all time.
+    pub fn modifier(self) -> Option<&'static str> {
+        match self {
+            DashboardWindow::Week => Some("-7 days"),
+            DashboardWindow::Month => Some("-30 days"),
+            DashboardWindow::Quarter => Some("-90 days"),
+            DashboardWindow::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DashboardQuery {
+    pub window: Option<DashboardWindow>, // 7d, 30d (default), 90d, all
+}
+
 #[derive(Debug, Serialize)]
 pub struct Dashboard {
     pub greeting: String,
     pub role: String,
     pub login_streak: i64,
-    pub artifacts_viewed: ArtifactCounts,
-    pub artifacts_downloaded: DownloadCounts,
     pub last_login: Option<String>,
+    pub window: DashboardWindow,
+    pub artifacts_viewed: ArtifactCounts,
+    pub artifacts_downloaded: ArtifactCounts,
+    pub artifacts_liked: ArtifactCounts,
+    pub contributions: Contributions,
+    pub timeline: Vec<ActivityEvent>,
+    pub recently_viewed: Vec<RecentView>,
+    pub recommended: Vec<Recommendation>,
+    pub heatmap: Vec<HeatmapDay>,
 }
 
-#[derive(Debug, Serialize)]
+/// Distinct entries per kind; kinds an action doesn't apply to stay 0.
+#[derive(Debug, Default, Serialize)]
 pub struct ArtifactCounts {
     pub datasets: i64,
     pub models: i64,
     pub use_cases: i64,
+    pub articles: i64,
 }
 
+/// Datasets the user uploaded and models they created, newest change first.
+/// There is no approval workflow, so visibility is the publication status.
 #[derive(Debug, Serialize)]
-pub struct DownloadCounts {
-    pub datasets: i64,
-    pub models: i64,
+pub struct Contributions {
+    pub total: i64,
+    pub open: i64,
+    pub restricted: i64,
+    pub private: i64,
+    pub items: Vec<Contribution>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Contribution {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub visibility: String,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
+    pub comment_count: i64,
+    pub views_count: i64,
+    pub downloads_count: i64,
+    pub likes_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ActivityEvent {
+    pub action: String, // viewed, downloaded, liked, reviewed, commented, contributed
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct RecentView {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub viewed_at: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Recommendation {
+    pub kind: String,
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+    pub sector: Option<String>,
+    pub reason: String, // sector_interest, or popular when there's no signal to go on
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub count: i64,
 }
 
 // =============================================================================
@@ -234,4 +1242,4 @@ pub struct PythonChatRequest {
 #[derive(Debug, Deserialize)]
 pub struct PythonChatResponse {
     pub answer: String,
-}
\ No newline at end of file
+}
@@ -0,0 +1,164 @@
+//! In-app notifications.
+//!
+//! Rows land in `notifications` from several writers: comment handlers (a
+//! reply to you, a comment on your entry), the saved-search checker, and
+//! `check_follows` below for entries published by what you follow. Writers
+//! call `Notifier::wake` for each recipient afterwards so open
+//! `/api/notifications/stream` connections fetch right away instead of
+//! waiting for their next poll.
+
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::errors::AppError;
+use crate::models::ArtifactKind;
+use crate::state::AppState;
+
+/// Wake-up channel from notification writers to SSE streams, keyed by user id.
+#[derive(Clone)]
+pub struct Notifier {
+    tx: broadcast::Sender<i64>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(256);
+        Self { tx }
+    }
+
+    /// Tell `user_id`'s open streams there is something new. No listeners is fine.
+    pub fn wake(&self, user_id: i64) {
+        let _ = self.tx.send(user_id);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<i64> {
+        self.tx.subscribe()
+    }
+}
+
+/// Wait until `user_id` is woken. A lagged receiver returns too, since the
+/// missed message may have been ours; a closed channel never returns.
+pub async fn woken(rx: &mut broadcast::Receiver<i64>, user_id: i64) {
+    loop {
+        match rx.recv().await {
+            Ok(id) if id == user_id => return,
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(_)) => return,
+            Err(broadcast::error::RecvError::Closed) => std::future::pending().await,
+        }
+    }
+}
+
+/// Insert one notification and wake its recipient.
+pub async fn notify(
+    db: &SqlitePool,
+    notifier: &Notifier,
+    user_id: i64,
+    kind: &str,
+    title: &str,
+    body: Option<&str>,
+    artifact: Option<(ArtifactKind, i64)>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, kind, title, body, artifact_kind, artifact_id)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(title)
+    .bind(body)
+    .bind(artifact.map(|(k, _)| k.as_str()))
+    .bind(artifact.map(|(_, id)| id))
+    .execute(db)
+    .await?;
+    notifier.wake(user_id);
+    Ok(())
+}
+
+/// Name of the followed source, for notification titles.
+const FOLLOW_SOURCE_NAME: &str = r#"
+    CASE f.target_kind
+        WHEN 'organization' THEN (SELECT name FROM organizations WHERE id = f.target_id)
+        WHEN 'sector'       THEN (SELECT name FROM sectors WHERE id = f.target_id)
+        WHEN 'tag'          THEN (SELECT name FROM tags WHERE id = f.target_id)
+        WHEN 'user'         THEN (SELECT full_name FROM users WHERE id = f.target_id)
+    END
+"#;
+
+/// Notify followers of `kind` entries published after they followed the
+/// source; returns the recipients, one row per notification created.
+fn follow_sql(kind: ArtifactKind) -> String {
+    let matches = match kind.tag_table() {
+        // Articles are untagged and only link to people, by author name.
+        None => "f.target_kind = 'user' AND a.author =
+                 (SELECT full_name FROM users WHERE id = f.target_id AND deleted_at IS NULL)"
+            .to_string(),
+        Some((tag_table, tag_fk)) => {
+            let owner = match kind {
+                ArtifactKind::Dataset => {
+                    "OR (f.target_kind = 'user' AND a.uploaded_by_user_id = f.target_id)"
+                }
+                ArtifactKind::Model => {
+                    "OR (f.target_kind = 'user' AND a.created_by_user_id = f.target_id)"
+                }
+                _ => "",
+            };
+            format!(
+                "(f.target_kind = 'organization' AND a.organization_id = f.target_id)
+                 OR (f.target_kind = 'sector' AND a.sector_id = f.target_id)
+                 OR (f.target_kind = 'tag' AND EXISTS (SELECT 1 FROM {tag_table} x
+                                                      WHERE x.{tag_fk} = a.id AND x.tag_id = f.target_id))
+                 {owner}"
+            )
+        }
+    };
+    format!(
+        r#"
+        INSERT OR IGNORE INTO notifications (user_id, kind, title, body, artifact_kind, artifact_id)
+        SELECT f.user_id, 'followed_activity',
+               'New {kind} from ' || COALESCE({FOLLOW_SOURCE_NAME}, 'something you follow'),
+               a.title, '{kind}', a.id
+        FROM follows f
+        JOIN {table} a ON a.deleted_at IS NULL AND a.created_at >= f.created_at
+        WHERE {matches}
+        ORDER BY a.id
+        RETURNING user_id
+        "#,
+        kind = kind.as_str(),
+        table = kind.table(),
+    )
+}
+
+/// Create follow-activity notifications; returns how many were created.
+pub async fn check_follows(db: &SqlitePool, notifier: &Notifier) -> Result<usize, AppError> {
+    let mut recipients = Vec::new();
+    for kind in ArtifactKind::ALL {
+        let users: Vec<i64> = sqlx::query_scalar(&follow_sql(kind)).fetch_all(db).await?;
+        recipients.extend(users);
+    }
+    let created = recipients.len();
+    recipients.sort_unstable();
+    recipients.dedup();
+    for user_id in recipients {
+        notifier.wake(user_id);
+    }
+    Ok(created)
+}
+
+/// Run `check_follows` every `follow_check_interval`.
+pub fn spawn_follow_checker(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.follow_check_interval);
+        loop {
+            ticker.tick().await;
+            match check_follows(&state.db, &state.notifier).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("created {n} follow notifications"),
+                Err(e) => tracing::error!("follow notification check failed: {e}"),
+            }
+        }
+    });
+}
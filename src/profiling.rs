@@ -0,0 +1,676 @@
+//! Profiling of uploaded tabular files.
+//!
+//! `spawn_profiler` picks up every dataset file without a profile, copies it
+//! out of storage and, for CSV, TSV, JSON Lines and Parquet, scans it once:
+//! row count, an inferred type per column, null and distinct counts, min/max
+//! and the first few rows. The result is stored in `dataset_file_profiles`
+//! and pre-fills the dataset's data dictionary (only names it doesn't have
+//! yet) and its `data_type` (only when empty). Other formats are recorded as
+//! unsupported so they aren't looked at again.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use futures::StreamExt;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use sqlx::{FromRow, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use crate::errors::AppError;
+use crate::handlers::validate_column_name;
+use crate::models::{ColumnProfile, ColumnType};
+use crate::state::AppState;
+use crate::storage::{Storage, TempFile};
+
+/// Files profiled per run.
+const BATCH_SIZE: i64 = 10;
+
+/// Storage fetches tried per file before it is recorded as failed.
+const MAX_FETCH_ATTEMPTS: i64 = 5;
+
+/// Wait after the first failed fetch; doubles with each further failure.
+const FETCH_RETRY_BASE: Duration = Duration::from_secs(60);
+
+/// Rows kept as samples.
+const SAMPLE_ROWS: usize = 5;
+
+/// Distinct values remembered per column before counting stops.
+const DISTINCT_CAP: usize = 10_000;
+
+/// Wider files are rejected rather than half-profiled.
+const MAX_COLUMNS: usize = 1000;
+
+/// Samples and min/max are cut to this many characters.
+const VALUE_CHARS: usize = 200;
+
+/// Example values copied into a pre-filled dictionary entry.
+const DICTIONARY_EXAMPLES: usize = 3;
+
+/// Text cells that mean "no value".
+const NULL_MARKERS: [&str; 4] = ["", "null", "na", "n/a"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Csv,
+    Tsv,
+    JsonLines,
+    Parquet,
+}
+
+impl Format {
+    /// By file extension, falling back to the upload's media type.
+    fn detect(name: &str, media_type: &str) -> Option<Self> {
+        let extension = name.rsplit_once('.').map(|(_, e)| e.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("csv") => return Some(Format::Csv),
+            Some("tsv" | "tab") => return Some(Format::Tsv),
+            Some("jsonl" | "ndjson") => return Some(Format::JsonLines),
+            Some("parquet") => return Some(Format::Parquet),
+            _ => {}
+        }
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        match essence.to_ascii_lowercase().as_str() {
+            "text/csv" => Some(Format::Csv),
+            "text/tab-separated-values" => Some(Format::Tsv),
+            "application/x-ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(Format::JsonLines)
+            }
+            "application/vnd.apache.parquet" | "application/x-parquet" => Some(Format::Parquet),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+            Format::JsonLines => "jsonl",
+            Format::Parquet => "parquet",
+        }
+    }
+
+    /// Value for the dataset's `data_type`, in the catalog's wording.
+    fn label(self) -> &'static str {
+        match self {
+            Format::Csv => "CSV",
+            Format::Tsv => "TSV",
+            Format::JsonLines => "JSON Lines",
+            Format::Parquet => "Parquet",
+        }
+    }
+}
+
+// =============================================================================
+// TYPE INFERENCE
+// =============================================================================
+
+/// A non-null value and the type it looks like.
+struct Cell {
+    text: String,
+    kind: ColumnType,
+}
+
+/// Classify a value that arrived as text (CSV/TSV fields, JSON strings).
+fn classify(raw: &str) -> Option<Cell> {
+    let text = raw.trim();
+    if NULL_MARKERS.iter().any(|m| text.eq_ignore_ascii_case(m)) {
+        return None;
+    }
+    // Zero-padded numbers ("007", district and PIN codes) are identifiers.
+    let zero_padded = text.len() > 1 && text.starts_with('0') && !text.starts_with("0.");
+    let kind = if text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false") {
+        ColumnType::Boolean
+    } else if !zero_padded && text.parse::<i64>().is_ok() {
+        ColumnType::Integer
+    } else if !zero_padded && text.parse::<f64>().is_ok_and(f64::is_finite) {
+        ColumnType::Decimal
+    } else if NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok() {
+        ColumnType::Date
+    } else if DateTime::parse_from_rfc3339(text).is_ok()
+        || NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").is_ok()
+        || NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S").is_ok()
+    {
+        ColumnType::Datetime
+    } else {
+        ColumnType::String
+    };
+    Some(Cell {
+        text: text.to_string(),
+        kind,
+    })
+}
+
+fn json_cell(value: serde_json::Value) -> Option<Cell> {
+    use serde_json::Value;
+    let (text, kind) = match value {
+        Value::Null => return None,
+        Value::String(s) => return classify(&s),
+        Value::Bool(b) => (b.to_string(), ColumnType::Boolean),
+        Value::Number(n) if n.is_f64() => (n.to_string(), ColumnType::Decimal),
+        Value::Number(n) => (n.to_string(), ColumnType::Integer),
+        nested => (nested.to_string(), ColumnType::Other),
+    };
+    Some(Cell { text, kind })
+}
+
+fn parquet_cell(field: &Field) -> Option<Cell> {
+    let (text, kind) = match field {
+        Field::Null => return None,
+        Field::Bool(b) => (b.to_string(), ColumnType::Boolean),
+        Field::Byte(v) => (v.to_string(), ColumnType::Integer),
+        Field::Short(v) => (v.to_string(), ColumnType::Integer),
+        Field::Int(v) => (v.to_string(), ColumnType::Integer),
+        Field::Long(v) => (v.to_string(), ColumnType::Integer),
+        Field::UByte(v) => (v.to_string(), ColumnType::Integer),
+        Field::UShort(v) => (v.to_string(), ColumnType::Integer),
+        Field::UInt(v) => (v.to_string(), ColumnType::Integer),
+        Field::ULong(v) => (v.to_string(), ColumnType::Integer),
+        Field::Float16(v) => (v.to_string(), ColumnType::Decimal),
+        Field::Float(v) => (v.to_string(), ColumnType::Decimal),
+        Field::Double(v) => (v.to_string(), ColumnType::Decimal),
+        Field::Decimal(_) => (field.to_string(), ColumnType::Decimal),
+        Field::Str(s) => (s.clone(), ColumnType::String),
+        Field::Date(days) => (
+            NaiveDate::from_num_days_from_ce_opt(days + 719_163)?
+                .format("%Y-%m-%d")
+                .to_string(),
+            ColumnType::Date,
+        ),
+        Field::TimestampMillis(ms) => (
+            DateTime::from_timestamp_millis(*ms)?
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            ColumnType::Datetime,
+        ),
+        Field::TimestampMicros(us) => (
+            DateTime::from_timestamp_micros(*us)?
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            ColumnType::Datetime,
+        ),
+        Field::Bytes(_) | Field::Group(_) | Field::ListInternal(_) | Field::MapInternal(_) => {
+            (field.to_string(), ColumnType::Other)
+        }
+    };
+    Some(Cell { text, kind })
+}
+
+/// The narrowest type covering both.
+fn widen(a: ColumnType, b: ColumnType) -> ColumnType {
+    use ColumnType::*;
+    match (a, b) {
+        _ if a == b => a,
+        (Integer, Decimal) | (Decimal, Integer) => Decimal,
+        (Date, Datetime) | (Datetime, Date) => Datetime,
+        (Other, _) | (_, Other) => Other,
+        _ => String,
+    }
+}
+
+fn clip(text: &str) -> String {
+    text.chars().take(VALUE_CHARS).collect()
+}
+
+// =============================================================================
+// PROFILER
+// =============================================================================
+
+struct ColumnStats {
+    name: String,
+    kind: Option<ColumnType>,
+    nulls: i64,
+    distinct: HashSet<String>,
+    capped: bool,
+    num_min: Option<(f64, String)>,
+    num_max: Option<(f64, String)>,
+    text_min: Option<String>,
+    text_max: Option<String>,
+}
+
+impl ColumnStats {
+    fn new(name: String, nulls: i64) -> Self {
+        Self {
+            name,
+            kind: None,
+            nulls,
+            distinct: HashSet::new(),
+            capped: false,
+            num_min: None,
+            num_max: None,
+            text_min: None,
+            text_max: None,
+        }
+    }
+
+    fn add(&mut self, cell: Option<Cell>) {
+        let Some(cell) = cell else {
+            self.nulls += 1;
+            return;
+        };
+        self.kind = Some(self.kind.map_or(cell.kind, |k| widen(k, cell.kind)));
+        if matches!(cell.kind, ColumnType::Integer | ColumnType::Decimal) {
+            if let Ok(n) = cell.text.parse::<f64>() {
+                if self.num_min.as_ref().is_none_or(|(m, _)| n < *m) {
+                    self.num_min = Some((n, cell.text.clone()));
+                }
+                if self.num_max.as_ref().is_none_or(|(m, _)| n > *m) {
+                    self.num_max = Some((n, cell.text.clone()));
+                }
+            }
+        }
+        if self.text_min.as_ref().is_none_or(|m| cell.text < *m) {
+            self.text_min = Some(cell.text.clone());
+        }
+        if self.text_max.as_ref().is_none_or(|m| cell.text > *m) {
+            self.text_max = Some(cell.text.clone());
+        }
+        if !self.capped && !self.distinct.contains(&cell.text) {
+            if self.distinct.len() < DISTINCT_CAP {
+                self.distinct.insert(cell.text);
+            } else {
+                self.capped = true;
+            }
+        }
+    }
+
+    fn finish(self, rows: i64) -> ColumnProfile {
+        let kind = self.kind.unwrap_or(ColumnType::String);
+        let (min, max) = match kind {
+            ColumnType::Integer | ColumnType::Decimal => {
+                (self.num_min.map(|(_, t)| t), self.num_max.map(|(_, t)| t))
+            }
+            ColumnType::Boolean | ColumnType::Other => (None, None),
+            _ => (self.text_min, self.text_max),
+        };
+        ColumnProfile {
+            name: self.name,
+            data_type: kind.as_str().to_string(),
+            null_count: self.nulls,
+            null_ratio: if rows > 0 {
+                self.nulls as f64 / rows as f64
+            } else {
+                0.0
+            },
+            distinct_count: self.distinct.len() as i64,
+            distinct_capped: self.capped,
+            min: min.as_deref().map(clip),
+            max: max.as_deref().map(clip),
+        }
+    }
+}
+
+pub struct Profile {
+    pub row_count: i64,
+    pub columns: Vec<ColumnProfile>,
+    pub sample_rows: Vec<Vec<Option<String>>>,
+}
+
+#[derive(Default)]
+struct Profiler {
+    columns: Vec<ColumnStats>,
+    index: HashMap<String, usize>,
+    rows: i64,
+    samples: Vec<Vec<Option<String>>>,
+}
+
+impl Profiler {
+    /// Position of column `name`, adding it (null in every earlier row) if new.
+    fn column(&mut self, name: &str) -> Result<usize, String> {
+        if let Some(&i) = self.index.get(name) {
+            return Ok(i);
+        }
+        if self.columns.len() == MAX_COLUMNS {
+            return Err(format!("more than {MAX_COLUMNS} columns"));
+        }
+        self.columns
+            .push(ColumnStats::new(name.to_string(), self.rows));
+        self.index.insert(name.to_string(), self.columns.len() - 1);
+        Ok(self.columns.len() - 1)
+    }
+
+    /// Add a column for a CSV header or JSON key: trimmed, `column_{n}` if
+    /// blank, and never merged with an earlier one. Repeats are suffixed with
+    /// their position (or the next free number, if another already took that
+    /// name).
+    fn header(&mut self, header: &str) -> Result<usize, String> {
+        let position = self.columns.len() + 1;
+        let name = match header.trim() {
+            "" => format!("column_{position}"),
+            name => name.to_string(),
+        };
+        let mut unique = name.clone();
+        let mut n = position;
+        while self.index.contains_key(&unique) {
+            unique = format!("{name}_{n}");
+            n += 1;
+        }
+        self.column(&unique)
+    }
+
+    /// One row, aligned with the columns; missing trailing cells are null.
+    fn push(&mut self, mut cells: Vec<Option<Cell>>) {
+        cells.resize_with(self.columns.len(), || None);
+        if self.samples.len() < SAMPLE_ROWS {
+            self.samples.push(
+                cells
+                    .iter()
+                    .map(|c| c.as_ref().map(|c| clip(&c.text)))
+                    .collect(),
+            );
+        }
+        for (stats, cell) in self.columns.iter_mut().zip(cells) {
+            stats.add(cell);
+        }
+        self.rows += 1;
+    }
+
+    fn finish(self) -> Profile {
+        let width = self.columns.len();
+        let rows = self.rows;
+        Profile {
+            row_count: rows,
+            columns: self.columns.into_iter().map(|c| c.finish(rows)).collect(),
+            sample_rows: self
+                .samples
+                .into_iter()
+                .map(|mut r| {
+                    r.resize(width, None);
+                    r
+                })
+                .collect(),
+        }
+    }
+}
+
+fn profile_delimited(path: &Path, delimiter: u8) -> Result<Profile, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_path(path)
+        .map_err(|e| e.to_string())?;
+    let mut profiler = Profiler::default();
+    let headers = reader.byte_headers().map_err(|e| e.to_string())?.clone();
+    for header in headers.iter() {
+        profiler.header(&String::from_utf8_lossy(header))?;
+    }
+    for record in reader.byte_records() {
+        let record = record.map_err(|e| e.to_string())?;
+        profiler.push(
+            record
+                .iter()
+                .map(|field| classify(&String::from_utf8_lossy(field)))
+                .collect(),
+        );
+    }
+    Ok(profiler.finish())
+}
+
+fn profile_json_lines(path: &Path) -> Result<Profile, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut profiler = Profiler::default();
+    // Keys are named like CSV headers; each distinct key keeps its column.
+    let mut keys: HashMap<String, usize> = HashMap::new();
+    for (n, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let serde_json::Value::Object(object) =
+            serde_json::from_str(&line).map_err(|e| format!("line {}: {e}", n + 1))?
+        else {
+            return Err(format!("line {}: expected a JSON object", n + 1));
+        };
+        let mut cells = Vec::new();
+        for (key, value) in object {
+            let i = match keys.get(&key) {
+                Some(&i) => i,
+                None => {
+                    let i = profiler.header(&key)?;
+                    keys.insert(key, i);
+                    i
+                }
+            };
+            if cells.len() <= i {
+                cells.resize_with(i + 1, || None);
+            }
+            cells[i] = json_cell(value);
+        }
+        profiler.push(cells);
+    }
+    Ok(profiler.finish())
+}
+
+fn profile_parquet(path: &Path) -> Result<Profile, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let mut profiler = Profiler::default();
+    for field in reader.metadata().file_metadata().schema().get_fields() {
+        profiler.column(field.name())?;
+    }
+    for row in reader.get_row_iter(None).map_err(|e| e.to_string())? {
+        let row = row.map_err(|e| e.to_string())?;
+        profiler.push(
+            row.get_column_iter()
+                .map(|(_, f)| parquet_cell(f))
+                .collect(),
+        );
+    }
+    Ok(profiler.finish())
+}
+
+fn profile_path(path: &Path, format: Format) -> Result<Profile, String> {
+    match format {
+        Format::Csv => profile_delimited(path, b','),
+        Format::Tsv => profile_delimited(path, b'\t'),
+        Format::JsonLines => profile_json_lines(path),
+        Format::Parquet => profile_parquet(path),
+    }
+}
+
+/// Copy `key` out of storage into a temp file and profile it off the runtime.
+/// The outer error is a fetch failure worth retrying; the inner one means the
+/// file itself couldn't be parsed.
+async fn profile_object(
+    storage: &dyn Storage,
+    key: &str,
+    format: Format,
+) -> Result<Result<Profile, String>, AppError> {
+    let tmp = TempFile::new();
+    let mut stream = storage.get(key, None).await?;
+    let mut out = tokio::fs::File::create(&tmp.path).await?;
+    while let Some(chunk) = stream.next().await {
+        out.write_all(&chunk?).await?;
+    }
+    out.flush().await?;
+    let path = tmp.path.clone();
+    Ok(
+        tokio::task::spawn_blocking(move || profile_path(&path, format))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string())),
+    )
+}
+
+// =============================================================================
+// JOB
+// =============================================================================
+
+#[derive(FromRow)]
+struct PendingFile {
+    id: i64,
+    dataset_id: i64,
+    name: String,
+    media_type: String,
+    storage_key: String,
+    profile_attempts: i64,
+}
+
+/// Record the outcome for `file_id`, unless the file was deleted meanwhile.
+async fn save(
+    db: &SqlitePool,
+    file_id: i64,
+    status: &str,
+    format: Option<Format>,
+    profile: Option<&Profile>,
+    error: Option<&str>,
+) -> Result<(), AppError> {
+    let columns = profile
+        .map(|p| serde_json::to_string(&p.columns))
+        .transpose()?;
+    let samples = profile
+        .map(|p| serde_json::to_string(&p.sample_rows))
+        .transpose()?;
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO dataset_file_profiles
+            (file_id, status, format, row_count, columns, sample_rows, error)
+        SELECT ?1, ?2, ?3, ?4, COALESCE(?5, '[]'), COALESCE(?6, '[]'), ?7
+        WHERE EXISTS (SELECT 1 FROM dataset_files WHERE id = ?1)
+        "#,
+    )
+    .bind(file_id)
+    .bind(status)
+    .bind(format.map(Format::as_str))
+    .bind(profile.map(|p| p.row_count))
+    .bind(columns)
+    .bind(samples)
+    .bind(error)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Add dictionary entries for profiled columns the dataset doesn't describe
+/// yet, and set its `data_type` if it has none. Curated entries are kept, and
+/// names the dictionary endpoints would reject are skipped.
+async fn prefill(
+    db: &SqlitePool,
+    dataset_id: i64,
+    format: Format,
+    profile: &Profile,
+) -> Result<(), AppError> {
+    let mut tx = db.begin().await?;
+    for (i, column) in profile.columns.iter().enumerate() {
+        let Ok(name) = validate_column_name(&column.name) else {
+            continue;
+        };
+        let mut examples: Vec<&str> = Vec::new();
+        for value in profile.sample_rows.iter().filter_map(|r| r[i].as_deref()) {
+            if examples.len() < DICTIONARY_EXAMPLES && !examples.contains(&value) {
+                examples.push(value);
+            }
+        }
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO dataset_columns
+                (dataset_id, position, name, data_type, nullable, example_values)
+            VALUES (?1,
+                    (SELECT COALESCE(MAX(position), 0) + 1
+                     FROM dataset_columns WHERE dataset_id = ?1),
+                    ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(dataset_id)
+        .bind(name)
+        .bind(&column.data_type)
+        .bind(column.null_count > 0)
+        .bind(serde_json::to_string(&examples)?)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        r#"
+        UPDATE datasets SET data_type = ?2
+        WHERE id = ?1 AND (data_type IS NULL OR trim(data_type) = '')
+        "#,
+    )
+    .bind(dataset_id)
+    .bind(format.label())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Profile files that have no profile yet; returns how many were profiled.
+/// A file that can't be parsed is recorded as failed and not retried; one that
+/// couldn't be fetched from storage is retried with backoff, behind files that
+/// haven't failed, and recorded as failed after `MAX_FETCH_ATTEMPTS`.
+pub async fn profile_pending(db: &SqlitePool, storage: &dyn Storage) -> Result<u64, AppError> {
+    let files = sqlx::query_as::<_, PendingFile>(
+        r#"
+        SELECT f.id, f.dataset_id, f.name, f.media_type, f.storage_key, f.profile_attempts
+        FROM dataset_files f
+        LEFT JOIN dataset_file_profiles p ON p.file_id = f.id
+        WHERE p.file_id IS NULL
+          AND (f.profile_retry_at IS NULL OR f.profile_retry_at <= datetime('now'))
+        ORDER BY f.profile_attempts, f.id
+        LIMIT ?1
+        "#,
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(db)
+    .await?;
+
+    let mut profiled = 0;
+    for f in files {
+        let Some(format) = Format::detect(&f.name, &f.media_type) else {
+            save(db, f.id, "unsupported", None, None, None).await?;
+            continue;
+        };
+        match profile_object(storage, &f.storage_key, format).await {
+            Ok(Ok(profile)) => {
+                save(db, f.id, "succeeded", Some(format), Some(&profile), None).await?;
+                prefill(db, f.dataset_id, format, &profile).await?;
+                profiled += 1;
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("profiling dataset file {} failed: {e}", f.id);
+                save(db, f.id, "failed", Some(format), None, Some(&e)).await?;
+            }
+            Err(e) => {
+                let attempts = f.profile_attempts + 1;
+                tracing::warn!(
+                    "dataset file {} not fetched for profiling (attempt {attempts}): {e}",
+                    f.id
+                );
+                if attempts >= MAX_FETCH_ATTEMPTS {
+                    let error = format!("could not fetch the file from storage: {e}");
+                    save(db, f.id, "failed", Some(format), None, Some(&error)).await?;
+                }
+                let delay = FETCH_RETRY_BASE
+                    .saturating_mul(2u32.saturating_pow(u32::try_from(attempts - 1).unwrap_or(0)));
+                sqlx::query(
+                    r#"
+                    UPDATE dataset_files
+                    SET profile_attempts = ?2, profile_retry_at = datetime('now', ?3)
+                    WHERE id = ?1
+                    "#,
+                )
+                .bind(f.id)
+                .bind(attempts)
+                .bind(format!("+{} seconds", delay.as_secs()))
+                .execute(db)
+                .await?;
+            }
+        }
+    }
+    Ok(profiled)
+}
+
+/// Profile new uploads every `profile_check_interval`.
+pub fn spawn_profiler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.profile_check_interval);
+        loop {
+            ticker.tick().await;
+            match profile_pending(&state.db, state.storage.as_ref()).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("profiled {n} dataset files"),
+                Err(e) => tracing::error!("file profiling failed: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,140 @@
+//! "See also" recommendations for a single catalog entry.
+//!
+//! Candidates of every requested kind are scored against the source on
+//! shared tags, same sector / organization, and embedding similarity (from
+//! the `embeddings` table), so a dataset can surface the models trained on
+//! it and the use cases built on those models.
+
+use sqlx::{FromRow, SqlitePool};
+
+use crate::embeddings::{self, Embedder};
+use crate::errors::AppError;
+use crate::models::{ArtifactKind, RelatedItem};
+
+/// Score weights; they sum to 1.0 so a perfect match on every signal scores 1.0.
+const TAG_WEIGHT: f64 = 0.4;
+const SECTOR_WEIGHT: f64 = 0.15;
+const ORG_WEIGHT: f64 = 0.1;
+const SIMILARITY_WEIGHT: f64 = 0.35;
+
+#[derive(FromRow)]
+struct Candidate {
+    id: i64,
+    title: String,
+    description: String,
+    image_url: Option<String>,
+    shared_tags: i64,
+    same_sector: bool,
+    same_organization: bool,
+}
+
+/// Candidate rows of `kind` with their overlap against the source. Binds:
+/// ?1 source tag ids (JSON array), ?2 source sector_id, ?3 source organization_id.
+fn candidates_sql(kind: ArtifactKind) -> String {
+    let shared = match kind.tag_table() {
+        Some((table, fk)) => format!(
+            "(SELECT COUNT(*) FROM {table} x
+               WHERE x.{fk} = c.id AND x.tag_id IN (SELECT value FROM json_each(?1)))"
+        ),
+        None => "0".to_string(),
+    };
+    let (sector, org) = match kind {
+        ArtifactKind::Article => ("0", "0"),
+        _ => (
+            "COALESCE(c.sector_id = ?2, 0)",
+            "COALESCE(c.organization_id = ?3, 0)",
+        ),
+    };
+    format!(
+        r#"
+        SELECT c.id, c.title, c.description, c.image_url,
+               {shared} AS shared_tags,
+               {sector} AS same_sector,
+               {org} AS same_organization
+        FROM {table} c
+        WHERE c.deleted_at IS NULL
+        "#,
+        table = kind.table(),
+    )
+}
+
+/// Artifacts of `kinds` related to (`kind`, `id`), best first.
+/// `kind` must be a tagged catalog kind (dataset, model or usecase).
+pub async fn related(
+    db: &SqlitePool,
+    embedder: &dyn Embedder,
+    kind: ArtifactKind,
+    id: i64,
+    kinds: &[ArtifactKind],
+    limit: usize,
+) -> Result<Vec<RelatedItem>, AppError> {
+    let Some((tag_table, tag_fk)) = kind.tag_table() else {
+        return Err(AppError::NotFound);
+    };
+
+    let source: (Option<i64>, Option<i64>) = sqlx::query_as(&format!(
+        "SELECT sector_id, organization_id FROM {} WHERE id = ?1 AND deleted_at IS NULL",
+        kind.table()
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let tag_ids: Vec<i64> = sqlx::query_scalar(&format!(
+        "SELECT tag_id FROM {tag_table} WHERE {tag_fk} = ?1"
+    ))
+    .bind(id)
+    .fetch_all(db)
+    .await?;
+
+    let similarity = embeddings::similar_to(db, embedder, kind, id).await?;
+
+    let mut items = Vec::new();
+    for &candidate_kind in kinds {
+        let rows = sqlx::query_as::<_, Candidate>(&candidates_sql(candidate_kind))
+            .bind(serde_json::json!(tag_ids).to_string())
+            .bind(source.0)
+            .bind(source.1)
+            .fetch_all(db)
+            .await?;
+
+        for row in rows {
+            if (candidate_kind, row.id) == (kind, id) {
+                continue;
+            }
+            let sim = similarity
+                .get(&(candidate_kind, row.id))
+                .copied()
+                .unwrap_or(0.0);
+            let tag_overlap = if tag_ids.is_empty() {
+                0.0
+            } else {
+                row.shared_tags as f64 / tag_ids.len() as f64
+            };
+            let score = TAG_WEIGHT * tag_overlap
+                + SECTOR_WEIGHT * f64::from(u8::from(row.same_sector))
+                + ORG_WEIGHT * f64::from(u8::from(row.same_organization))
+                + SIMILARITY_WEIGHT * sim;
+            if score <= 0.0 {
+                continue;
+            }
+            items.push(RelatedItem {
+                kind: candidate_kind,
+                id: row.id,
+                title: row.title,
+                description: row.description,
+                image_url: row.image_url,
+                score,
+                shared_tags: row.shared_tags,
+                same_sector: row.same_sector,
+                same_organization: row.same_organization,
+                similarity: sim,
+            });
+        }
+    }
+
+    items.sort_by(|a, b| b.score.total_cmp(&a.score));
+    items.truncate(limit);
+    Ok(items)
+}
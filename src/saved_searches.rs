@@ -0,0 +1,174 @@
+//! Saved-search alerts.
+//!
+//! `spawn_checker` periodically re-runs every saved search against artifacts
+//! created since its `last_checked_at` and records one notification per new
+//! match for the owner. The check window overlaps the previous one by design;
+//! the unique index on (saved_search_id, artifact) keeps alerts from repeating.
+
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+
+use crate::errors::AppError;
+use crate::models::ArtifactKind;
+use crate::notifications::Notifier;
+use crate::search::{SearchIndex, SearchQuery, Synonyms};
+use crate::state::AppState;
+
+#[derive(FromRow)]
+struct PendingSearch {
+    id: i64,
+    user_id: i64,
+    name: String,
+    kind: String,
+    search: Option<String>,
+    sector: Option<String>,
+    organization_id: Option<i64>,
+    last_checked_at: String,
+}
+
+/// Insert notifications for artifacts of `kind` matching a saved search.
+/// Binds: ?1 saved search id, ?2 owner, ?3 notification title, ?4 window start,
+/// ?5 FTS expression (NULL = no text terms), ?6 sector slug, ?7 organization id,
+/// ?8/?9/?10 qualifier filters (sectors / orgs / tags as JSON arrays),
+/// ?11 data dictionary column names (datasets only; NULL for other kinds).
+fn notify_sql(kind: ArtifactKind) -> Option<String> {
+    let (tag_table, tag_fk) = kind.tag_table()?;
+    let columns = match kind {
+        ArtifactKind::Dataset => {
+            r#"NOT EXISTS (SELECT 1 FROM json_each(?11) j
+                          WHERE NOT EXISTS (SELECT 1 FROM dataset_columns c
+                                            WHERE c.dataset_id = a.id
+                                              AND c.name = j.value COLLATE NOCASE))"#
+        }
+        _ => "?11 IS NULL",
+    };
+    Some(format!(
+        r#"
+        INSERT OR IGNORE INTO notifications
+            (user_id, kind, title, body, artifact_kind, artifact_id, saved_search_id)
+        SELECT ?2, 'saved_search_match', ?3, a.title, '{kind}', a.id, ?1
+        FROM {table} a
+        LEFT JOIN organizations o ON o.id = a.organization_id
+        LEFT JOIN sectors s       ON s.id = a.sector_id
+        WHERE a.deleted_at IS NULL
+          AND a.created_at >= ?4
+          AND (?5 IS NULL OR a.id IN (SELECT rowid FROM {fts} WHERE {fts} MATCH ?5))
+          AND (?6 IS NULL OR s.slug = ?6)
+          AND (?7 IS NULL OR a.organization_id = ?7)
+          AND (?8 IS NULL OR EXISTS (SELECT 1 FROM json_each(?8) j
+                               WHERE s.slug = j.value OR s.slug LIKE j.value || '-%'))
+          AND (?9 IS NULL OR EXISTS (SELECT 1 FROM json_each(?9) j
+                               WHERE o.slug = j.value OR o.slug LIKE j.value || '-%'))
+          AND NOT EXISTS (SELECT 1 FROM json_each(?10) j
+                          WHERE NOT EXISTS (SELECT 1 FROM {tag_table} x JOIN tags t ON t.id = x.tag_id
+                                            WHERE x.{tag_fk} = a.id AND t.slug = j.value))
+          AND {columns}
+        ORDER BY a.id
+        "#,
+        kind = kind.as_str(),
+        table = kind.table(),
+        fts = kind.fts_table(),
+    ))
+}
+
+/// Run every saved search once; returns the number of notifications created.
+pub async fn check(db: &SqlitePool, notifier: &Notifier) -> Result<u64, AppError> {
+    let (started_at,): (String,) = sqlx::query_as("SELECT datetime('now')")
+        .fetch_one(db)
+        .await?;
+    let searches = sqlx::query_as::<_, PendingSearch>(
+        r#"
+        SELECT id, user_id, name, kind, search, sector, organization_id, last_checked_at
+        FROM saved_searches
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+    let synonyms = Synonyms::load(db).await?;
+
+    let mut created = 0;
+    for s in searches {
+        // One bad search (or a failed write) must not hold up everyone else's.
+        match check_one(db, &s, &synonyms, &started_at).await {
+            Ok(n) => {
+                if n > 0 {
+                    notifier.wake(s.user_id);
+                }
+                created += n;
+            }
+            Err(e) => tracing::error!("saved search {} check failed: {e}", s.id),
+        }
+    }
+    Ok(created)
+}
+
+/// Run one saved search over its window and advance `last_checked_at` to
+/// `started_at`; returns the number of notifications created.
+async fn check_one(
+    db: &SqlitePool,
+    s: &PendingSearch,
+    synonyms: &Synonyms,
+    started_at: &str,
+) -> Result<u64, AppError> {
+    let Some((kind, index, sql)) = s
+        .kind
+        .parse::<ArtifactKind>()
+        .ok()
+        .and_then(|k| Some((k, SearchIndex::for_kind(k)?, notify_sql(k)?)))
+    else {
+        return Ok(0);
+    };
+    // Queries are validated on save; a parser change could still reject an
+    // old one. Matching on its filters alone would flood the owner with
+    // alerts, so skip it (leaving the window open) until the query is fixed.
+    let query = match s.search.as_deref().map(SearchQuery::parse).transpose() {
+        Ok(q) => q,
+        Err(e) => {
+            tracing::warn!("skipping saved search {} with an invalid query: {e}", s.id);
+            return Ok(0);
+        }
+    };
+    let filters = query.as_ref().map(SearchQuery::filters).unwrap_or_default();
+    let fts = query
+        .filter(SearchQuery::has_terms)
+        .map(|q| q.fts_expression(index, synonyms));
+
+    let mut tx = db.begin().await?;
+    let result = sqlx::query(&sql)
+        .bind(s.id)
+        .bind(s.user_id)
+        .bind(format!("New {} matches \"{}\"", kind.as_str(), s.name))
+        .bind(&s.last_checked_at)
+        .bind(fts)
+        .bind(s.sector.as_deref())
+        .bind(s.organization_id)
+        .bind(filters.sectors.as_deref())
+        .bind(filters.orgs.as_deref())
+        .bind(filters.tags.as_deref())
+        .bind(filters.columns.as_deref())
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE saved_searches SET last_checked_at = ?1 WHERE id = ?2")
+        .bind(started_at)
+        .bind(s.id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected())
+}
+
+/// Run `check` every `saved_search_check_interval`.
+pub fn spawn_checker(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.saved_search_check_interval);
+        loop {
+            ticker.tick().await;
+            match check(&state.db, &state.notifier).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("created {n} saved-search notifications"),
+                Err(e) => tracing::error!("saved search check failed: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,882 @@
+//! Search query parsing and FTS5 query building.
+//!
+//! User input never reaches FTS5 verbatim. `SearchQuery::parse` understands
+//!
+//!     title:aadhaar tag:census org:indiaai sector:healthcare -synthetic "exact phrase" a OR b
+//!
+//! and splits it into an FTS5 MATCH expression (quoted terms and phrases,
+//! column filters, OR, NOT) plus SQL filters for the tag / org / sector
+//! qualifiers and the datasets-only `column:` qualifier, which matches names
+//! in the data dictionary. Other words containing a colon (`time:series`,
+//! `3:1`, URLs) are plain text. Bare words are OR-ed with their synonyms from
+//! `search_synonyms`. When a search matches nothing, words are compared
+//! against the index vocabulary (`*_fts_vocab`) by edit distance and the
+//! search is retried once with the corrected query.
+
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::errors::AppError;
+use crate::models::ArtifactKind;
+
+/// Longest synonym phrase (in words) looked for in the query.
+const MAX_SYNONYM_WORDS: usize = 3;
+
+/// Tokens shorter than this are never spell-corrected.
+const MIN_CORRECTABLE_LEN: usize = 3;
+
+const MAX_QUERY_LEN: usize = 500;
+const MAX_QUERY_CLAUSES: usize = 32;
+
+/// FTS index a search runs against.
+#[derive(Debug, Clone, Copy)]
+pub enum SearchIndex {
+    Datasets,
+    Models,
+    UseCases,
+}
+
+impl SearchIndex {
+    /// Index for a searchable catalog kind; articles aren't list-searchable.
+    pub fn for_kind(kind: ArtifactKind) -> Option<Self> {
+        match kind {
+            ArtifactKind::Dataset => Some(SearchIndex::Datasets),
+            ArtifactKind::Model => Some(SearchIndex::Models),
+            ArtifactKind::UseCase => Some(SearchIndex::UseCases),
+            ArtifactKind::Article => None,
+        }
+    }
+
+    fn vocab_table(self) -> &'static str {
+        match self {
+            SearchIndex::Datasets => "datasets_fts_vocab",
+            SearchIndex::Models => "models_fts_vocab",
+            SearchIndex::UseCases => "usecases_fts_vocab",
+        }
+    }
+
+    fn about_column(self) -> &'static str {
+        match self {
+            SearchIndex::Datasets => "about_dataset",
+            SearchIndex::Models => "about_model",
+            SearchIndex::UseCases => "about_use_case",
+        }
+    }
+}
+
+/// Wrap a term in double quotes, escaping embedded quotes.
+/// Multi-word terms become FTS5 phrases.
+fn quote(term: &str) -> String {
+    format!("\"{}\"", term.replace('"', "\"\""))
+}
+
+/// Lowercase a token and strip surrounding punctuation for dictionary lookups.
+fn normalize(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Lowercase, hyphen-separated form used to compare qualifier values with slugs.
+pub fn slugify(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|p| !p.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Convert a free-form user search string into an FTS5 MATCH expression.
+/// Splits on whitespace, wraps each token in double quotes, joins with AND.
+/// "AI agriculture" -> r#""AI" AND "agriculture""#
+/// This is safe against FTS5 syntax injection (e.g., user typing `"OR"` or `-`).
+pub fn build_fts_query(input: &str) -> String {
+    input
+        .split_whitespace()
+        .filter(|t| !t.is_empty())
+        .map(quote)
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Like `build_fts_query`, but joined with OR so partial matches still rank
+/// (used where bm25 is one signal among several).
+pub fn build_fts_any_query(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(quote)
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+/// Like `build_fts_query`, but the last token is a prefix match for autocomplete.
+/// "crop yi" -> r#""crop" AND "yi"*"#
+pub fn build_fts_prefix_query(input: &str) -> String {
+    let mut query = build_fts_query(input);
+    if !query.is_empty() {
+        query.push('*');
+    }
+    query
+}
+
+// =============================================================================
+// SYNONYMS
+// =============================================================================
+
+/// Bidirectional synonym lookup keyed by normalized term.
+pub struct Synonyms(HashMap<String, Vec<String>>);
+
+impl Synonyms {
+    pub async fn load(db: &SqlitePool) -> Result<Self, AppError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT term, synonym FROM search_synonyms")
+                .fetch_all(db)
+                .await?;
+
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (term, synonym) in rows {
+            map.entry(term.clone()).or_default().push(synonym.clone());
+            map.entry(synonym).or_default().push(term);
+        }
+        Ok(Self(map))
+    }
+
+    fn contains(&self, term: &str) -> bool {
+        self.0.contains_key(term)
+    }
+
+    /// `term` quoted and OR-ed with its synonyms, if it has any.
+    fn expand_term(&self, term: &str) -> String {
+        let key = term
+            .split_whitespace()
+            .map(normalize)
+            .collect::<Vec<_>>()
+            .join(" ");
+        match self.0.get(&key) {
+            Some(alts) => {
+                let mut terms = vec![quote(&key)];
+                terms.extend(alts.iter().map(|a| quote(a)));
+                format!("({})", terms.join(" OR "))
+            }
+            None => quote(term),
+        }
+    }
+
+    /// Longest run of leading `words` that is a known term, with its synonyms.
+    fn longest_match(&self, words: &[&str]) -> Option<(usize, &[String])> {
+        (1..=words.len().min(MAX_SYNONYM_WORDS))
+            .rev()
+            .find_map(|n| {
+                let phrase = words[..n]
+                    .iter()
+                    .map(|t| normalize(t))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.0.get(&phrase).map(|alts| (n, alts.as_slice()))
+            })
+    }
+
+    /// One FTS5 group per word, except that multi-word synonym phrases
+    /// ("machine learning") collapse into a single group.
+    fn expand_words(&self, words: &[&str]) -> Vec<String> {
+        let mut groups = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            match self.longest_match(&words[i..]) {
+                Some((n, _)) => {
+                    groups.push(self.expand_term(&words[i..i + n].join(" ")));
+                    i += n;
+                }
+                None => {
+                    groups.push(quote(words[i]));
+                    i += 1;
+                }
+            }
+        }
+        groups
+    }
+
+    /// Build an FTS5 MATCH expression, OR-ing each word (or phrase) with its synonyms.
+    /// "ML crops" -> r#"("ml" OR "machine learning") AND "crops""#
+    pub fn expand(&self, input: &str) -> String {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        self.expand_words(&words).join(" AND ")
+    }
+}
+
+// =============================================================================
+// QUERY PARSER
+// =============================================================================
+
+/// FTS column a `title:` / `description:` / `about:` qualifier restricts to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Title,
+    Description,
+    About,
+}
+
+impl Column {
+    fn name(self) -> &'static str {
+        match self {
+            Column::Title => "title",
+            Column::Description => "description",
+            Column::About => "about",
+        }
+    }
+
+    fn fts_name(self, index: SearchIndex) -> &'static str {
+        match self {
+            Column::Title => "title",
+            Column::Description => "description",
+            Column::About => index.about_column(),
+        }
+    }
+}
+
+/// SQL-side qualifiers; matched against slugs (or data dictionary column
+/// names) rather than the FTS index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Filter {
+    Tag,
+    Org,
+    Sector,
+    Column,
+}
+
+impl Filter {
+    fn name(self) -> &'static str {
+        match self {
+            Filter::Tag => "tag",
+            Filter::Org => "org",
+            Filter::Sector => "sector",
+            Filter::Column => "column",
+        }
+    }
+}
+
+/// A recognised `name:` prefix.
+#[derive(Debug, Clone, Copy)]
+enum Qualifier {
+    Column(Column),
+    Filter(Filter),
+}
+
+impl Qualifier {
+    fn parse(name: &str) -> Option<Self> {
+        let qualifier = match name.to_lowercase().as_str() {
+            "title" => Qualifier::Column(Column::Title),
+            "description" => Qualifier::Column(Column::Description),
+            "about" => Qualifier::Column(Column::About),
+            "tag" | "tags" => Qualifier::Filter(Filter::Tag),
+            "org" | "organization" => Qualifier::Filter(Filter::Org),
+            "sector" => Qualifier::Filter(Filter::Sector),
+            "column" | "col" => Qualifier::Filter(Filter::Column),
+            _ => return None,
+        };
+        Some(qualifier)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Clause {
+    Or,
+    Term {
+        column: Option<Column>,
+        text: String,
+        phrase: bool,
+        negated: bool,
+    },
+    Filter {
+        filter: Filter,
+        value: String,
+    },
+}
+
+impl Clause {
+    fn is_positive_term(&self) -> bool {
+        matches!(self, Clause::Term { negated: false, .. })
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Clause::Or => "OR".to_string(),
+            Clause::Term {
+                column,
+                text,
+                phrase,
+                negated,
+            } => {
+                let mut out = String::new();
+                if *negated {
+                    out.push('-');
+                }
+                if let Some(c) = column {
+                    out.push_str(c.name());
+                    out.push(':');
+                }
+                if *phrase {
+                    out.push_str(&format!("\"{text}\""));
+                } else {
+                    out.push_str(text);
+                }
+                out
+            }
+            Clause::Filter { filter, value } if value.contains(char::is_whitespace) => {
+                format!("{}:\"{value}\"", filter.name())
+            }
+            Clause::Filter { filter, value } => format!("{}:{value}", filter.name()),
+        }
+    }
+}
+
+/// SQL filters extracted from qualifiers, as JSON arrays of slugs ready to
+/// bind against `json_each(?)`. `None` means "no constraint".
+#[derive(Debug, Default)]
+pub struct QueryFilters {
+    pub tags: Option<String>,
+    pub orgs: Option<String>,
+    pub sectors: Option<String>,
+    /// Lowercased column names rather than slugs; datasets only.
+    pub columns: Option<String>,
+}
+
+/// A parsed user search string.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    clauses: Vec<Clause>,
+}
+
+fn invalid(msg: impl Into<String>) -> AppError {
+    AppError::ValidationError(msg.into())
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Result<Self, AppError> {
+        if input.chars().count() > MAX_QUERY_LEN {
+            return Err(invalid(format!(
+                "Search query is too long (max {MAX_QUERY_LEN} characters)"
+            )));
+        }
+
+        let chars: Vec<char> = input.chars().collect();
+        let mut clauses = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i].is_whitespace() {
+                i += 1;
+                continue;
+            }
+
+            let negated = chars[i] == '-' && chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+            if negated {
+                i += 1;
+            }
+
+            if chars[i] == '"' {
+                let (text, next) = read_phrase(&chars, i)?;
+                i = next;
+                clauses.push(Clause::Term {
+                    column: None,
+                    text,
+                    phrase: true,
+                    negated,
+                });
+                continue;
+            }
+
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '"' {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+
+            if word == "OR" && !negated {
+                clauses.push(Clause::Or);
+                continue;
+            }
+            if word == "AND" && !negated {
+                continue;
+            }
+
+            // Only known qualifiers are special; `time:series`, `3:1` or a URL
+            // are searched as ordinary text.
+            let qualified = word
+                .split_once(':')
+                .and_then(|(name, rest)| Some((name, Qualifier::parse(name)?, rest)));
+            let Some((name, qualifier, rest)) = qualified else {
+                if word == "-" {
+                    return Err(invalid(
+                        "'-' must be directly followed by the term to exclude",
+                    ));
+                }
+                clauses.push(Clause::Term {
+                    column: None,
+                    text: word,
+                    phrase: false,
+                    negated,
+                });
+                continue;
+            };
+
+            // `title:"exact phrase"` — the value is the quoted phrase that follows.
+            let (value, phrase) = if rest.is_empty() && chars.get(i) == Some(&'"') {
+                let (text, next) = read_phrase(&chars, i)?;
+                i = next;
+                (text, true)
+            } else {
+                (rest.to_string(), false)
+            };
+            if value.trim().is_empty() {
+                return Err(invalid(format!("Qualifier '{name}:' needs a value")));
+            }
+
+            match qualifier {
+                Qualifier::Column(column) => clauses.push(Clause::Term {
+                    column: Some(column),
+                    text: value,
+                    phrase,
+                    negated,
+                }),
+                Qualifier::Filter(filter) if negated => {
+                    return Err(invalid(format!(
+                        "Filter '{}:' cannot be negated",
+                        filter.name()
+                    )));
+                }
+                Qualifier::Filter(filter) => clauses.push(Clause::Filter { filter, value }),
+            }
+        }
+
+        if clauses.len() > MAX_QUERY_CLAUSES {
+            return Err(invalid(format!(
+                "Search query has too many terms (max {MAX_QUERY_CLAUSES})"
+            )));
+        }
+
+        for (idx, clause) in clauses.iter().enumerate() {
+            if !matches!(clause, Clause::Or) {
+                continue;
+            }
+            let before = idx.checked_sub(1).and_then(|j| clauses.get(j));
+            let after = clauses.get(idx + 1);
+            if !before.is_some_and(Clause::is_positive_term)
+                || !after.is_some_and(Clause::is_positive_term)
+            {
+                return Err(invalid(
+                    "OR must join two search terms (not filters or -exclusions)",
+                ));
+            }
+        }
+
+        let has_negation = clauses
+            .iter()
+            .any(|c| matches!(c, Clause::Term { negated: true, .. }));
+        if has_negation && !clauses.iter().any(Clause::is_positive_term) {
+            return Err(invalid(
+                "Exclusions (-term) need at least one other search term",
+            ));
+        }
+
+        Ok(Self { clauses })
+    }
+
+    /// Whether the query has anything for FTS5 to match (vs. filters only).
+    pub fn has_terms(&self) -> bool {
+        self.clauses.iter().any(Clause::is_positive_term)
+    }
+
+    /// The query written back in user syntax (used for "did you mean").
+    pub fn render(&self) -> String {
+        self.clauses
+            .iter()
+            .map(Clause::render)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn filters(&self) -> QueryFilters {
+        let collect = |want: Filter| {
+            let slugs: Vec<String> = self
+                .clauses
+                .iter()
+                .filter_map(|c| match c {
+                    Clause::Filter { filter, value } if *filter == want => Some(match want {
+                        Filter::Column => value.trim().to_lowercase(),
+                        _ => slugify(value),
+                    }),
+                    _ => None,
+                })
+                .filter(|s| !s.is_empty())
+                .collect();
+            (!slugs.is_empty()).then(|| serde_json::json!(slugs).to_string())
+        };
+        QueryFilters {
+            tags: collect(Filter::Tag),
+            orgs: collect(Filter::Org),
+            sectors: collect(Filter::Sector),
+            columns: collect(Filter::Column),
+        }
+    }
+
+    /// Compile to an FTS5 MATCH expression for `index`. Only meaningful when
+    /// `has_terms()` is true.
+    pub fn fts_expression(&self, index: SearchIndex, synonyms: &Synonyms) -> String {
+        let mut positive = String::new();
+        let mut negative = Vec::new();
+        let mut op = " AND ";
+        let push = |positive: &mut String, op: &mut &str, group: String| {
+            if !positive.is_empty() {
+                positive.push_str(op);
+            }
+            positive.push_str(&group);
+            *op = " AND ";
+        };
+
+        let mut i = 0;
+        while i < self.clauses.len() {
+            match &self.clauses[i] {
+                Clause::Or => op = " OR ",
+                Clause::Filter { .. } => {}
+                Clause::Term {
+                    column,
+                    text,
+                    phrase,
+                    negated,
+                } => {
+                    let prefix = column
+                        .map(|c| format!("{} : ", c.fts_name(index)))
+                        .unwrap_or_default();
+                    if *negated {
+                        negative.push(format!("{prefix}{}", quote(text)));
+                    } else if column.is_some() || *phrase {
+                        push(
+                            &mut positive,
+                            &mut op,
+                            format!("{prefix}{}", synonyms.expand_term(text)),
+                        );
+                    } else {
+                        // Gather the run of bare words so multi-word synonyms can match.
+                        let mut words = vec![text.as_str()];
+                        while let Some(Clause::Term {
+                            column: None,
+                            text,
+                            phrase: false,
+                            negated: false,
+                        }) = self.clauses.get(i + 1)
+                        {
+                            words.push(text);
+                            i += 1;
+                        }
+                        for group in synonyms.expand_words(&words) {
+                            push(&mut positive, &mut op, group);
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        if negative.is_empty() {
+            positive
+        } else {
+            format!("({positive}) NOT {}", negative.join(" NOT "))
+        }
+    }
+}
+
+/// Read a double-quoted phrase starting at `chars[start] == '"'`.
+/// Returns the phrase text and the index just past the closing quote.
+fn read_phrase(chars: &[char], start: usize) -> Result<(String, usize), AppError> {
+    let end = chars[start + 1..]
+        .iter()
+        .position(|&c| c == '"')
+        .map(|p| start + 1 + p)
+        .ok_or_else(|| invalid("Unterminated quote in search query"))?;
+    let text: String = chars[start + 1..end].iter().collect();
+    if text.trim().is_empty() {
+        return Err(invalid("Empty phrase in search query"));
+    }
+    Ok((text.trim().to_string(), end + 1))
+}
+
+// =============================================================================
+// SPELLING CORRECTION
+// =============================================================================
+
+/// Edit distance allowed for a token of `len` characters.
+fn max_edits(len: usize) -> usize {
+    if len <= 4 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Levenshtein distance over Unicode scalar values.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Closest vocabulary term to `token`, or `None` if the token is already
+/// indexed or nothing is within `max_edits`. Ties go to the more common term.
+async fn closest_term(
+    db: &SqlitePool,
+    index: SearchIndex,
+    token: &str,
+) -> Result<Option<String>, AppError> {
+    let len = token.chars().count();
+    let max = max_edits(len);
+    let sql = format!(
+        "SELECT term, doc FROM {} WHERE length(term) BETWEEN ?1 AND ?2",
+        index.vocab_table()
+    );
+    let candidates: Vec<(String, i64)> = sqlx::query_as(&sql)
+        .bind(len.saturating_sub(max) as i64)
+        .bind((len + max) as i64)
+        .fetch_all(db)
+        .await?;
+
+    if candidates.iter().any(|(term, _)| term == token) {
+        return Ok(None);
+    }
+
+    Ok(candidates
+        .into_iter()
+        .map(|(term, doc)| (levenshtein(token, &term), doc, term))
+        .filter(|(dist, _, _)| *dist <= max)
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, _, term)| term))
+}
+
+/// Rewrite `query` with misspelled bare words replaced by their closest
+/// indexed term. Phrases are left alone. Returns `None` when nothing changed.
+pub async fn correct_query(
+    db: &SqlitePool,
+    index: SearchIndex,
+    query: &SearchQuery,
+    synonyms: &Synonyms,
+) -> Result<Option<SearchQuery>, AppError> {
+    let mut changed = false;
+    let mut corrected = query.clone();
+    for clause in &mut corrected.clauses {
+        let Clause::Term {
+            text,
+            phrase: false,
+            ..
+        } = clause
+        else {
+            continue;
+        };
+        let norm = normalize(text);
+        let correctable = norm.chars().count() >= MIN_CORRECTABLE_LEN
+            && norm.chars().all(char::is_alphabetic)
+            && !synonyms.contains(&norm);
+        if !correctable {
+            continue;
+        }
+        if let Some(term) = closest_term(db, index, &norm).await? {
+            *text = term;
+            changed = true;
+        }
+    }
+    Ok(changed.then_some(corrected))
+}
+
+/// Run `run` with the compiled FTS expression. If nothing matches, retry once
+/// with the spelling-corrected query; the corrected query (in user syntax) is
+/// returned alongside the rows only when the retry found something.
+pub async fn search_with_fallback<T, F, Fut>(
+    db: &SqlitePool,
+    index: SearchIndex,
+    query: &SearchQuery,
+    run: F,
+) -> Result<(Vec<T>, Option<String>), AppError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, AppError>>,
+{
+    let synonyms = Synonyms::load(db).await?;
+    let rows = run(query.fts_expression(index, &synonyms)).await?;
+    if !rows.is_empty() {
+        return Ok((rows, None));
+    }
+
+    let Some(corrected) = correct_query(db, index, query, &synonyms).await? else {
+        return Ok((rows, None));
+    };
+    let retried = run(corrected.fts_expression(index, &synonyms)).await?;
+    if retried.is_empty() {
+        return Ok((rows, None));
+    }
+    Ok((retried, Some(corrected.render())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> SearchQuery {
+        SearchQuery::parse(input).unwrap_or_else(|e| panic!("{input:?} should parse: {e}"))
+    }
+
+    fn error(input: &str) -> String {
+        match SearchQuery::parse(input) {
+            Ok(q) => panic!("{input:?} should not parse, got {:?}", q.render()),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn fts(input: &str) -> String {
+        parse(input).fts_expression(SearchIndex::Datasets, &Synonyms(HashMap::new()))
+    }
+
+    #[test]
+    fn bare_words_are_anded() {
+        assert_eq!(fts("crop yield"), r#""crop" AND "yield""#);
+    }
+
+    #[test]
+    fn phrases_and_or() {
+        assert_eq!(
+            fts(r#""crop yield" OR rainfall"#),
+            r#""crop yield" OR "rainfall""#
+        );
+        assert_eq!(fts("a AND b"), r#""a" AND "b""#);
+    }
+
+    #[test]
+    fn column_qualifiers_restrict_the_fts_column() {
+        assert_eq!(fts("title:aadhaar"), r#"title : "aadhaar""#);
+        assert_eq!(fts("About:census"), r#"about_dataset : "census""#);
+        assert_eq!(
+            parse("title:census").fts_expression(SearchIndex::Models, &Synonyms(HashMap::new())),
+            r#"title : "census""#
+        );
+        assert_eq!(fts(r#"title:"exact phrase""#), r#"title : "exact phrase""#);
+    }
+
+    #[test]
+    fn exclusions() {
+        assert_eq!(fts("census -synthetic"), r#"("census") NOT "synthetic""#);
+        assert_eq!(
+            fts("census -title:draft"),
+            r#"("census") NOT title : "draft""#
+        );
+    }
+
+    #[test]
+    fn unknown_qualifiers_are_plain_text() {
+        for input in [
+            "time:series",
+            "http://x",
+            "ratio 3:1",
+            "foo:",
+            ":bar",
+            "-foo:bar x",
+        ] {
+            let q = parse(input);
+            assert!(q.filters().tags.is_none(), "{input}");
+            assert_eq!(q.render(), input, "{input}");
+        }
+        assert_eq!(fts("ratio 3:1"), r#""ratio" AND "3:1""#);
+        assert_eq!(fts("time:series"), r#""time:series""#);
+    }
+
+    #[test]
+    fn filters_become_slug_arrays() {
+        let q = parse(r#"tag:"Open Data" org:IndiaAI sector:health tags:census rain"#);
+        let f = q.filters();
+        assert_eq!(f.tags.as_deref(), Some(r#"["open-data","census"]"#));
+        assert_eq!(f.orgs.as_deref(), Some(r#"["indiaai"]"#));
+        assert_eq!(f.sectors.as_deref(), Some(r#"["health"]"#));
+        assert!(f.columns.is_none());
+        assert!(q.has_terms());
+    }
+
+    #[test]
+    fn column_filter_keeps_names_lowercased() {
+        let f = parse("column:District_Code col:PIN").filters();
+        assert_eq!(f.columns.as_deref(), Some(r#"["district_code","pin"]"#));
+    }
+
+    #[test]
+    fn filters_only_query_has_no_terms() {
+        let q = parse("tag:census sector:health");
+        assert!(!q.has_terms());
+        assert_eq!(q.render(), "tag:census sector:health");
+    }
+
+    #[test]
+    fn render_round_trips() {
+        for input in [
+            "a OR b",
+            r#""exact phrase" -draft"#,
+            r#"title:"two words" tag:"open data""#,
+            "description:rain",
+        ] {
+            assert_eq!(parse(input).render(), input);
+        }
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(error(r#"census "unclosed"#).contains("Unterminated quote"));
+        assert!(error(r#"census """#).contains("Empty phrase"));
+        assert!(error("title:").contains("needs a value"));
+        assert!(error("-tag:census x").contains("cannot be negated"));
+        assert!(error("-draft").contains("need at least one other"));
+        assert!(error("census -").contains("'-' must be directly followed"));
+        for input in [
+            "OR census",
+            "census OR",
+            "a OR OR b",
+            "tag:x OR b",
+            "a OR -b",
+        ] {
+            assert!(error(input).contains("OR must join"), "{input}");
+        }
+    }
+
+    #[test]
+    fn limits() {
+        assert!(error(&"a".repeat(MAX_QUERY_LEN + 1)).contains("too long"));
+        let many = vec!["w"; MAX_QUERY_CLAUSES + 1].join(" ");
+        assert!(error(&many).contains("too many terms"));
+        parse(&vec!["w"; MAX_QUERY_CLAUSES].join(" "));
+    }
+
+    #[test]
+    fn synonyms_expand_bare_words() {
+        let synonyms = Synonyms(HashMap::from([(
+            "crop".to_string(),
+            vec!["agriculture".to_string()],
+        )]));
+        assert_eq!(
+            parse("crop yield").fts_expression(SearchIndex::Datasets, &synonyms),
+            r#"("crop" OR "agriculture") AND "yield""#
+        );
+    }
+
+    #[test]
+    fn slugify_normalises() {
+        assert_eq!(
+            slugify("  Health & Family  Welfare "),
+            "health-family-welfare"
+        );
+        assert_eq!(slugify("--"), "");
+    }
+}
@@ -0,0 +1,86 @@
+//! Search analytics logging.
+//!
+//! List and search handlers call `record` after running a text search; the
+//! returned event id goes back to the client in the `X-Search-Id` header so a
+//! later click on a result can be attributed to the search that produced it.
+//! Logging never fails the request — errors are only traced.
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use sqlx::SqlitePool;
+use std::time::Instant;
+
+/// Response header carrying the id of the logged search.
+pub const SEARCH_ID_HEADER: HeaderName = HeaderName::from_static("x-search-id");
+
+/// Lowercased, whitespace-collapsed form that reports group by.
+fn normalize(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// A search in flight; create it before running the query so latency
+/// covers the whole request.
+pub struct SearchLog {
+    surface: &'static str,
+    user_id: Option<i64>,
+    started: Instant,
+}
+
+impl SearchLog {
+    pub fn start(surface: &'static str, user_id: Option<i64>) -> Self {
+        Self {
+            surface,
+            user_id,
+            started: Instant::now(),
+        }
+    }
+
+    /// Store the event; returns its id, or `None` if the insert failed.
+    pub async fn record(
+        self,
+        db: &SqlitePool,
+        query: &str,
+        filters: serde_json::Value,
+        result_count: usize,
+        corrected_query: Option<&str>,
+    ) -> Option<i64> {
+        let latency_ms = self.started.elapsed().as_millis() as i64;
+        let res = sqlx::query_scalar(
+            r#"
+            INSERT INTO search_events
+                (user_id, surface, query, normalized_query, filters, result_count, latency_ms, corrected_query)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            RETURNING id
+            "#,
+        )
+        .bind(self.user_id)
+        .bind(self.surface)
+        .bind(query)
+        .bind(normalize(query))
+        .bind(filters.to_string())
+        .bind(result_count as i64)
+        .bind(latency_ms)
+        .bind(corrected_query)
+        .fetch_one(db)
+        .await;
+        match res {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!("failed to log {} search: {e}", self.surface);
+                None
+            }
+        }
+    }
+}
+
+/// `X-Search-Id` for a logged search; empty when nothing was logged.
+pub fn header(search_id: Option<i64>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(id) = search_id {
+        headers.insert(SEARCH_ID_HEADER, HeaderValue::from(id));
+    }
+    headers
+}
@@ -1,13 +1,22 @@
 use crate::config::Config;
+use crate::embeddings::{self, Embedder};
+use crate::mail::{self, Mailer};
+use crate::notifications::Notifier;
+use crate::storage::{self, Storage};
 use anyhow::{Context, Result};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
 use sqlx::ConnectOptions;
 use std::str::FromStr;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: SqlitePool,
     pub config: Config,
+    pub embedder: Arc<dyn Embedder>,
+    pub storage: Arc<dyn Storage>,
+    pub mailer: Arc<dyn Mailer>,
+    pub notifier: Notifier,
 }
 
 impl AppState {
@@ -27,6 +36,17 @@ impl AppState {
             .await
             .context("failed to open SQLite database; run `cargo run --bin db_setup` first")?;
 
-        Ok(Self { db, config })
+        let embedder = embeddings::from_config(&config);
+        let storage = storage::from_config(&config);
+        let mailer = mail::from_config(&config);
+
+        Ok(Self {
+            db,
+            config,
+            embedder,
+            storage,
+            mailer,
+            notifier: Notifier::new(),
+        })
     }
-}
\ No newline at end of file
+}
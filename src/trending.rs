@@ -0,0 +1,139 @@
+//! Trending rankings.
+//!
+//! Raw `views_count`/`downloads_count` favor whatever has been around
+//! longest. A trending score instead sums recent view, like and download
+//! events, each weighted by how much intent it shows and halved every
+//! `TRENDING_HALF_LIFE_HOURS` of age. `spawn_refresher` recomputes every
+//! score into `trending_scores`, which `/api/trending` and `sort=trending`
+//! on the list endpoints read.
+
+use sqlx::{FromRow, SqlitePool};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::errors::AppError;
+use crate::models::ArtifactKind;
+use crate::state::AppState;
+
+/// Events older than this no longer count (at the default half-life they'd
+/// weigh about a thousandth of a fresh one anyway).
+const HORIZON: &str = "-30 days";
+
+const VIEW_WEIGHT: f64 = 1.0;
+const LIKE_WEIGHT: f64 = 3.0;
+const DOWNLOAD_WEIGHT: f64 = 5.0;
+
+/// Events per entry, action and whole hour of age.
+#[derive(FromRow)]
+struct Bucket {
+    kind: String,
+    id: i64,
+    action: String,
+    age_hours: i64,
+    events: i64,
+}
+
+#[derive(Default)]
+struct Score {
+    score: f64,
+    views: i64,
+    likes: i64,
+    downloads: i64,
+}
+
+/// Recompute every trending score; returns how many entries have one.
+pub async fn refresh(db: &SqlitePool, half_life_hours: f64) -> Result<usize, AppError> {
+    let buckets = sqlx::query_as::<_, Bucket>(
+        r#"
+        WITH events AS (
+            SELECT artifact_kind AS kind, artifact_id AS id, 'view' AS action, created_at
+            FROM view_events WHERE created_at >= datetime('now', ?1)
+            UNION ALL
+            SELECT artifact_kind, artifact_id, 'like', created_at
+            FROM likes WHERE created_at >= datetime('now', ?1)
+              AND artifact_kind IN ('dataset', 'model', 'usecase')
+            UNION ALL
+            SELECT artifact_kind, artifact_id, 'download', created_at
+            FROM download_events WHERE created_at >= datetime('now', ?1)
+        )
+        SELECT kind, id, action,
+               MAX(CAST((julianday('now') - julianday(created_at)) * 24 AS INTEGER), 0) AS age_hours,
+               COUNT(*) AS events
+        FROM events
+        GROUP BY kind, id, action, age_hours
+        "#,
+    )
+    .bind(HORIZON)
+    .fetch_all(db)
+    .await?;
+
+    let mut scores: HashMap<(String, i64), Score> = HashMap::new();
+    for b in buckets {
+        let entry = scores.entry((b.kind, b.id)).or_default();
+        let weight = match b.action.as_str() {
+            "view" => {
+                entry.views += b.events;
+                VIEW_WEIGHT
+            }
+            "like" => {
+                entry.likes += b.events;
+                LIKE_WEIGHT
+            }
+            _ => {
+                entry.downloads += b.events;
+                DOWNLOAD_WEIGHT
+            }
+        };
+        // Age at the middle of the hour bucket.
+        let decay = 0.5f64.powf((b.age_hours as f64 + 0.5) / half_life_hours);
+        entry.score += weight * b.events as f64 * decay;
+    }
+
+    let mut tx = db.begin().await?;
+    sqlx::query("DELETE FROM trending_scores")
+        .execute(&mut *tx)
+        .await?;
+    for ((kind, id), s) in &scores {
+        sqlx::query(
+            r#"
+            INSERT INTO trending_scores (artifact_kind, artifact_id, score, views, likes, downloads)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(kind)
+        .bind(id)
+        .bind(s.score)
+        .bind(s.views)
+        .bind(s.likes)
+        .bind(s.downloads)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(scores.len())
+}
+
+/// Current scores for one kind, by entry id; entries without a row score 0.
+pub async fn scores(db: &SqlitePool, kind: ArtifactKind) -> Result<HashMap<i64, f64>, AppError> {
+    let rows: Vec<(i64, f64)> =
+        sqlx::query_as("SELECT artifact_id, score FROM trending_scores WHERE artifact_kind = ?1")
+            .bind(kind.as_str())
+            .fetch_all(db)
+            .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Recompute scores every `trending_refresh_interval`, starting right away.
+pub fn spawn_refresher(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.trending_refresh_interval);
+        loop {
+            ticker.tick().await;
+            match refresh(&state.db, state.config.trending_half_life_hours).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("refreshed trending scores for {n} entries"),
+                Err(e) => tracing::error!("trending refresh failed: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,366 @@
+//! Outgoing webhooks for catalog events.
+//!
+//! Triggers (migration 0019) append dataset/model changes to `catalog_events`.
+//! `spawn_dispatcher` periodically fans new events out to every active
+//! subscription that asked for them, as `webhook_deliveries` rows whose JSON
+//! body is fixed at that point, then POSTs the deliveries that are due.
+//! Failures are retried with exponential backoff until `webhook_max_attempts`.
+//! Dispatched events and finished deliveries are pruned after
+//! `webhook_retention_days`.
+//!
+//! Each request carries `X-Aikosh-Signature: sha256=<hex>`, an HMAC-SHA256 of
+//! `"{X-Aikosh-Timestamp}.{body}"` keyed with the subscription's secret, so a
+//! receiver can check both origin and freshness.
+
+use futures::stream::{self, StreamExt};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::{FromRow, SqlitePool};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::errors::AppError;
+use crate::state::AppState;
+
+/// Events a subscription can ask for.
+pub const EVENTS: [&str; 8] = [
+    "dataset.created",
+    "dataset.updated",
+    "dataset.published",
+    "dataset.deleted",
+    "model.created",
+    "model.updated",
+    "model.published",
+    "model.deleted",
+];
+
+/// Sent on demand to check a receiver; never produced by the catalog.
+pub const PING_EVENT: &str = "ping";
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const DELIVERY_BATCH: i64 = 50;
+/// Deliveries in flight at once, so one slow receiver can't stall the batch.
+const DELIVERY_CONCURRENCY: usize = 8;
+const FAN_OUT_BATCH: i64 = 500;
+const MAX_RESPONSE_BODY_LEN: usize = 2000;
+/// How often the dispatcher prunes old events and deliveries.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Backoff never waits longer than this between attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A fresh signing secret for a new subscription.
+pub fn new_secret() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// JSON body for a delivery; `data` describes the entry, or just identifies
+/// it if the row is gone.
+pub fn payload(event: &str, occurred_at: &str, data: serde_json::Value) -> String {
+    serde_json::json!({
+        "event": event,
+        "occurred_at": occurred_at,
+        "data": data,
+    })
+    .to_string()
+}
+
+#[derive(FromRow)]
+struct PendingEvent {
+    id: i64,
+    event: String,
+    artifact_kind: String,
+    artifact_id: i64,
+    created_at: String,
+}
+
+#[derive(FromRow, serde::Serialize)]
+struct EntrySnapshot {
+    id: i64,
+    title: String,
+    description: String,
+    visibility: String,
+    created_at: String,
+    updated_at: String,
+    deleted_at: Option<String>,
+}
+
+/// Turn undispatched catalog events into deliveries; returns how many were created.
+/// Subscriptions only receive events that happened after they were registered.
+pub async fn fan_out(db: &SqlitePool) -> Result<u64, AppError> {
+    let events = sqlx::query_as::<_, PendingEvent>(
+        r#"
+        SELECT id, event, artifact_kind, artifact_id, created_at
+        FROM catalog_events
+        WHERE dispatched_at IS NULL
+        ORDER BY id
+        LIMIT ?1
+        "#,
+    )
+    .bind(FAN_OUT_BATCH)
+    .fetch_all(db)
+    .await?;
+
+    let mut created = 0;
+    for e in events {
+        let table = match e.artifact_kind.as_str() {
+            "dataset" => "datasets",
+            _ => "models",
+        };
+        let entry = sqlx::query_as::<_, EntrySnapshot>(&format!(
+            r#"
+            SELECT id, title, description, visibility, created_at, updated_at, deleted_at
+            FROM {table} WHERE id = ?1
+            "#
+        ))
+        .bind(e.artifact_id)
+        .fetch_optional(db)
+        .await?;
+        let mut data = match entry {
+            Some(entry) => serde_json::to_value(entry)?,
+            None => serde_json::json!({ "id": e.artifact_id }),
+        };
+        if let Some(obj) = data.as_object_mut() {
+            obj.insert("kind".to_string(), serde_json::json!(e.artifact_kind));
+        }
+        let body = payload(&e.event, &e.created_at, data);
+
+        let mut tx = db.begin().await?;
+        let res = sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, catalog_event_id, event, payload)
+            SELECT w.id, ?1, ?2, ?3
+            FROM webhooks w
+            WHERE w.active = 1
+              AND w.created_at <= ?4
+              AND EXISTS (SELECT 1 FROM json_each(w.events) j WHERE j.value = ?2)
+            "#,
+        )
+        .bind(e.id)
+        .bind(&e.event)
+        .bind(&body)
+        .bind(&e.created_at)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE catalog_events SET dispatched_at = datetime('now') WHERE id = ?1")
+            .bind(e.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        created += res.rows_affected();
+    }
+    Ok(created)
+}
+
+#[derive(FromRow)]
+struct DueDelivery {
+    id: i64,
+    event: String,
+    payload: String,
+    attempts: i64,
+    url: String,
+    secret: String,
+}
+
+/// Result of one POST: the receiver's status and body, or why there was none.
+struct Attempt {
+    status: Option<u16>,
+    body: Option<String>,
+    error: Option<String>,
+}
+
+impl Attempt {
+    fn succeeded(&self) -> bool {
+        self.status.is_some_and(|s| (200..300).contains(&s))
+    }
+}
+
+async fn attempt(client: &reqwest::Client, d: &DueDelivery) -> Attempt {
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign(&d.secret, timestamp, &d.payload);
+    let result = client
+        .post(&d.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header("content-type", "application/json")
+        .header("user-agent", "aikosh-webhooks")
+        .header("x-aikosh-event", &d.event)
+        .header("x-aikosh-delivery", d.id.to_string())
+        .header("x-aikosh-timestamp", timestamp.to_string())
+        .header("x-aikosh-signature", format!("sha256={signature}"))
+        .body(d.payload.clone())
+        .send()
+        .await;
+    match result {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            Attempt {
+                status: Some(status),
+                body: Some(body.chars().take(MAX_RESPONSE_BODY_LEN).collect()),
+                error: (!(200..300).contains(&status))
+                    .then(|| format!("receiver returned {status}")),
+            }
+        }
+        Err(e) => Attempt {
+            status: None,
+            body: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Delay before attempt `attempts + 1`: base, 2x base, 4x base, ... capped.
+fn retry_delay(config: &Config, attempts: i64) -> Duration {
+    let factor = 2u32.saturating_pow(u32::try_from(attempts - 1).unwrap_or(0));
+    config
+        .webhook_retry_base
+        .saturating_mul(factor)
+        .min(MAX_RETRY_DELAY)
+}
+
+/// POST every delivery that is due; returns (succeeded, given up on).
+pub async fn deliver_due(
+    db: &SqlitePool,
+    client: &reqwest::Client,
+    config: &Config,
+) -> Result<(u64, u64), AppError> {
+    let due = sqlx::query_as::<_, DueDelivery>(
+        r#"
+        SELECT d.id, d.event, d.payload, d.attempts, w.url, w.secret
+        FROM webhook_deliveries d
+        JOIN webhooks w ON w.id = d.webhook_id
+        WHERE d.status = 'pending'
+          AND d.next_attempt_at <= datetime('now')
+          AND w.active = 1
+        ORDER BY d.next_attempt_at, d.id
+        LIMIT ?1
+        "#,
+    )
+    .bind(DELIVERY_BATCH)
+    .fetch_all(db)
+    .await?;
+
+    let outcomes: Vec<_> = stream::iter(due)
+        .map(|d| deliver(db, client, config, d))
+        .buffer_unordered(DELIVERY_CONCURRENCY)
+        .collect()
+        .await;
+    let (mut succeeded, mut failed) = (0, 0);
+    for outcome in outcomes {
+        match outcome {
+            Ok("succeeded") => succeeded += 1,
+            Ok("failed") => failed += 1,
+            Ok(_) => {}
+            // The row stays due and is retried on the next run.
+            Err(e) => tracing::error!("recording webhook delivery failed: {e}"),
+        }
+    }
+    Ok((succeeded, failed))
+}
+
+/// POST one delivery and record the attempt; returns its new status.
+async fn deliver(
+    db: &SqlitePool,
+    client: &reqwest::Client,
+    config: &Config,
+    d: DueDelivery,
+) -> Result<&'static str, AppError> {
+    let outcome = attempt(client, &d).await;
+    let attempts = d.attempts + 1;
+    let status = if outcome.succeeded() {
+        "succeeded"
+    } else if attempts >= config.webhook_max_attempts {
+        "failed"
+    } else {
+        "pending"
+    };
+    let delay = retry_delay(config, attempts);
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries SET
+            status          = ?1,
+            attempts        = ?2,
+            last_attempt_at = datetime('now'),
+            next_attempt_at = datetime('now', ?3),
+            response_status = ?4,
+            response_body   = ?5,
+            error           = ?6
+        WHERE id = ?7
+        "#,
+    )
+    .bind(status)
+    .bind(attempts)
+    .bind(format!("+{} seconds", delay.as_secs()))
+    .bind(outcome.status)
+    .bind(outcome.body)
+    .bind(outcome.error)
+    .bind(d.id)
+    .execute(db)
+    .await?;
+    Ok(status)
+}
+
+/// Delete dispatched events and finished deliveries older than `retention_days`;
+/// returns (events, deliveries) removed. Pending deliveries are always kept.
+pub async fn prune(db: &SqlitePool, retention_days: i64) -> Result<(u64, u64), AppError> {
+    let cutoff = format!("-{retention_days} days");
+    let deliveries = sqlx::query(
+        r#"
+        DELETE FROM webhook_deliveries
+        WHERE status IN ('succeeded', 'failed')
+          AND COALESCE(last_attempt_at, created_at) < datetime('now', ?1)
+        "#,
+    )
+    .bind(&cutoff)
+    .execute(db)
+    .await?;
+    let events =
+        sqlx::query("DELETE FROM catalog_events WHERE dispatched_at < datetime('now', ?1)")
+            .bind(&cutoff)
+            .execute(db)
+            .await?;
+    Ok((events.rows_affected(), deliveries.rows_affected()))
+}
+
+/// Fan out and deliver every `webhook_dispatch_interval`.
+pub fn spawn_dispatcher(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut ticker = tokio::time::interval(state.config.webhook_dispatch_interval);
+        let mut last_pruned: Option<tokio::time::Instant> = None;
+        loop {
+            ticker.tick().await;
+            match fan_out(&state.db).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("queued {n} webhook deliveries"),
+                Err(e) => tracing::error!("webhook fan-out failed: {e}"),
+            }
+            match deliver_due(&state.db, &client, &state.config).await {
+                Ok((0, 0)) => {}
+                Ok((ok, failed)) => {
+                    tracing::info!("delivered {ok} webhooks, gave up on {failed}")
+                }
+                Err(e) => tracing::error!("webhook delivery failed: {e}"),
+            }
+            if last_pruned.is_some_and(|t| t.elapsed() < PRUNE_INTERVAL) {
+                continue;
+            }
+            last_pruned = Some(tokio::time::Instant::now());
+            match prune(&state.db, state.config.webhook_retention_days).await {
+                Ok((0, 0)) => {}
+                Ok((events, deliveries)) => tracing::info!(
+                    "pruned {events} catalog events and {deliveries} webhook deliveries"
+                ),
+                Err(e) => tracing::error!("webhook prune failed: {e}"),
+            }
+        }
+    });
+}